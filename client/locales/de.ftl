@@ -0,0 +1,381 @@
+## Deutsche Meldungen der stitch-CLI und -TUI. Argumente in { $klammern }
+## werden zur Laufzeit eingesetzt und müssen erhalten bleiben.
+
+## Allgemein
+
+config-load-failed = Warnung: Konfiguration konnte nicht geladen werden: { $error }
+no-channels-selected = Keine Kanäle ausgewählt
+no-channel-names = Keine Kanalnamen gefunden
+operation-cancelled = Vorgang abgebrochen
+request-retrying = Anfrage fehlgeschlagen ({ $error }), neuer Versuch in { $delay } s … (noch { $left } Versuche)
+request-id = Anfrage-ID: { $id }
+hint = Tipp: { $hint }
+read-failed = { $path } konnte nicht gelesen werden
+
+## Tipps zu Fehlergründen des Servers
+
+hint-channel-not-tracked = mit `stitch list` die verfolgten Kanäle anzeigen
+hint-twitch-user-not-found = Schreibweise des Kanalnamens prüfen
+hint-twitch-rate-limited = Twitch drosselt den Server; gleich noch einmal versuchen
+hint-eventsub-quota-exceeded = das EventSub-Abonnementlimit ist erreicht; ungenutzte Kanäle entfernen
+hint-database-unavailable = die Datenbank des Servers ist nicht erreichbar; später erneut versuchen
+hint-discord-permission-denied = Discord-Bot-Token des Servers prüfen und ob der Bot in seinem Kanal schreiben darf
+hint-discord-unavailable = Discord war nicht erreichbar; gleich noch einmal versuchen
+
+## Verbindung
+
+tls-not-verifying = Warnung: Das TLS-Zertifikat des Servers wird nicht geprüft
+invalid-server-url = Ungültige Server-URL
+invalid-tls-config = Ungültige TLS-Konfiguration
+connect-retrying = Verbindung fehlgeschlagen, neuer Versuch in { $delay } s … (noch { $left } Versuche)
+connect-failed = Keine Verbindung zum Stitch-Server unter { $server }
+connect-solutions-heading = Mögliche Lösungen
+connect-solutions =
+    { $heading }:
+      • Server starten: { $command }
+      • Prüfen, ob der Server auf einem anderen Port läuft
+      • Netzwerkverbindung prüfen
+
+    Einen anderen Server wählen mit: { $flag }
+
+## Verfolgen
+
+tracked = Kanal wird jetzt verfolgt: { $name }
+tracked-live = { $name } wird verfolgt — gerade LIVE
+tracked-live-playing = { $name } wird verfolgt — gerade LIVE mit { $category }
+already-tracked = Kanal '{ $name }' wird bereits verfolgt
+track-failed = Kanal '{ $name }' konnte nicht verfolgt werden: { $error }
+read-stdin-failed = Kanalnamen konnten nicht von stdin gelesen werden
+validate-failed = Kanäle konnten nicht geprüft werden: { $error }
+dry-run-track = Probelauf: würde { $channels } Kanal/Kanäle verfolgen und { $subscriptions } EventSub-Abonnement(s) anlegen. Nichts wurde geändert.
+dry-run-untrack = Probelauf: würde { $channels } Kanal/Kanäle nicht mehr verfolgen und { $subscriptions } EventSub-Abonnement(s) entfernen. Nichts wurde geändert.
+dry-run-failures = { $failed } von { $total } Kanal/Kanälen würden fehlschlagen
+outcome-would-track = würde verfolgt
+outcome-would-untrack = würde entfernt
+outcome-already-tracked = bereits verfolgt
+outcome-not-tracked = nicht verfolgt
+outcome-not-found = nicht gefunden
+outcome-invalid = ungültig
+outcome-lookup-failed = Abfrage fehlgeschlagen
+result-tracked = verfolgt
+result-untracked = entfernt
+result-skipped = übersprungen
+result-failed = fehlgeschlagen
+track-summary = { $tracked } verfolgt, { $skipped } übersprungen, { $failed } fehlgeschlagen
+track-failures = { $count } Kanal/Kanäle konnten nicht verfolgt werden
+rate-limited-waiting = { $name } (Ratenlimit, warte { $seconds } s)
+no-channel-names-given = Keine Kanalnamen angegeben
+no-channels-tracked = Es werden keine Kanäle verfolgt
+picker-done = Fertig ({ $count } ausgewählt)
+picker-live = live
+picker-prompt = { $prompt } (Enter wählt aus, Esc beendet)
+pick-untrack = Nicht mehr zu verfolgende Kanäle
+
+## Entfernen
+
+confirm-choices = [j/N]
+confirm-yes = j
+untrack-confirm = Soll '{ $name }' wirklich nicht mehr verfolgt werden?
+untracked = Kanal wird nicht mehr verfolgt: { $name }
+untrack-failed = Kanal '{ $name }' konnte nicht entfernt werden: { $error }
+untrack-many-confirm = Sollen { $count } Kanäle ({ $names }) wirklich nicht mehr verfolgt werden?
+untrack-many-failed = Kanäle konnten nicht entfernt werden: { $error }
+untrack-summary = { $untracked } entfernt, { $failed } fehlgeschlagen
+untrack-failures = { $count } Kanal/Kanäle konnten nicht entfernt werden
+
+## Aufräumen
+
+prune-failed = Inaktive Kanäle konnten nicht ermittelt werden: { $error }
+prune-none = Alle Kanäle haben in den letzten { $days } Tagen gestreamt
+prune-found = { $count } Kanal/Kanäle ohne Stream in den letzten { $days } Tagen
+prune-last-stream = { $date }, vor { $days } Tagen
+prune-never-streamed = nie
+prune-needs-yes = Mit --yes ohne Nachfrage entfernen oder mit --dry-run nur auflisten
+prune-prompt = Zu entfernende Kanäle (Leertaste wählt aus, Enter entfernt, Esc bricht ab)
+
+## Auflisten
+
+list-failed = Kanäle konnten nicht aufgelistet werden
+live-badge = LIVE
+stream-ended = beendet
+channel-broken = keine Ereignisse
+no-channels-found = Keine Kanäle gefunden
+total-channels = Kanäle insgesamt: { $count }
+watch-needs-table = --watch funktioniert nur mit Tabellenausgabe
+watch-heading = Alle { $seconds } s: stitch list
+
+## Status und Öffnen von Kanälen
+
+status-failed = Status von '{ $name }' konnte nicht abgerufen werden: { $error }
+status-is = { $name } ist { $state }
+status-offline = offline
+label-title = Titel:
+label-category = Kategorie:
+label-uptime = Laufzeit:
+filter-failed = Filter von '{ $name }' konnten nicht abgerufen werden: { $error }
+filter-update-failed = Filter von '{ $name }' konnten nicht geändert werden: { $error }
+filter-updated = Filter von { $name } geändert
+label-categories = Kategorien:
+label-muted-keywords = Stummgeschaltete Begriffe:
+filter-any-category = alle
+filter-no-keywords = keine
+open-failed = '{ $name }' konnte nicht geöffnet werden: { $error }
+opened = { $url } geöffnet
+tui-copied = { $text } kopiert
+tui-copy-failed = Kopieren in die Zwischenablage fehlgeschlagen: { $error }
+browser-launch-failed = Browser für { $url } konnte nicht gestartet werden
+browser-failed = { $url } konnte nicht geöffnet werden ({ $status })
+
+## Livestreams und Abonnements
+
+live-failed = Livestreams konnten nicht aufgelistet werden
+nobody-live = Gerade ist niemand live
+live-total = Live-Kanäle: { $count }
+subscriptions-failed = Abonnements konnten nicht aufgelistet werden: { $error }
+no-subscriptions = Keine passenden Abonnements
+subscription-untracked = { $id } (nicht verfolgt)
+subscriptions-total = Abonnements: { $total } ({ $enabled } aktiv)
+
+## Versionen
+
+label-client = Client:
+label-server = Server:
+label-warning = WARNUNG:
+server-version-failed = Serverversion konnte nicht abgerufen werden: { $error }
+version-with-protocol = { $version } (Protokoll { $protocol })
+protocol-unknown = unbekannt
+protocol-mismatch = Client und Server wurden aus verschiedenen Ständen der API gebaut; neuere Befehle werden eventuell nicht unterstützt
+versions-compatible = Client und Server sind kompatibel
+major-version-mismatch = { $warning } stitch { $client } spricht mit stitch-server { $server }, einer anderen Hauptversion. Befehle können fehlschlagen oder sich falsch verhalten; die ältere Seite aktualisieren.
+
+## Benachrichtigungen und Wartung
+
+notify-sent = Testnachricht im Discord-Kanal { $channel } gepostet
+notify-failed = Testbenachrichtigung konnte nicht gesendet werden: { $error }
+resyncing = Abonnements werden abgeglichen …
+resync-failed = Abonnements konnten nicht abgeglichen werden: { $error }
+change-added = hinzugefügt
+change-removed = entfernt
+change-stale = veraltet
+resync-in-sync = Abonnements sind bereits abgeglichen
+resync-summary = Hinzugefügt: { $added }, entfernt: { $removed }, veraltet: { $stale }, fehlgeschlagen: { $failed }, behalten: { $kept }
+resync-failures = { $count } Abonnementänderung(en) konnten nicht angewendet werden
+
+## Verlauf und Statistiken
+
+history-failed = Verlauf von '{ $name }' konnte nicht abgerufen werden: { $error }
+no-streams-for = Keine Streams für '{ $name }' aufgezeichnet
+duration-live = { $duration } (live)
+stats-failed = Statistiken konnten nicht abgerufen werden: { $error }
+no-streams = Noch keine Streams aufgezeichnet
+chart-all-channels = Alle Kanäle
+chart-weekly-total = { $hours } h in { $weeks } Woche(n)
+chart-busiest = , meiste { $hours } h (Woche ab { $week })
+chart-top-total = Anteile an { $hours } h über die { $count } häufigsten Kategorien
+top-failed = Häufigste Kategorien konnten nicht abgerufen werden: { $error }
+
+## Ereignisse und Logs
+
+events-failed = Ereignisse konnten nicht beobachtet werden: { $error }
+events-watching = Warte auf Ereignisse, Strg+C beendet
+events-interrupted = Ereignisstrom unterbrochen
+events-closed = Der Server hat den Ereignisstrom geschlossen
+logs-failed = Logs konnten nicht gelesen werden: { $error }
+logs-interrupted = Logstrom unterbrochen
+event-online = ONLINE
+event-offline = OFFLINE
+event-update = ÄNDERUNG
+
+## Anmeldung und Konfiguration
+
+token-stdin-failed = Token konnte nicht von stdin gelesen werden
+token-prompt = API-Token für { $server }
+no-token = Kein Token angegeben
+token-saved-keyring = Token für { $server } im Schlüsselbund gespeichert
+keyring-fallback = Schlüsselbund nicht verfügbar ({ $error }), Token wird in der Konfigurationsdatei gespeichert
+token-saved-file = Token in { $path } gespeichert
+keyring-unavailable = Schlüsselbund nicht verfügbar: { $error }
+token-removed = Gespeichertes Token für { $server } entfernt
+no-saved-token = Kein gespeichertes Token für { $server }
+config-set = { $key } = { $value } gesetzt
+editor-launch-failed = Editor '{ $editor }' konnte nicht gestartet werden
+editor-failed = Editor beendet mit { $status }
+config-saved = Konfiguration gespeichert
+config-problems = Die Konfiguration hat Fehler: { $error }
+config-edit-again = Mit `stitch config edit` erneut bearbeiten, um sie zu beheben
+config-valid = { $path } ist gültig
+export-failed = Daten konnten nicht exportiert werden
+create-failed = { $path } konnte nicht angelegt werden
+export-interrupted = Export unterbrochen
+exported = Daten nach { $path } exportiert
+
+## Einrichtungsassistent
+
+setup-welcome = Willkommen beim Stitch-Einrichtungsassistenten!
+setup-intro = Dieser Assistent hilft bei der ersten Einrichtung von Stitch.
+setup-overwrite = Die Konfigurationsdatei existiert bereits. Überschreiben?
+setup-cancelled = Einrichtung abgebrochen. Die bestehende Konfiguration bleibt erhalten.
+setup-server = Adresse des Stitch-Servers
+setup-server-default = (Standard: { $server })
+setup-server-scheme = Die Serveradresse muss mit http:// oder https:// beginnen
+setup-server-invalid = Ungültige Serveradresse
+setup-output = Standard-Ausgabeformat
+setup-color = Farbige Ausgabe aktivieren?
+setup-saved = Konfiguration in { $path } gespeichert
+setup-skip = überspringen
+setup-completions = Shell-Vervollständigung erzeugen für
+setup-completions-install = So wird die Vervollständigung für { $shell } installiert:
+setup-step-mkdir = Verzeichnis anlegen, falls es fehlt:
+setup-step-generate = Vervollständigung erzeugen und installieren:
+setup-step-reload = Shell neu laden:
+setup-step-zshrc = Zu ~/.zshrc hinzufügen, falls noch nicht vorhanden:
+setup-step-fish = Fish legt das Verzeichnis selbst an, einfach ausführen:
+setup-step-powershell = Zum PowerShell-Profil hinzufügen:
+setup-step-powershell-reload = Danach das Profil neu laden:
+setup-completions-try = Nach der Installation nach 'stitch' die TAB-Taste drücken, um die Vervollständigung zu sehen!
+setup-test = Verbindung zum Server testen?
+setup-testing = Verbindung wird getestet …
+setup-connected = Verbindung zum Server hergestellt!
+setup-connect-failed = Verbindung fehlgeschlagen: { $error }
+setup-server-running = Sicherstellen, dass der Stitch-Server läuft.
+setup-start-server = Server starten mit: { $command }
+setup-complete = Einrichtung abgeschlossen!
+setup-get-started = Erste Schritte:
+setup-cmd-list = Alle Kanäle auflisten
+setup-cmd-track = Einen neuen Kanal verfolgen
+setup-cmd-interactive = Interaktiver Modus
+setup-cmd-help = Hilfe anzeigen
+
+## Interaktiver Modus
+
+tui-title = Stitch TUI
+tui-loaded = Kanäle geladen
+tui-load-failed = Fehler beim Laden der Kanäle: { $error }
+tui-track-failed = Kanal konnte nicht verfolgt werden: { $error }
+tui-untrack-failed = Kanal konnte nicht entfernt werden: { $error }
+tui-tab-channels = Kanäle
+tui-tab-settings = Einstellungen
+tui-tab-dashboard = Übersicht
+tui-add-channel = Kanal hinzufügen:
+tui-add-hint = Enter fügt hinzu, Esc bricht ab
+tui-delete-confirm = '{ $name }' löschen? Y bestätigt, N bricht ab
+tui-search = Suche:
+tui-search-server = Suche auf dem Server:
+tui-search-hint = Strg-S sucht auf dem Server auch in Streamtiteln und Kategorien
+tui-search-failed = Serversuche fehlgeschlagen, durchsuche stattdessen geladene Kanäle: { $error }
+tui-tracking = verfolge { $name }
+tui-untracking = entferne { $name }
+tui-loading = Lädt …
+tui-footer = [{ $quit }] Beenden | [{ $tabs }] Reiter wechseln | [{ $search }] Suche | [?] Hilfe | [{ $refresh }] Aktualisieren
+tui-details = Kanaldetails
+tui-label-id = ID:
+tui-label-name = Name:
+tui-label-alias = Alias:
+tui-label-status = Status:
+tui-label-display-name = Anzeigename:
+tui-label-twitch-id = Twitch-ID:
+tui-label-tracked-since = Verfolgt seit:
+tui-label-last-title = Letzter Titel:
+tui-label-last-stream = Letzter Stream:
+tui-label-total = Gesamt:
+tui-last-stream = { $date }, { $duration }
+tui-total = { $hours } h in { $streams } Stream(s)
+tui-paused = pausiert
+tui-broken = Empfängt keine Ereignisse: { $reason }
+tui-setting-server = Server-URL
+tui-setting-output-format = Ausgabeformat
+tui-setting-color = Farbige Ausgabe
+tui-setting-refresh-interval = Aktualisierungsintervall (s)
+tui-recent-streams = Letzte { $count } Streams
+tui-weekday-activity = Nach Wochentag (UTC)
+tui-weekdays = Mo Di Mi Do Fr Sa So
+tui-settings-help = [j/k] Auswählen | [Enter] Bearbeiten oder umschalten
+tui-settings-hint = Enter zum Speichern, Esc zum Abbrechen
+tui-settings-saved = { $key } gespeichert
+tui-settings-saved-restart = { $key } gespeichert, Neustart verbindet mit dem neuen Server
+tui-settings-file = Konfigurationsdatei: { $path }
+tui-untracked-undo = { $name } entfernt, u macht es rückgängig
+tui-reconnected = Wieder mit dem Server verbunden
+tui-status-connecting = Verbinde
+tui-status-connected = Verbunden
+tui-status-reconnecting = Verbinde erneut
+tui-status-lost = Getrennt ({ $error }), neuer Versuch in { $seconds } s, Versuch { $attempts }
+tui-status-loaded = Aktualisiert { $time }
+tui-status-stale = Veraltet seit { $time }
+tui-status-not-loaded = Noch nicht geladen
+tui-dashboard-tracked = Verfolgte Kanäle
+tui-dashboard-live = Gerade live
+tui-dashboard-week = Diese Woche gestreamt
+tui-dashboard-categories = Top-Kategorien dieser Woche
+tui-dashboard-streamers = Aktivste Kanäle dieser Woche
+tui-dashboard-empty = Diese Woche noch nichts gestreamt
+tui-dashboard-failed = Übersicht konnte nicht geladen werden: { $error }
+tui-events = Ereignisse
+tui-events-waiting = Warte auf Ereignisse...
+tui-events-disconnected = Nicht abonniert, zweimal e drücken zum Neuverbinden
+tui-layout-save-failed = Layout konnte nicht gespeichert werden: { $error }
+tui-history-title = Streamverlauf von { $name }
+tui-history-empty = Noch keine Streams aufgezeichnet
+tui-history-failed = Streamverlauf konnte nicht geladen werden: { $error }
+tui-history-footer = [j/k] Blättern | [Bild↑/Bild↓] Seite | [Esc] Schließen | [{ $quit }] Beenden
+tui-help = Hilfe
+tui-help-navigation = Navigation
+tui-help-up = Nach oben
+tui-help-down = Nach unten
+tui-help-tabs = Reiter wechseln
+tui-help-page = Eine Seite nach oben oder unten
+tui-help-ends = Zum ersten oder letzten Kanal springen
+tui-help-channels = Kanalverwaltung
+tui-help-add = Neuen Kanal hinzufügen
+tui-help-delete = Ausgewählten Kanal löschen
+tui-help-open = Ausgewählten Kanal im Browser öffnen
+tui-help-copy = URL / Login des ausgewählten Kanals kopieren
+tui-help-refresh = Kanalliste aktualisieren
+tui-help-history = Streamverlauf des ausgewählten Kanals anzeigen
+tui-help-events = Live-Ereignisfeed ein-/ausblenden
+tui-help-undo = Zuletzt entfernten Kanal wieder verfolgen
+tui-help-layout = Layout
+tui-help-layout-details = Detailbereich ein-/ausblenden
+tui-help-layout-split = Kanalliste schmaler oder breiter machen
+tui-help-layout-compact = Kompakte Kanalzeilen ein-/ausschalten
+tui-help-search = Suche
+tui-help-search-start = Suche starten
+tui-help-search-cancel = Suche abbrechen
+tui-help-search-confirm = Suche bestätigen
+tui-help-search-server = Suche in Titeln und Kategorien auf dem Server umschalten
+tui-help-general = Allgemein
+tui-help-toggle = Diese Hilfe ein-/ausblenden
+tui-help-quit = Anwendung beenden
+tui-help-settings-edit = Ausgewählte Einstellung bearbeiten oder umschalten
+tui-help-filter = Filter:
+tui-help-filter-hint = [/] Filtern | [j/k] Blättern | [Esc] Schließen
+tui-help-no-match = Keine passenden Tasten
+
+## Spaltenüberschriften
+
+column-average = Schnitt
+column-categories = Kategorien
+column-category = Kategorie
+column-change = Änderung
+column-channel = Kanal
+column-channels = Kanäle
+column-created = Angelegt
+column-date = Datum
+column-details = Details
+column-display-name = Anzeigename
+column-duration = Dauer
+column-hours = Stunden
+column-id = ID
+column-last-stream = Letzter Stream
+column-live = Live
+column-name = Name
+column-result = Ergebnis
+column-status = Status
+column-streams = Streams
+column-title = Titel
+column-top-games = Top-Spiele
+column-tracked-since = Verfolgt seit
+column-twitch-id = Twitch-ID
+column-type = Typ
+column-uptime = Laufzeit
+column-viewers = Zuschauer
@@ -0,0 +1,381 @@
+## Messages of the stitch CLI and TUI. Arguments in { $braces } are filled in
+## at runtime; keep them when translating.
+
+## General
+
+config-load-failed = Warning: Failed to load config: { $error }
+no-channels-selected = No channels selected
+no-channel-names = No channel names found
+operation-cancelled = Operation cancelled
+request-retrying = Request failed ({ $error }), retrying in { $delay }s... ({ $left } attempts left)
+request-id = request id: { $id }
+hint = hint: { $hint }
+read-failed = Failed to read { $path }
+
+## Hints for server error reasons
+
+hint-channel-not-tracked = run `stitch list` to see tracked channels
+hint-twitch-user-not-found = check the spelling of the channel name
+hint-twitch-rate-limited = Twitch is throttling the server; retry shortly
+hint-eventsub-quota-exceeded = the EventSub subscription limit is reached; untrack unused channels
+hint-database-unavailable = the server's database is down; retry later
+hint-discord-permission-denied = check the server's Discord bot token and that the bot can post in its channel
+hint-discord-unavailable = Discord couldn't be reached; retry shortly
+
+## Connecting
+
+tls-not-verifying = Warning: Not verifying the server's TLS certificate
+invalid-server-url = Invalid server URL
+invalid-tls-config = Invalid TLS configuration
+connect-retrying = Connection failed, retrying in { $delay }s... ({ $left } attempts left)
+connect-failed = Failed to connect to Stitch server at { $server }
+connect-solutions-heading = Possible solutions
+connect-solutions =
+    { $heading }:
+      • Start the server: { $command }
+      • Check if server is running on a different port
+      • Verify network connectivity
+
+    You can specify a different server with: { $flag }
+
+## Tracking
+
+tracked = Successfully tracked channel: { $name }
+tracked-live = Tracked { $name } — currently LIVE
+tracked-live-playing = Tracked { $name } — currently LIVE playing { $category }
+already-tracked = Channel '{ $name }' is already being tracked
+track-failed = Failed to track channel '{ $name }': { $error }
+read-stdin-failed = Failed to read channel names from stdin
+validate-failed = Failed to check channels: { $error }
+dry-run-track = Dry run: would track { $channels } channel(s), creating { $subscriptions } EventSub subscription(s). Nothing was changed.
+dry-run-untrack = Dry run: would untrack { $channels } channel(s), removing { $subscriptions } EventSub subscription(s). Nothing was changed.
+dry-run-failures = { $failed } of { $total } channel(s) would fail
+outcome-would-track = would track
+outcome-would-untrack = would untrack
+outcome-already-tracked = already tracked
+outcome-not-tracked = not tracked
+outcome-not-found = not found
+outcome-invalid = invalid
+outcome-lookup-failed = lookup failed
+result-tracked = tracked
+result-untracked = untracked
+result-skipped = skipped
+result-failed = failed
+track-summary = { $tracked } tracked, { $skipped } skipped, { $failed } failed
+track-failures = { $count } channel(s) could not be tracked
+rate-limited-waiting = { $name } (rate limited, waiting { $seconds }s)
+no-channel-names-given = No channel names given
+no-channels-tracked = No channels are being tracked
+picker-done = Done ({ $count } selected)
+picker-live = live
+picker-prompt = { $prompt } (Enter toggles, Esc finishes)
+pick-untrack = Channels to untrack
+
+## Untracking
+
+confirm-choices = [y/N]
+confirm-yes = y
+untrack-confirm = Are you sure you want to untrack '{ $name }'?
+untracked = Successfully untracked channel: { $name }
+untrack-failed = Failed to untrack channel '{ $name }': { $error }
+untrack-many-confirm = Are you sure you want to untrack { $count } channels ({ $names })?
+untrack-many-failed = Failed to untrack channels: { $error }
+untrack-summary = { $untracked } untracked, { $failed } failed
+untrack-failures = { $count } channel(s) could not be untracked
+
+## Pruning
+
+prune-failed = Failed to find inactive channels: { $error }
+prune-none = Every channel has streamed in the last { $days } days
+prune-found = { $count } channel(s) without a stream in the last { $days } days
+prune-last-stream = { $date }, { $days } days ago
+prune-never-streamed = never
+prune-needs-yes = Pass --yes to untrack them without asking, or --dry-run to only list them
+prune-prompt = Channels to untrack (Space toggles, Enter untracks, Esc cancels)
+
+## Listing
+
+list-failed = Failed to list channels
+live-badge = LIVE
+stream-ended = ended
+channel-broken = no events
+no-channels-found = No channels found
+total-channels = Total channels: { $count }
+watch-needs-table = --watch only works with table output
+watch-heading = Every { $seconds }s: stitch list
+
+## Status and opening channels
+
+status-failed = Failed to get status of '{ $name }': { $error }
+status-is = { $name } is { $state }
+status-offline = offline
+label-title = Title:
+label-category = Category:
+label-uptime = Uptime:
+filter-failed = Failed to get filters of '{ $name }': { $error }
+filter-update-failed = Failed to update filters of '{ $name }': { $error }
+filter-updated = Updated the filters of { $name }
+label-categories = Categories:
+label-muted-keywords = Muted keywords:
+filter-any-category = any
+filter-no-keywords = none
+open-failed = Failed to open '{ $name }': { $error }
+opened = Opened { $url }
+tui-copied = Copied { $text }
+tui-copy-failed = Couldn't copy to the clipboard: { $error }
+browser-launch-failed = Failed to launch a browser for { $url }
+browser-failed = Failed to open { $url } ({ $status })
+
+## Live streams and subscriptions
+
+live-failed = Failed to list live streams
+nobody-live = Nobody is live right now
+live-total = Live channels: { $count }
+subscriptions-failed = Failed to list subscriptions: { $error }
+no-subscriptions = No matching subscriptions
+subscription-untracked = { $id } (untracked)
+subscriptions-total = Subscriptions: { $total } ({ $enabled } enabled)
+
+## Versions
+
+label-client = Client:
+label-server = Server:
+label-warning = WARNING:
+server-version-failed = Failed to get server version: { $error }
+version-with-protocol = { $version } (protocol { $protocol })
+protocol-unknown = unknown
+protocol-mismatch = Client and server were built from different revisions of the API; newer commands may not be supported
+versions-compatible = Client and server are compatible
+major-version-mismatch = { $warning } stitch { $client } is talking to stitch-server { $server }, a different major version. Commands may fail or misbehave; upgrade whichever is older.
+
+## Notifications and maintenance
+
+notify-sent = Posted a test message to Discord channel { $channel }
+notify-failed = Failed to send test notification: { $error }
+resyncing = Resyncing subscriptions...
+resync-failed = Failed to resync subscriptions: { $error }
+change-added = added
+change-removed = removed
+change-stale = stale
+resync-in-sync = Subscriptions are already in sync
+resync-summary = Added: { $added }, removed: { $removed }, stale: { $stale }, failed: { $failed }, kept: { $kept }
+resync-failures = { $count } subscription change(s) could not be applied
+
+## History and statistics
+
+history-failed = Failed to get history of '{ $name }': { $error }
+no-streams-for = No streams recorded for '{ $name }'
+duration-live = { $duration } (live)
+stats-failed = Failed to get stats: { $error }
+no-streams = No streams recorded yet
+chart-all-channels = All channels
+chart-weekly-total = { $hours }h over { $weeks } week(s)
+chart-busiest = , busiest { $hours }h (week of { $week })
+chart-top-total = Shares of { $hours }h across the top { $count } categories
+top-failed = Failed to get top categories: { $error }
+
+## Events and logs
+
+events-failed = Failed to watch events: { $error }
+events-watching = Watching for events, press Ctrl+C to stop
+events-interrupted = Event stream interrupted
+events-closed = Server closed the event stream
+logs-failed = Failed to read logs: { $error }
+logs-interrupted = Log stream interrupted
+event-online = ONLINE
+event-offline = OFFLINE
+event-update = UPDATE
+
+## Login and configuration
+
+token-stdin-failed = Failed to read token from stdin
+token-prompt = API token for { $server }
+no-token = No token given
+token-saved-keyring = Saved token for { $server } in the keyring
+keyring-fallback = Keyring unavailable ({ $error }), saving to the config file
+token-saved-file = Saved token in { $path }
+keyring-unavailable = Keyring unavailable: { $error }
+token-removed = Removed saved token for { $server }
+no-saved-token = No saved token for { $server }
+config-set = Set { $key } = { $value }
+editor-launch-failed = Failed to launch editor '{ $editor }'
+editor-failed = Editor exited with { $status }
+config-saved = Config saved
+config-problems = Config has problems: { $error }
+config-edit-again = Run `stitch config edit` again to fix them
+config-valid = { $path } is valid
+export-failed = Failed to export data
+create-failed = Failed to create { $path }
+export-interrupted = Export interrupted
+exported = Exported data to { $path }
+
+## Setup wizard
+
+setup-welcome = Welcome to Stitch Setup Wizard!
+setup-intro = This wizard will help you configure Stitch for first-time use.
+setup-overwrite = Configuration file already exists. Overwrite?
+setup-cancelled = Setup cancelled. Your existing configuration was preserved.
+setup-server = Stitch server address
+setup-server-default = (default: { $server })
+setup-server-scheme = Server address should start with http:// or https://
+setup-server-invalid = Invalid server address
+setup-output = Default output format
+setup-color = Enable colored output?
+setup-saved = Configuration saved to { $path }
+setup-skip = skip
+setup-completions = Generate shell completions for
+setup-completions-install = To install completions for { $shell }:
+setup-step-mkdir = Create directory if it doesn't exist:
+setup-step-generate = Generate and install completions:
+setup-step-reload = Reload your shell:
+setup-step-zshrc = Add to ~/.zshrc if not already present:
+setup-step-fish = Fish automatically creates the directory, just run:
+setup-step-powershell = Add to your PowerShell profile:
+setup-step-powershell-reload = Then reload your profile:
+setup-completions-try = After installation, try pressing TAB after typing 'stitch' to see completions!
+setup-test = Test connection to server?
+setup-testing = Testing connection...
+setup-connected = Successfully connected to server!
+setup-connect-failed = Failed to connect: { $error }
+setup-server-running = Make sure the Stitch server is running.
+setup-start-server = Start the server with: { $command }
+setup-complete = Setup complete!
+setup-get-started = Get started with:
+setup-cmd-list = List all channels
+setup-cmd-track = Track a new channel
+setup-cmd-interactive = Interactive mode
+setup-cmd-help = Show help
+
+## Interactive mode
+
+tui-title = Stitch TUI
+tui-loaded = Channels loaded successfully
+tui-load-failed = Error loading channels: { $error }
+tui-track-failed = Failed to track channel: { $error }
+tui-untrack-failed = Failed to untrack channel: { $error }
+tui-tab-channels = Channels
+tui-tab-settings = Settings
+tui-tab-dashboard = Dashboard
+tui-add-channel = Add channel:
+tui-add-hint = Press Enter to add, Esc to cancel
+tui-delete-confirm = Delete '{ $name }'? Press Y to confirm, N to cancel
+tui-search = Search:
+tui-search-server = Search on server:
+tui-search-hint = Ctrl-S searches stream titles and categories on the server
+tui-search-failed = Server search failed, searching loaded channels instead: { $error }
+tui-loading = Loading...
+tui-tracking = tracking { $name }
+tui-untracking = untracking { $name }
+tui-footer = [{ $quit }] Quit | [{ $tabs }] Switch tabs | [{ $search }] Search | [?] Help | [{ $refresh }] Refresh
+tui-details = Channel Details
+tui-label-id = ID:
+tui-label-name = Name:
+tui-label-alias = Alias:
+tui-label-status = Status:
+tui-label-display-name = Display name:
+tui-label-twitch-id = Twitch ID:
+tui-label-tracked-since = Tracked since:
+tui-label-last-title = Last title:
+tui-label-last-stream = Last stream:
+tui-label-total = Total:
+tui-last-stream = { $date }, { $duration }
+tui-total = { $hours }h over { $streams } stream(s)
+tui-paused = paused
+tui-broken = Not receiving events: { $reason }
+tui-setting-server = Server URL
+tui-setting-output-format = Output format
+tui-setting-color = Colored output
+tui-setting-refresh-interval = Refresh interval (s)
+tui-recent-streams = Last { $count } streams
+tui-weekday-activity = By weekday (UTC)
+tui-weekdays = Mon Tue Wed Thu Fri Sat Sun
+tui-settings-help = [j/k] Select | [Enter] Edit or toggle
+tui-settings-hint = Press Enter to save, Esc to cancel
+tui-settings-saved = Saved { $key }
+tui-settings-saved-restart = Saved { $key }, restart to connect to the new server
+tui-settings-file = Configuration file: { $path }
+tui-untracked-undo = Untracked { $name }, press u to undo
+tui-reconnected = Reconnected to the server
+tui-status-connecting = Connecting
+tui-status-connected = Connected
+tui-status-reconnecting = Reconnecting
+tui-status-lost = Disconnected ({ $error }), retrying in { $seconds }s, attempt { $attempts }
+tui-status-loaded = Updated { $time }
+tui-status-stale = Stale since { $time }
+tui-status-not-loaded = Not loaded yet
+tui-dashboard-tracked = Tracked channels
+tui-dashboard-live = Live now
+tui-dashboard-week = Streamed this week
+tui-dashboard-categories = Top categories this week
+tui-dashboard-streamers = Most active this week
+tui-dashboard-empty = Nothing streamed this week yet
+tui-dashboard-failed = Failed to load the dashboard: { $error }
+tui-events = Events
+tui-events-waiting = Waiting for events...
+tui-events-disconnected = Not subscribed, press e twice to reconnect
+tui-layout-save-failed = Failed to save the layout: { $error }
+tui-history-title = Stream history of { $name }
+tui-history-empty = No streams recorded yet
+tui-history-failed = Failed to load stream history: { $error }
+tui-history-footer = [j/k] Scroll | [PgUp/PgDn] Page | [Esc] Close | [{ $quit }] Quit
+tui-help = Help
+tui-help-navigation = Navigation
+tui-help-up = Move up
+tui-help-down = Move down
+tui-help-tabs = Switch tabs
+tui-help-page = Move a page up or down
+tui-help-ends = Jump to first or last channel
+tui-help-channels = Channel Management
+tui-help-add = Add new channel
+tui-help-delete = Delete selected channel
+tui-help-open = Open selected channel in browser
+tui-help-copy = Copy selected channel's URL / login
+tui-help-refresh = Refresh channel list
+tui-help-history = Show stream history of selected channel
+tui-help-events = Toggle live event feed
+tui-help-undo = Track the channel untracked last again
+tui-help-layout = Layout
+tui-help-layout-details = Show or hide the details pane
+tui-help-layout-split = Narrow or widen the channel list
+tui-help-layout-compact = Toggle compact channel rows
+tui-help-search = Search
+tui-help-search-start = Start search
+tui-help-search-cancel = Cancel search
+tui-help-search-confirm = Confirm search
+tui-help-search-server = Toggle searching titles and categories on the server
+tui-help-general = General
+tui-help-toggle = Toggle this help
+tui-help-quit = Quit application
+tui-help-settings-edit = Edit or toggle selected setting
+tui-help-filter = Filter:
+tui-help-filter-hint = [/] Filter | [j/k] Scroll | [Esc] Close
+tui-help-no-match = No keys match
+
+## Table column headers, looked up from the English header in kebab case
+
+column-average = Average
+column-categories = Categories
+column-category = Category
+column-change = Change
+column-channel = Channel
+column-channels = Channels
+column-created = Created
+column-date = Date
+column-details = Details
+column-display-name = Display Name
+column-duration = Duration
+column-hours = Hours
+column-id = ID
+column-last-stream = Last Stream
+column-live = Live
+column-name = Name
+column-result = Result
+column-status = Status
+column-streams = Streams
+column-title = Title
+column-top-games = Top Games
+column-tracked-since = Tracked Since
+column-twitch-id = Twitch ID
+column-type = Type
+column-uptime = Uptime
+column-viewers = Viewers
@@ -60,7 +60,7 @@ pub async fn show_welcome_animation() -> Result<(), io::Error> {
 
         // Get terminal dimensions (rows, columns)
         let (term_height, term_width) = term.size();
-        
+
         // Center vertically
         let frame_lines = frame.trim().lines().count();
         let vertical_padding = (term_height as usize).saturating_sub(frame_lines) / 2;
@@ -88,7 +88,7 @@ pub async fn show_welcome_animation() -> Result<(), io::Error> {
 
     // Get terminal dimensions (rows, columns)
     let (term_height, term_width) = term.size();
-    
+
     // Center vertically
     let frame_lines = final_frame.trim().lines().count();
     let vertical_padding = (term_height as usize).saturating_sub(frame_lines) / 2;
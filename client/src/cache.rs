@@ -0,0 +1,64 @@
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use prost::Message;
+
+/// How long a cached response is served before asking the server again.
+const CACHE_TTL: Duration = Duration::from_secs(10);
+
+/// Read-only responses kept on disk for a few seconds, so commands run from
+/// shell prompts or completions don't each hit the server. Entries are keyed
+/// by server, API key, method and request, and every operation is best effort:
+/// a cache that can't be read or written is just a miss.
+#[derive(Clone)]
+pub struct ResponseCache {
+    dir: PathBuf,
+    scope: u64,
+}
+
+impl ResponseCache {
+    pub fn new(server: &str, api_key: Option<&str>) -> Option<Self> {
+        let dir = dirs::cache_dir()?.join("stitch").join("responses");
+        let mut hasher = DefaultHasher::new();
+        (server, api_key).hash(&mut hasher);
+        Some(Self {
+            dir,
+            scope: hasher.finish(),
+        })
+    }
+
+    /// The response to `request` if one was stored less than `CACHE_TTL` ago.
+    pub fn get<R: Message + Default>(&self, method: &str, request: &impl Message) -> Option<R> {
+        let path = self.path(method, request);
+        let age = fs::metadata(&path).ok()?.modified().ok()?.elapsed().ok()?;
+        if age > CACHE_TTL {
+            return None;
+        }
+        R::decode(fs::read(&path).ok()?.as_slice()).ok()
+    }
+
+    pub fn put(&self, method: &str, request: &impl Message, response: &impl Message) {
+        let path = self.path(method, request);
+        // Write then rename, so a concurrent `get` never sees half a response.
+        let tmp = path.with_extension(format!("{}.tmp", std::process::id()));
+        let _ = fs::create_dir_all(&self.dir)
+            .and_then(|()| fs::write(&tmp, response.encode_to_vec()))
+            .and_then(|()| fs::rename(&tmp, &path));
+    }
+
+    /// Drops every cached response, e.g. after the tracked channels changed.
+    pub fn clear(&self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+
+    fn path(&self, method: &str, request: &impl Message) -> PathBuf {
+        let mut hasher = DefaultHasher::new();
+        self.scope.hash(&mut hasher);
+        method.hash(&mut hasher);
+        request.encode_to_vec().hash(&mut hasher);
+        self.dir.join(format!("{:016x}", hasher.finish()))
+    }
+}
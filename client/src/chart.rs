@@ -0,0 +1,77 @@
+use chrono::{DateTime, Datelike, Days, NaiveTime, Utc};
+use unicode_width::UnicodeWidthStr;
+
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Longest run of weeks a sparkline covers.
+pub const MAX_WEEKS: usize = 52;
+
+/// One character per value, scaled so the largest is a full block. Zeros get
+/// a space, so gaps stand out from slow weeks.
+pub fn sparkline(values: &[f64]) -> String {
+    let max = values.iter().cloned().fold(0.0, f64::max);
+    values
+        .iter()
+        .map(|&v| match v {
+            v if v <= 0.0 => ' ',
+            v => {
+                SPARKS[((v / max * SPARKS.len() as f64).ceil() as usize).clamp(1, SPARKS.len()) - 1]
+            }
+        })
+        .collect()
+}
+
+/// A bar of up to `width` blocks for `value` out of `max`, using eighth
+/// blocks for the remainder. Any nonzero value gets at least a sliver.
+pub fn bar(value: f64, max: f64, width: usize) -> String {
+    const EIGHTHS: [&str; 8] = ["", "▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+    if value <= 0.0 || max <= 0.0 {
+        return String::new();
+    }
+    let eighths = ((value / max * (width * 8) as f64).round() as usize).max(1);
+    format!("{}{}", "█".repeat(eighths / 8), EIGHTHS[eighths % 8])
+}
+
+/// Midnight UTC on the Monday of `time`'s week.
+pub fn week_start(time: DateTime<Utc>) -> DateTime<Utc> {
+    let monday = time.date_naive() - Days::new(time.weekday().num_days_from_monday().into());
+    monday.and_time(NaiveTime::MIN).and_utc()
+}
+
+/// Hours per week from `since`'s week (or the first week with any) through
+/// the current one, filling in weeks without streams and keeping at most the
+/// last [`MAX_WEEKS`].
+pub fn weekly_hours(
+    weeks: &[(DateTime<Utc>, i64)],
+    since: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> Vec<(DateTime<Utc>, f64)> {
+    let Some(first) = since
+        .map(week_start)
+        .or_else(|| weeks.iter().map(|(week, _)| *week).min())
+    else {
+        return Vec::new();
+    };
+    let mut series = Vec::new();
+    let mut week = first;
+    while week <= now {
+        let seconds: i64 = weeks
+            .iter()
+            .filter(|(start, _)| *start == week)
+            .map(|(_, seconds)| seconds)
+            .sum();
+        series.push((week, seconds as f64 / 3600.0));
+        week += chrono::TimeDelta::weeks(1);
+    }
+    let skip = series.len().saturating_sub(MAX_WEEKS);
+    series.split_off(skip)
+}
+
+/// Pads `label` with spaces to `width` terminal columns.
+pub fn pad(label: &str, width: usize) -> String {
+    format!(
+        "{}{}",
+        label,
+        " ".repeat(width.saturating_sub(label.width()))
+    )
+}
@@ -1,7 +1,16 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use clap::ValueEnum;
+use crossterm::event::KeyCode;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use crate::output::OutputFormat;
+
+pub const DEFAULT_TIMEOUT_SECS: u64 = 30;
+pub const DEFAULT_RETRIES: u32 = 3;
+pub const DEFAULT_REFRESH_INTERVAL_SECS: u64 = 30;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CliConfig {
@@ -14,6 +23,265 @@ pub struct CliConfig {
     pub color: bool,
     pub timeout: u64,
     pub retries: u32,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+
+    /// Whether long tables are piped through a pager.
+    #[serde(default = "default_paging")]
+    pub paging: bool,
+    /// Pager command, falling back to `$PAGER` when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pager: Option<String>,
+
+    /// Seconds between channel list refreshes in the TUI, 0 to only refresh
+    /// on demand.
+    #[serde(default = "default_refresh_interval")]
+    pub refresh_interval: u64,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls_cert: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls_key: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls_ca: Option<PathBuf>,
+    /// Use TLS even for an `http://` server URL.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub tls: bool,
+    /// Name to send as SNI and check the certificate against, when it
+    /// differs from the server URL's host.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls_server_name: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub insecure_skip_verify: bool,
+
+    /// Named overrides selected with `--profile`, e.g. `[profiles.prod]`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, Profile>,
+
+    /// Local nicknames for channel logins, e.g. `alias.x = "some_long_login"`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub alias: BTreeMap<String, String>,
+
+    /// TUI key bindings, e.g. `keys.quit = "x"`.
+    #[serde(default, skip_serializing_if = "KeyBindings::is_default")]
+    pub keys: KeyBindings,
+
+    /// How the TUI's channels tab is laid out, e.g. `layout.split = 50`.
+    /// Changed from the TUI itself.
+    #[serde(default, skip_serializing_if = "PaneLayout::is_default")]
+    pub layout: PaneLayout,
+}
+
+/// Layout of the TUI's channels tab.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct PaneLayout {
+    /// Whether the details pane is shown next to the channel list.
+    pub details: bool,
+    /// Percent of the width the channel list takes while the details pane
+    /// is shown.
+    pub split: u16,
+    /// Leaves out everything but the live state, name and category of each
+    /// channel.
+    pub compact: bool,
+}
+
+impl Default for PaneLayout {
+    fn default() -> Self {
+        Self {
+            details: true,
+            split: 40,
+            compact: false,
+        }
+    }
+}
+
+impl PaneLayout {
+    /// The narrowest and widest the channel list can be made, in percent.
+    pub const SPLIT_RANGE: std::ops::RangeInclusive<u16> = 20..=80;
+
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Keys for the TUI actions that can be remapped. Each is a single
+/// character, `Tab`, `BackTab` or `F1` to `F12`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct KeyBindings {
+    pub quit: String,
+    pub add: String,
+    pub delete: String,
+    pub refresh: String,
+    pub search: String,
+    pub tabs: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KeyAction {
+    Quit,
+    Add,
+    Delete,
+    Refresh,
+    Search,
+    Tabs,
+}
+
+/// TUI actions on keys that can't be remapped.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FixedAction {
+    Help,
+    Down,
+    Up,
+    Open,
+    CopyUrl,
+    CopyName,
+    History,
+    Events,
+    Undo,
+    Details,
+    Compact,
+    Narrower,
+    Wider,
+}
+
+impl FixedAction {
+    /// Each fixed action with its key. Remapped actions may not use these.
+    pub const KEYS: [(char, FixedAction); 13] = [
+        ('?', FixedAction::Help),
+        ('j', FixedAction::Down),
+        ('k', FixedAction::Up),
+        ('o', FixedAction::Open),
+        ('y', FixedAction::CopyUrl),
+        ('Y', FixedAction::CopyName),
+        ('h', FixedAction::History),
+        ('e', FixedAction::Events),
+        ('u', FixedAction::Undo),
+        ('v', FixedAction::Details),
+        ('c', FixedAction::Compact),
+        ('<', FixedAction::Narrower),
+        ('>', FixedAction::Wider),
+    ];
+
+    /// The fixed action on `code`, if any.
+    pub fn of(code: KeyCode) -> Option<Self> {
+        Self::KEYS
+            .into_iter()
+            .find(|(key, _)| code == KeyCode::Char(*key))
+            .map(|(_, action)| action)
+    }
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            quit: "q".to_string(),
+            add: "a".to_string(),
+            delete: "d".to_string(),
+            refresh: "r".to_string(),
+            search: "/".to_string(),
+            tabs: "Tab".to_string(),
+        }
+    }
+}
+
+impl KeyBindings {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Each action with its config key and the key it's bound to.
+    pub fn bindings(&self) -> [(KeyAction, &'static str, &str); 6] {
+        [
+            (KeyAction::Quit, "quit", &self.quit),
+            (KeyAction::Add, "add", &self.add),
+            (KeyAction::Delete, "delete", &self.delete),
+            (KeyAction::Refresh, "refresh", &self.refresh),
+            (KeyAction::Search, "search", &self.search),
+            (KeyAction::Tabs, "tabs", &self.tabs),
+        ]
+    }
+
+    /// The action bound to `code`, if any.
+    pub fn action(&self, code: KeyCode) -> Option<KeyAction> {
+        self.bindings()
+            .into_iter()
+            .find(|(_, _, key)| parse_key(key) == Some(code))
+            .map(|(action, _, _)| action)
+    }
+
+    /// Checks that every key parses and that no two actions share one.
+    pub fn validate(&self) -> Result<()> {
+        let mut taken: Vec<(KeyCode, &str)> = FixedAction::KEYS
+            .iter()
+            .map(|&(c, _)| (KeyCode::Char(c), "a built-in action"))
+            .collect();
+        for (_, name, key) in self.bindings() {
+            let Some(code) = parse_key(key) else {
+                bail!(
+                    "keys.{} must be a single character, Tab, BackTab or F1 to F12, got '{}'",
+                    name,
+                    key
+                );
+            };
+            if let Some((_, other)) = taken.iter().find(|(taken, _)| *taken == code) {
+                bail!("keys.{} '{}' is already bound to {}", name, key, other);
+            }
+            taken.push((code, name));
+        }
+        Ok(())
+    }
+}
+
+/// Parses a key as written in the `[keys]` config.
+pub fn parse_key(key: &str) -> Option<KeyCode> {
+    let mut chars = key.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return (!c.is_whitespace()).then_some(KeyCode::Char(c));
+    }
+    match key.to_ascii_lowercase().as_str() {
+        "tab" => Some(KeyCode::Tab),
+        "backtab" => Some(KeyCode::BackTab),
+        key => key
+            .strip_prefix('f')?
+            .parse()
+            .ok()
+            .filter(|n| (1..=12).contains(n))
+            .map(KeyCode::F),
+    }
+}
+
+/// Settings that replace the top-level ones when the profile is selected.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct Profile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_format: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls_cert: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls_key: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls_ca: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls_server_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub insecure_skip_verify: Option<bool>,
+}
+
+fn default_paging() -> bool {
+    true
+}
+
+fn default_refresh_interval() -> u64 {
+    DEFAULT_REFRESH_INTERVAL_SECS
 }
 
 impl Default for CliConfig {
@@ -22,23 +290,192 @@ impl Default for CliConfig {
             server: "http://127.0.0.1:50051".to_string(),
             output_format: "table".to_string(),
             color: true,
-            timeout: 30,
-            retries: 3,
+            timeout: DEFAULT_TIMEOUT_SECS,
+            retries: DEFAULT_RETRIES,
+            api_key: None,
+            paging: true,
+            pager: None,
+            refresh_interval: DEFAULT_REFRESH_INTERVAL_SECS,
+            tls_cert: None,
+            tls_key: None,
+            tls_ca: None,
+            tls: false,
+            tls_server_name: None,
+            insecure_skip_verify: false,
+            profiles: BTreeMap::new(),
+            alias: BTreeMap::new(),
+            keys: KeyBindings::default(),
+            layout: PaneLayout::default(),
         }
     }
 }
 
 impl CliConfig {
-    pub fn load() -> Result<Self> {
-        let config_path = Self::config_path()?;
+    /// Settings that `stitch config get/set` understand.
+    pub const KEYS: &'static [&'static str] = &[
+        "server",
+        "output_format",
+        "color",
+        "timeout",
+        "retries",
+        "api_key",
+        "paging",
+        "pager",
+        "refresh_interval",
+        "tls",
+        "tls_ca",
+        "tls_server_name",
+        "insecure_skip_verify",
+    ];
+
+    pub fn get(&self, key: &str) -> Result<String> {
+        Ok(match key {
+            "server" => self.server.clone(),
+            "output_format" => self.output_format.clone(),
+            "color" => self.color.to_string(),
+            "timeout" => self.timeout.to_string(),
+            "retries" => self.retries.to_string(),
+            "api_key" => self.api_key.clone().unwrap_or_default(),
+            "paging" => self.paging.to_string(),
+            "pager" => self.pager.clone().unwrap_or_default(),
+            "refresh_interval" => self.refresh_interval.to_string(),
+            "tls" => self.tls.to_string(),
+            "tls_ca" => self
+                .tls_ca
+                .as_ref()
+                .map(|p| p.display().to_string())
+                .unwrap_or_default(),
+            "tls_server_name" => self.tls_server_name.clone().unwrap_or_default(),
+            "insecure_skip_verify" => self.insecure_skip_verify.to_string(),
+            _ => bail!("Unknown config key '{}'", key),
+        })
+    }
+
+    /// Sets `key` from its string form. An empty value clears optional keys.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        let invalid = || format!("Invalid value '{}' for {}", value, key);
+        match key {
+            "server" => self.server = value.to_string(),
+            "output_format" => self.output_format = value.to_lowercase(),
+            "color" => self.color = value.parse().with_context(invalid)?,
+            "timeout" => self.timeout = value.parse().with_context(invalid)?,
+            "retries" => self.retries = value.parse().with_context(invalid)?,
+            "api_key" => self.api_key = Some(value.to_string()).filter(|v| !v.is_empty()),
+            "paging" => self.paging = value.parse().with_context(invalid)?,
+            "pager" => self.pager = Some(value.to_string()).filter(|v| !v.is_empty()),
+            "refresh_interval" => self.refresh_interval = value.parse().with_context(invalid)?,
+            "tls" => self.tls = value.parse().with_context(invalid)?,
+            "tls_ca" => self.tls_ca = Some(PathBuf::from(value)).filter(|_| !value.is_empty()),
+            "tls_server_name" => {
+                self.tls_server_name = Some(value.to_string()).filter(|v| !v.is_empty())
+            }
+            "insecure_skip_verify" => {
+                self.insecure_skip_verify = value.parse().with_context(invalid)?
+            }
+            _ => bail!("Unknown config key '{}'", key),
+        }
+        self.validate()
+    }
+
+    /// This config with the named profile's settings applied on top.
+    pub fn with_profile(mut self, name: &str) -> Result<Self> {
+        let Some(profile) = self.profiles.get(name).cloned() else {
+            let known: Vec<_> = self.profiles.keys().map(String::as_str).collect();
+            if known.is_empty() {
+                bail!("Unknown profile '{}': no profiles are configured", name);
+            }
+            bail!(
+                "Unknown profile '{}', expected one of: {}",
+                name,
+                known.join(", ")
+            );
+        };
+        if let Some(server) = profile.server {
+            self.server = server;
+        }
+        if let Some(output_format) = profile.output_format {
+            self.output_format = output_format;
+        }
+        self.api_key = profile.api_key.or(self.api_key);
+        self.tls_cert = profile.tls_cert.or(self.tls_cert);
+        self.tls_key = profile.tls_key.or(self.tls_key);
+        self.tls_ca = profile.tls_ca.or(self.tls_ca);
+        self.tls = profile.tls.unwrap_or(self.tls);
+        self.tls_server_name = profile.tls_server_name.or(self.tls_server_name);
+        self.insecure_skip_verify = profile
+            .insecure_skip_verify
+            .unwrap_or(self.insecure_skip_verify);
+        Ok(self)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        self.validate_settings()?;
+        for name in self.profiles.keys() {
+            self.clone()
+                .with_profile(name)?
+                .validate_settings()
+                .with_context(|| format!("In profile '{}'", name))?;
+        }
+        Ok(())
+    }
+
+    fn validate_settings(&self) -> Result<()> {
+        if !self.server.starts_with("http://") && !self.server.starts_with("https://") {
+            bail!(
+                "server must be an http:// or https:// URL, got '{}'",
+                self.server
+            );
+        }
+        if !self.output_format.is_empty()
+            && OutputFormat::from_str(&self.output_format, true).is_err()
+        {
+            let formats: Vec<_> = OutputFormat::value_variants()
+                .iter()
+                .filter_map(|f| f.to_possible_value())
+                .map(|v| v.get_name().to_string())
+                .collect();
+            bail!(
+                "output_format must be one of {}, got '{}'",
+                formats.join(", "),
+                self.output_format
+            );
+        }
+        if self.timeout == 0 {
+            bail!("timeout must be at least 1 second");
+        }
+        if self.tls_cert.is_some() != self.tls_key.is_some() {
+            bail!("tls_cert and tls_key must be set together");
+        }
+        for (alias, login) in &self.alias {
+            if alias.trim().is_empty() || login.trim().is_empty() {
+                bail!("alias '{}' must name a channel login", alias);
+            }
+        }
+        self.keys.validate()?;
+        if !PaneLayout::SPLIT_RANGE.contains(&self.layout.split) {
+            bail!(
+                "layout.split must be between {} and {}",
+                PaneLayout::SPLIT_RANGE.start(),
+                PaneLayout::SPLIT_RANGE.end()
+            );
+        }
+        Ok(())
+    }
 
+    /// Loads the config at `config_path`, writing out the defaults first if
+    /// there's no file yet.
+    pub fn load(config_path: &Path) -> Result<Self> {
         if !config_path.exists() {
             let config = Self::default();
-            config.save()?;
+            config.save(config_path)?;
             return Ok(config);
         }
 
-        let contents = fs::read_to_string(&config_path)
+        Self::load_from(config_path)
+    }
+
+    pub fn load_from(config_path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(config_path)
             .with_context(|| format!("Failed to read config from {:?}", config_path))?;
 
         let config: Self = toml::from_str(&contents)
@@ -47,9 +484,7 @@ impl CliConfig {
         Ok(config)
     }
 
-    pub fn save(&self) -> Result<()> {
-        let config_path = Self::config_path()?;
-
+    pub fn save(&self, config_path: &Path) -> Result<()> {
         if let Some(parent) = config_path.parent() {
             fs::create_dir_all(parent)
                 .with_context(|| format!("Failed to create config directory {:?}", parent))?;
@@ -57,15 +492,92 @@ impl CliConfig {
 
         let contents = toml::to_string_pretty(self).context("Failed to serialize config")?;
 
-        fs::write(&config_path, contents)
+        fs::write(config_path, contents)
             .with_context(|| format!("Failed to write config to {:?}", config_path))?;
 
         Ok(())
     }
 
-    pub fn config_path() -> Result<PathBuf> {
-        let home = dirs::home_dir().context("Failed to get home directory")?;
+    /// `$XDG_CONFIG_HOME/stitch/config.toml`, falling back to
+    /// `~/.config/stitch/config.toml` on every platform.
+    pub fn default_path() -> Result<PathBuf> {
+        let base = match std::env::var_os("XDG_CONFIG_HOME").map(PathBuf::from) {
+            // The spec says to ignore relative paths.
+            Some(dir) if dir.is_absolute() => dir,
+            _ => dirs::home_dir()
+                .context("Failed to get home directory")?
+                .join(".config"),
+        };
+
+        Ok(base.join("stitch").join("config.toml"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_key() {
+        assert_eq!(parse_key("q"), Some(KeyCode::Char('q')));
+        assert_eq!(parse_key("/"), Some(KeyCode::Char('/')));
+        assert_eq!(parse_key("Tab"), Some(KeyCode::Tab));
+        assert_eq!(parse_key("backtab"), Some(KeyCode::BackTab));
+        assert_eq!(parse_key("F1"), Some(KeyCode::F(1)));
+        assert_eq!(parse_key("f12"), Some(KeyCode::F(12)));
+        assert_eq!(parse_key("F13"), None);
+        assert_eq!(parse_key("F0"), None);
+        assert_eq!(parse_key(" "), None);
+        assert_eq!(parse_key(""), None);
+        assert_eq!(parse_key("ab"), None);
+    }
+
+    #[test]
+    fn test_validate_keys() {
+        assert!(KeyBindings::default().validate().is_ok());
+
+        let keys = KeyBindings {
+            refresh: "F5".to_string(),
+            tabs: "BackTab".to_string(),
+            ..Default::default()
+        };
+        assert!(keys.validate().is_ok());
+        assert_eq!(keys.action(KeyCode::F(5)), Some(KeyAction::Refresh));
+        assert_eq!(keys.action(KeyCode::Char('r')), None);
+
+        // Remapping onto a fixed key.
+        for (key, _) in FixedAction::KEYS {
+            let keys = KeyBindings {
+                add: key.to_string(),
+                ..Default::default()
+            };
+            let err = keys.validate().unwrap_err().to_string();
+            assert!(err.contains("a built-in action"), "{err}");
+        }
+
+        // Two actions on one key.
+        let keys = KeyBindings {
+            delete: "q".to_string(),
+            ..Default::default()
+        };
+        let err = keys.validate().unwrap_err().to_string();
+        assert!(err.contains("keys.delete") && err.contains("quit"), "{err}");
+
+        let keys = KeyBindings {
+            search: "F13".to_string(),
+            ..Default::default()
+        };
+        assert!(keys.validate().is_err());
+    }
 
-        Ok(home.join(".config").join("stitch").join("config.toml"))
+    #[test]
+    fn test_fixed_action() {
+        assert_eq!(FixedAction::of(KeyCode::Char('j')), Some(FixedAction::Down));
+        assert_eq!(
+            FixedAction::of(KeyCode::Char('Y')),
+            Some(FixedAction::CopyName)
+        );
+        assert_eq!(FixedAction::of(KeyCode::Char('q')), None);
+        assert_eq!(FixedAction::of(KeyCode::Down), None);
     }
 }
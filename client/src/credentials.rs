@@ -0,0 +1,27 @@
+use keyring::Entry;
+
+const SERVICE: &str = "stitch";
+
+/// The keyring entry holding the API token for `server`. Tokens are stored
+/// per server URL so profiles pointing at different servers don't collide.
+fn entry(server: &str) -> keyring::Result<Entry> {
+    Entry::new(SERVICE, server)
+}
+
+/// The token saved by `stitch login` for `server`, if the keyring has one.
+pub fn load_token(server: &str) -> Option<String> {
+    entry(server).and_then(|e| e.get_password()).ok()
+}
+
+pub fn store_token(server: &str, token: &str) -> keyring::Result<()> {
+    entry(server)?.set_password(token)
+}
+
+/// Removes the saved token. Returns whether there was one.
+pub fn delete_token(server: &str) -> keyring::Result<bool> {
+    match entry(server)?.delete_credential() {
+        Ok(()) => Ok(true),
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(e) => Err(e),
+    }
+}
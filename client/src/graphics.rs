@@ -0,0 +1,179 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use image::{imageops::FilterType, RgbaImage};
+use std::fmt::Write;
+
+/// Ways of drawing pixels into a terminal.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Protocol {
+    Kitty,
+    Sixel,
+}
+
+/// Pixels of a cell when the terminal doesn't say.
+const DEFAULT_CELL: (u32, u32) = (10, 20);
+
+/// Bytes of base64 per kitty escape, the most the protocol allows.
+const KITTY_CHUNK: usize = 4096;
+
+/// Levels per channel of the sixel palette, a 6×6×6 color cube.
+const LEVELS: u32 = 6;
+
+/// Guesses from the environment whether the terminal can draw images, and
+/// how. Terminal multiplexers get nothing, since they'd need the escapes
+/// wrapped and would lose the images on redraw anyway.
+pub fn detect() -> Option<Protocol> {
+    let var = |name: &str| std::env::var(name).unwrap_or_default();
+    if std::env::var_os("TMUX").is_some() || var("TERM").starts_with("screen") {
+        return None;
+    }
+    let term = var("TERM");
+    let program = var("TERM_PROGRAM");
+    if std::env::var_os("KITTY_WINDOW_ID").is_some()
+        || term == "xterm-kitty"
+        || term == "xterm-ghostty"
+        || matches!(program.as_str(), "WezTerm" | "ghostty")
+    {
+        Some(Protocol::Kitty)
+    } else if term.starts_with("foot") || term.starts_with("mlterm") || term.contains("sixel") {
+        Some(Protocol::Sixel)
+    } else {
+        None
+    }
+}
+
+/// Escapes that draw `image` at the cursor, scaled to `cols` by `rows`
+/// cells, without moving the cursor for kitty.
+pub fn encode(protocol: Protocol, image: &RgbaImage, cols: u16, rows: u16) -> String {
+    match protocol {
+        Protocol::Kitty => kitty(image, cols, rows),
+        Protocol::Sixel => {
+            let (cell_width, cell_height) = cell_size();
+            let image = image::imageops::resize(
+                image,
+                cell_width * u32::from(cols),
+                cell_height * u32::from(rows),
+                FilterType::Triangle,
+            );
+            sixel(&image)
+        }
+    }
+}
+
+/// Escapes that remove whatever [`encode`] drew, for protocols that keep
+/// images apart from the text. Sixels are just overwritten.
+pub fn clear(protocol: Protocol) -> &'static str {
+    match protocol {
+        Protocol::Kitty => "\x1b_Ga=d,d=A,q=2\x1b\\",
+        Protocol::Sixel => "",
+    }
+}
+
+/// Pixels per cell as the terminal reports them.
+fn cell_size() -> (u32, u32) {
+    match crossterm::terminal::window_size() {
+        Ok(size) if size.width > 0 && size.columns > 0 && size.rows > 0 => (
+            u32::from(size.width / size.columns),
+            u32::from(size.height / size.rows),
+        ),
+        _ => DEFAULT_CELL,
+    }
+}
+
+/// The image as raw RGBA in kitty's graphics protocol, split over as many
+/// escapes as it takes.
+fn kitty(image: &RgbaImage, cols: u16, rows: u16) -> String {
+    let data = STANDARD.encode(image.as_raw());
+    let chunks: Vec<&str> = data
+        .as_bytes()
+        .chunks(KITTY_CHUNK)
+        .map(|chunk| std::str::from_utf8(chunk).expect("base64 is ASCII"))
+        .collect();
+    let mut out = String::new();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(i + 1 < chunks.len());
+        if i == 0 {
+            let _ = write!(
+                out,
+                "\x1b_Ga=T,f=32,s={},v={},c={cols},r={rows},C=1,q=2,m={more};{chunk}\x1b\\",
+                image.width(),
+                image.height()
+            );
+        } else {
+            let _ = write!(out, "\x1b_Gm={more};{chunk}\x1b\\");
+        }
+    }
+    out
+}
+
+/// The image as sixels over a fixed color cube, with mostly transparent
+/// pixels left out.
+fn sixel(image: &RgbaImage) -> String {
+    let (width, height) = image.dimensions();
+    let color = |x: u32, y: u32| {
+        let pixel = image.get_pixel(x, y).0;
+        if pixel[3] < 128 {
+            return None;
+        }
+        let level = |v: u8| (u32::from(v) * (LEVELS - 1) + 127) / 255;
+        Some((level(pixel[0]) * LEVELS + level(pixel[1])) * LEVELS + level(pixel[2]))
+    };
+
+    let mut out = format!("\x1bP0;1;0q\"1;1;{width};{height}");
+    for i in 0..LEVELS.pow(3) {
+        let percent = |level: u32| level * 100 / (LEVELS - 1);
+        let _ = write!(
+            out,
+            "#{i};2;{};{};{}",
+            percent(i / (LEVELS * LEVELS)),
+            percent(i / LEVELS % LEVELS),
+            percent(i % LEVELS)
+        );
+    }
+
+    for top in (0..height).step_by(6) {
+        let band: Vec<Vec<Option<u32>>> = (0..width)
+            .map(|x| (top..(top + 6).min(height)).map(|y| color(x, y)).collect())
+            .collect();
+        let mut used: Vec<u32> = band.iter().flatten().flatten().copied().collect();
+        used.sort_unstable();
+        used.dedup();
+        for c in used {
+            let _ = write!(out, "#{c}");
+            let sixels = band.iter().map(|column| {
+                let bits = column
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, &pixel)| pixel == Some(c))
+                    .fold(0u8, |bits, (dy, _)| bits | 1 << dy);
+                char::from(63 + bits)
+            });
+            push_runs(&mut out, sixels);
+            out.push('$');
+        }
+        out.push('-');
+    }
+    out.push_str("\x1b\\");
+    out
+}
+
+/// Appends `sixels`, writing runs of the same one with a repeat count.
+fn push_runs(out: &mut String, sixels: impl Iterator<Item = char>) {
+    let mut run: Option<(char, usize)> = None;
+    let flush = |out: &mut String, run: Option<(char, usize)>| match run {
+        Some((c, n)) if n > 3 => {
+            let _ = write!(out, "!{n}{c}");
+        }
+        Some((c, n)) => out.extend(std::iter::repeat_n(c, n)),
+        None => {}
+    };
+    for c in sixels {
+        run = match run {
+            Some((prev, n)) if prev == c => Some((prev, n + 1)),
+            other => {
+                flush(out, other);
+                Some((c, 1))
+            }
+        };
+    }
+    flush(out, run);
+}
@@ -0,0 +1,103 @@
+use std::sync::OnceLock;
+
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource};
+use unic_langid::LanguageIdentifier;
+
+/// Message catalogs by language. English comes first and is the fallback for
+/// anything another catalog doesn't translate.
+const CATALOGS: &[(&str, &str)] = &[
+    ("en", include_str!("../locales/en.ftl")),
+    ("de", include_str!("../locales/de.ftl")),
+];
+
+struct Catalog {
+    bundle: FluentBundle<FluentResource>,
+    fallback: Option<FluentBundle<FluentResource>>,
+}
+
+static CATALOG: OnceLock<Catalog> = OnceLock::new();
+
+/// Formats a message from the user's catalog, e.g. `t!("track-done", name = login)`.
+macro_rules! t {
+    ($id:literal) => {
+        $crate::i18n::message($id, None)
+    };
+    ($id:literal, $($key:ident = $value:expr),+ $(,)?) => {{
+        let mut args = fluent_bundle::FluentArgs::new();
+        $(args.set(stringify!($key), $value);)+
+        $crate::i18n::message($id, Some(&args))
+    }};
+}
+pub(crate) use t;
+
+/// The user's language as POSIX tools find it, from `LC_ALL`, `LC_MESSAGES`
+/// or `LANG`, e.g. `de_DE.UTF-8` becomes `de-DE`.
+fn detect_locale() -> Option<LanguageIdentifier> {
+    let value = ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok().filter(|v| !v.is_empty()))?;
+    let tag = value.split(['.', '@']).next()?.replace('_', "-");
+    tag.parse().ok()
+}
+
+fn bundle(language: &str, source: &str) -> FluentBundle<FluentResource> {
+    let id: LanguageIdentifier = language.parse().expect("catalog languages are valid tags");
+    let mut bundle = FluentBundle::new_concurrent(vec![id]);
+    // Terminals have no use for the Unicode isolation marks around arguments.
+    bundle.set_use_isolating(false);
+    let resource = FluentResource::try_new(source.to_string())
+        .unwrap_or_else(|(_, errors)| panic!("{language} catalog has errors: {errors:?}"));
+    bundle
+        .add_resource(resource)
+        .unwrap_or_else(|errors| panic!("{language} catalog has errors: {errors:?}"));
+    bundle
+}
+
+fn catalog() -> &'static Catalog {
+    CATALOG.get_or_init(|| {
+        let language = detect_locale().map(|l| l.language.as_str().to_string());
+        let (_, english) = CATALOGS[0];
+        match CATALOGS
+            .iter()
+            .find(|(l, _)| Some(*l) == language.as_deref())
+        {
+            Some((l, source)) if *l != "en" => Catalog {
+                bundle: bundle(l, source),
+                fallback: Some(bundle("en", english)),
+            },
+            _ => Catalog {
+                bundle: bundle("en", english),
+                fallback: None,
+            },
+        }
+    })
+}
+
+fn lookup(id: &str, args: Option<&FluentArgs>) -> Option<String> {
+    let catalog = catalog();
+    std::iter::once(&catalog.bundle)
+        .chain(&catalog.fallback)
+        .find_map(|bundle| {
+            let pattern = bundle.get_message(id)?.value()?;
+            let mut errors = Vec::new();
+            Some(
+                bundle
+                    .format_pattern(pattern, args, &mut errors)
+                    .into_owned(),
+            )
+        })
+}
+
+/// Message `id` in the user's language. A message missing from every
+/// catalog shows as its ID, which is easy to spot and grep for.
+pub fn message(id: &str, args: Option<&FluentArgs>) -> String {
+    lookup(id, args).unwrap_or_else(|| id.to_string())
+}
+
+/// A table column header in the user's language, looked up as
+/// `column-<header in kebab case>` and left as is without a translation.
+pub fn column(header: &str) -> String {
+    let id = format!("column-{}", header.to_lowercase().replace(' ', "-"));
+    lookup(&id, None).unwrap_or_else(|| header.to_string())
+}
@@ -1,26 +1,201 @@
 mod animations;
+mod cache;
+mod chart;
 mod config;
+mod credentials;
+mod graphics;
+mod i18n;
+mod output;
+mod pager;
+mod tls;
 mod tui;
+mod version;
 
 use anyhow::{Context, Result};
+use chrono::{DateTime, Local, NaiveDate, NaiveTime, TimeDelta, TimeZone, Utc};
+use clap::builder::PossibleValuesParser;
 use clap::{ArgAction, Parser, Subcommand, ValueEnum};
+use indicatif::{ProgressBar, ProgressStyle};
 use owo_colors::OwoColorize;
+use proto::errors;
 use proto::stitch::stitch_service_client::StitchServiceClient;
 use proto::stitch::*;
-use std::collections::HashMap;
-use std::io::{self, Write};
+use std::collections::{BTreeMap, HashMap};
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
 use std::time::Duration;
-use tabled::{settings::Style as TableStyle, Table, Tabled};
+use tabled::settings::{format::Format, object::Rows, Modify, Style as TableStyle};
+use tabled::{Table, Tabled};
 use tokio::time::sleep;
-use tonic::transport::{Channel, Endpoint};
+use tonic::codec::CompressionEncoding;
+use tonic::transport::{Certificate, Channel, ClientTlsConfig, Endpoint, Identity};
 use tonic::{Code, Request};
+use tonic_types::StatusExt;
+use unicode_width::UnicodeWidthStr;
+
+use cache::ResponseCache;
+use config::{CliConfig, DEFAULT_RETRIES, DEFAULT_TIMEOUT_SECS};
+use i18n::t;
+use output::{
+    log_level_name, print_porcelain, print_record, print_records, to_datetime, ChannelList,
+    ChannelRecord, EventRecord, FilterRecord, InactiveRecord, LiveRecord, LogRecord, OutputFormat,
+    ResyncRecord, StatsRecord, StatusRecord, StreamRecord, SubscriptionRecord, TopCategoryRecord,
+    ValidationRecord, VersionRecord,
+};
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+enum LogLevelArg {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl From<LogLevelArg> for LogLevel {
+    fn from(level: LogLevelArg) -> Self {
+        match level {
+            LogLevelArg::Trace => LogLevel::Trace,
+            LogLevelArg::Debug => LogLevel::Debug,
+            LogLevelArg::Info => LogLevel::Info,
+            LogLevelArg::Warn => LogLevel::Warn,
+            LogLevelArg::Error => LogLevel::Error,
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+enum Compression {
+    None,
+    Gzip,
+    Zstd,
+}
+
+impl Compression {
+    fn encoding(self) -> Option<CompressionEncoding> {
+        match self {
+            Compression::None => None,
+            Compression::Gzip => Some(CompressionEncoding::Gzip),
+            Compression::Zstd => Some(CompressionEncoding::Zstd),
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum ListSort {
+    Id,
+    Name,
+    /// Longest-running streams first, offline channels last
+    Uptime,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum LiveSort {
+    Uptime,
+    Viewers,
+    Name,
+}
+
+#[derive(Tabled)]
+struct LiveDisplay {
+    #[tabled(rename = "Channel")]
+    channel: String,
+    #[tabled(rename = "Viewers")]
+    viewers: u32,
+    #[tabled(rename = "Uptime")]
+    uptime: String,
+    #[tabled(rename = "Category")]
+    category: String,
+    #[tabled(rename = "Title")]
+    title: String,
+}
+
+#[derive(Tabled)]
+struct StatsDisplay {
+    #[tabled(rename = "Channel")]
+    channel: String,
+    #[tabled(rename = "Streams")]
+    streams: u32,
+    #[tabled(rename = "Hours")]
+    hours: String,
+    #[tabled(rename = "Average")]
+    average: String,
+    #[tabled(rename = "Viewers")]
+    viewers: String,
+    #[tabled(rename = "Top Games")]
+    top_games: String,
+}
+
+#[derive(Tabled)]
+struct TopCategoryDisplay {
+    #[tabled(rename = "#")]
+    rank: usize,
+    #[tabled(rename = "Category")]
+    category: String,
+    #[tabled(rename = "Hours")]
+    hours: String,
+    #[tabled(rename = "")]
+    bar: String,
+    #[tabled(rename = "Channels")]
+    channels: u32,
+    #[tabled(rename = "Streams")]
+    streams: u32,
+}
+
+#[derive(Tabled)]
+struct StreamDisplay {
+    #[tabled(rename = "Date")]
+    date: String,
+    #[tabled(rename = "Duration")]
+    duration: String,
+    #[tabled(rename = "Title")]
+    title: String,
+    #[tabled(rename = "Categories")]
+    categories: String,
+}
+
+#[derive(Tabled)]
+struct SubscriptionDisplay {
+    #[tabled(rename = "Channel")]
+    channel: String,
+    #[tabled(rename = "Type")]
+    kind: String,
+    #[tabled(rename = "Status")]
+    status: String,
+    #[tabled(rename = "Created")]
+    created: String,
+}
+
+#[derive(Tabled)]
+struct ChangeDisplay {
+    #[tabled(rename = "Change")]
+    change: String,
+    #[tabled(rename = "Channel")]
+    channel: String,
+    #[tabled(rename = "Type")]
+    kind: String,
+    #[tabled(rename = "Details")]
+    details: String,
+}
 
-use config::CliConfig;
+#[derive(Tabled)]
+struct BulkResultDisplay {
+    #[tabled(rename = "Channel")]
+    channel: String,
+    #[tabled(rename = "Result")]
+    result: String,
+    #[tabled(rename = "Details")]
+    details: String,
+}
 
-#[derive(ValueEnum, Clone, Debug)]
-enum OutputFormat {
-    Json,
-    Table,
+#[derive(Tabled)]
+struct InactiveDisplay {
+    #[tabled(rename = "Channel")]
+    channel: String,
+    #[tabled(rename = "Last Stream")]
+    last_stream: String,
+    #[tabled(rename = "Tracked Since")]
+    tracked_since: String,
 }
 
 #[derive(Tabled)]
@@ -29,25 +204,278 @@ struct ChannelDisplay {
     id: i32,
     #[tabled(rename = "Name")]
     name: String,
+    #[tabled(rename = "Live")]
+    live: String,
+    #[tabled(rename = "Category")]
+    category: String,
+    #[tabled(rename = "Uptime")]
+    uptime: String,
+}
+
+#[derive(Tabled)]
+struct ChannelWideDisplay {
+    #[tabled(rename = "ID")]
+    id: i32,
+    #[tabled(rename = "Name")]
+    name: String,
+    #[tabled(rename = "Display Name")]
+    display_name: String,
+    #[tabled(rename = "Twitch ID")]
+    twitch_id: String,
+    #[tabled(rename = "Live")]
+    live: String,
+    #[tabled(rename = "Category")]
+    category: String,
+    #[tabled(rename = "Uptime")]
+    uptime: String,
+    #[tabled(rename = "Tracked Since")]
+    tracked_since: String,
 }
 
 #[derive(Subcommand)]
 enum Command {
     #[command(alias = "ls")]
-    List,
+    List {
+        /// Only channels whose name contains this text
+        #[arg(long, short = 'f')]
+        filter: Option<String>,
+
+        /// Only channels that are streaming right now
+        #[arg(long)]
+        live_only: bool,
+
+        #[arg(long, value_enum, default_value_t = ListSort::Id)]
+        sort: ListSort,
+
+        /// Redraw the table every SECONDS (default 5), highlighting changes
+        #[arg(
+            long,
+            value_name = "SECONDS",
+            num_args = 0..=1,
+            default_missing_value = "5",
+            value_parser = clap::value_parser!(u64).range(1..)
+        )]
+        watch: Option<u64>,
+    },
 
     Track {
-        name: String,
+        /// Channel names, twitch.tv URLs or numeric user IDs
+        #[arg(required_unless_present = "from_file")]
+        names: Vec<String>,
+
+        /// Track every channel listed in a file, one per line ("-" reads stdin)
+        #[arg(long, conflicts_with = "names")]
+        from_file: Option<PathBuf>,
+
+        /// Look the channels up and show what would change, without tracking
+        #[arg(long)]
+        dry_run: bool,
     },
 
     #[command(alias = "rm")]
     Untrack {
-        name: String,
+        /// Channels to untrack; pick them interactively when left out
+        names: Vec<String>,
 
         #[arg(long, short = 'y')]
         yes: bool,
+
+        /// Show what would change, without untracking
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Find channels that haven't streamed in a while and untrack them
+    Prune {
+        /// Channels without a stream in this many days
+        #[arg(long, default_value_t = 30)]
+        days: u32,
+
+        /// Untrack every inactive channel without asking
+        #[arg(long, short = 'y')]
+        yes: bool,
+
+        /// Only list the inactive channels
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Show whether a channel is live and what it's streaming
+    Status {
+        name: String,
+    },
+
+    /// Open a tracked channel on twitch.tv in the browser
+    Open {
+        name: String,
+    },
+
+    /// Show or change which of a channel's streams are posted to Discord
+    Filter {
+        name: String,
+
+        /// Only post streams in this category; repeat for several
+        #[arg(long = "category", value_name = "CATEGORY")]
+        categories: Vec<String>,
+
+        /// Don't post streams whose title contains this; repeat for several
+        #[arg(long = "mute", value_name = "KEYWORD")]
+        muted_keywords: Vec<String>,
+
+        /// Remove the channel's current filters first
+        #[arg(long)]
+        clear: bool,
+    },
+
+    /// List channels that are streaming right now
+    Live {
+        /// Order streams by longest uptime, most viewers, or name
+        #[arg(long, value_enum, default_value_t = LiveSort::Uptime)]
+        sort: LiveSort,
+
+        /// Print JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show recent streams for a channel
+    History {
+        name: String,
+
+        /// Number of streams to show
+        #[arg(long, short = 'n', default_value_t = 10)]
+        limit: u32,
+
+        /// Only streams started since a date (2025-08-01) or a time ago (12h, 7d, 2w)
+        #[arg(long, value_parser = parse_since)]
+        since: Option<DateTime<Utc>>,
+
+        /// Print JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show hours streamed, average stream length and top games per channel
+    Stats {
+        /// Only show this channel
+        #[arg(long, short = 'c')]
+        channel: Option<String>,
+
+        /// Only count streams started since a date (2025-08-01) or a time ago (12h, 7d, 2w)
+        #[arg(long, value_parser = parse_since)]
+        since: Option<DateTime<Utc>>,
+
+        /// Print JSON instead of a table
+        #[arg(long)]
+        json: bool,
+
+        /// Chart hours streamed per week and time per category instead
+        #[arg(long, conflicts_with = "json")]
+        chart: bool,
+    },
+
+    /// Most streamed categories across all tracked channels
+    Top {
+        /// Only count streams started since a date (2025-08-01) or a time ago (12h, 7d, 2w)
+        #[arg(long, value_parser = parse_since)]
+        since: Option<DateTime<Utc>>,
+
+        /// Number of categories to show
+        #[arg(long, short = 'n', default_value_t = 10)]
+        limit: u32,
+
+        /// Print JSON instead of a table
+        #[arg(long)]
+        json: bool,
+
+        /// Chart each category's share of the time instead
+        #[arg(long, conflicts_with = "json")]
+        chart: bool,
+    },
+
+    /// Follow online/offline/update events as they happen
+    Watch {
+        /// Only show events for these channels
+        names: Vec<String>,
+
+        /// Print one JSON object per line
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show the server's recent log output
+    Logs {
+        /// Number of recent entries to show first
+        #[arg(long, short = 'n', default_value_t = 100)]
+        lines: u32,
+
+        /// Keep printing new entries as they're logged
+        #[arg(long, short)]
+        follow: bool,
+
+        /// Only entries at least this severe
+        #[arg(long, value_enum)]
+        level: Option<LogLevelArg>,
+
+        /// Only entries whose target starts with this, e.g. stitch_server::adapters::webhook
+        #[arg(long)]
+        target: Option<String>,
+
+        /// Print one JSON object per line
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show the client and server versions and whether they're compatible
+    Version {
+        /// Print JSON instead of text
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Dump channels and stream history as newline-delimited JSON
+    Export {
+        /// Write to a file instead of stdout
+        #[arg(long, short = 'f')]
+        file: Option<PathBuf>,
+    },
+
+    /// Show the server's Twitch EventSub subscriptions
+    #[command(alias = "subs")]
+    Subscriptions {
+        /// Only subscriptions for this channel
+        name: Option<String>,
+        /// Only subscriptions that aren't enabled
+        #[arg(long)]
+        failing: bool,
+    },
+
+    /// Post a test message to the server's Discord channel
+    NotifyTest {
+        /// Text for the test embed
+        #[arg(long, short)]
+        message: Option<String>,
+    },
+
+    /// Reconcile the server's EventSub subscriptions with its tracked channels
+    Resync,
+
+    /// Show or change CLI settings
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+
+    /// Save an API token in the OS keyring for the current server
+    Login {
+        /// Read the token from stdin instead of prompting
+        #[arg(long)]
+        token_stdin: bool,
     },
 
+    /// Forget the API token saved for the current server
+    Logout,
+
     Completions {
         shell: clap_complete::Shell,
     },
@@ -55,6 +483,34 @@ enum Command {
     Setup,
 }
 
+#[derive(Subcommand)]
+enum ConfigCommand {
+    /// Print every setting
+    Show,
+
+    /// Print one setting
+    Get {
+        #[arg(value_parser = PossibleValuesParser::new(CliConfig::KEYS))]
+        key: String,
+    },
+
+    /// Change one setting
+    Set {
+        #[arg(value_parser = PossibleValuesParser::new(CliConfig::KEYS))]
+        key: String,
+        value: String,
+    },
+
+    /// Open the config file in $EDITOR
+    Edit,
+
+    /// Print where the config file lives
+    Path,
+
+    /// Check the config file for mistakes
+    Validate,
+}
+
 #[derive(Parser)]
 #[command(
     name = "stitch",
@@ -66,24 +522,93 @@ struct Cli {
     #[arg(long, env = "STITCH_SERVER", default_value = "http://127.0.0.1:50051")]
     server: String,
 
+    /// Config file to use [default: $XDG_CONFIG_HOME/stitch/config.toml, or
+    /// ~/.config/stitch/config.toml]
+    #[arg(long, env = "STITCH_CONFIG")]
+    config: Option<PathBuf>,
+
+    /// Use the settings of a `[profiles.<name>]` section of the config
+    #[arg(long, env = "STITCH_PROFILE")]
+    profile: Option<String>,
+
     #[arg(long, short, value_enum, env = "STITCH_OUTPUT", default_value_t = OutputFormat::Table)]
     output: OutputFormat,
 
+    /// Stable tab-separated output for scripts: no header, no color
+    ///
+    /// Columns for each command, in order. Existing columns never move;
+    /// new ones are only added at the end.
+    ///
+    ///   list     id, name, display_name, twitch_id, paused, tags, is_live,
+    ///            category, uptime_seconds, created_at, broken_reason
+    ///   status   name, live, title, category, started_at, uptime_seconds
+    ///   history  stream_id, started_at, ended_at, duration_seconds, title,
+    ///            categories
+    ///
+    /// Lists are comma-separated, missing values are empty, times are RFC 3339
+    /// in UTC, and tabs or newlines inside values become spaces. Takes
+    /// precedence over --output.
+    #[arg(long, verbatim_doc_comment)]
+    porcelain: bool,
+
+    /// Never pipe long output through $PAGER
+    #[arg(long, env = "STITCH_NO_PAGER")]
+    no_pager: bool,
+
+    /// Always ask the server instead of reusing a response from the last few
+    /// seconds
+    #[arg(long, env = "STITCH_NO_CACHE")]
+    no_cache: bool,
+
     #[arg(long, short, action = ArgAction::Count)]
     verbose: u8,
 
     #[arg(long, env = "NO_COLOR")]
     no_color: bool,
 
-    #[arg(long, default_value_t = 30)]
-    timeout: u64,
+    /// Per-request timeout in seconds [default: config `timeout`, or 30]
+    #[arg(long, env = "STITCH_TIMEOUT")]
+    timeout: Option<u64>,
 
-    #[arg(long, default_value_t = 3)]
-    retries: u32,
+    /// Attempts per request before giving up on an unreachable server
+    /// [default: config `retries`, or 3]
+    #[arg(long, env = "STITCH_RETRIES")]
+    retries: Option<u32>,
 
     #[arg(long, value_delimiter = ',', hide = true)]
     headers: Option<Vec<String>>,
 
+    #[arg(long, env = "STITCH_API_KEY", hide_env_values = true)]
+    api_key: Option<String>,
+
+    /// Client certificate (PEM) for servers requiring mutual TLS
+    #[arg(long, env = "STITCH_TLS_CERT", requires = "tls_key")]
+    tls_cert: Option<PathBuf>,
+
+    /// Private key (PEM) for --tls-cert
+    #[arg(long, env = "STITCH_TLS_KEY", requires = "tls_cert")]
+    tls_key: Option<PathBuf>,
+
+    /// CA bundle (PEM) used to verify the server certificate
+    #[arg(long, visible_alias = "ca-cert", env = "STITCH_TLS_CA")]
+    tls_ca: Option<PathBuf>,
+
+    /// Connect with TLS even if the server URL is http://
+    #[arg(long, env = "STITCH_TLS")]
+    tls: bool,
+
+    /// Server name for SNI and certificate checks, e.g. behind a reverse proxy
+    #[arg(long, env = "STITCH_TLS_SERVER_NAME")]
+    tls_server_name: Option<String>,
+
+    /// Don't verify the server certificate (implies --tls)
+    #[arg(long, env = "STITCH_INSECURE_SKIP_VERIFY")]
+    insecure_skip_verify: bool,
+
+    /// Compress requests and ask the server to compress responses
+    #[arg(long, env = "STITCH_COMPRESSION", value_enum, default_value_t = Compression::None)]
+    compression: Compression,
+
     #[command(subcommand)]
     command: Option<Command>,
 }
@@ -95,25 +620,50 @@ async fn main() -> Result<()> {
 
     let mut cli = Cli::parse();
 
-    let config = match CliConfig::load() {
+    let config_path = match &cli.config {
+        Some(path) => path.clone(),
+        None => CliConfig::default_path()?,
+    };
+    let mut config = match CliConfig::load(&config_path) {
         Ok(cfg) => cfg,
         Err(e) => {
-            eprintln!("Warning: Failed to load config: {}", e);
+            eprintln!("{}", t!("config-load-failed", error = e.to_string()));
             CliConfig::default()
         }
     };
+    if let Some(profile) = &cli.profile {
+        config = config.with_profile(profile)?;
+    }
 
     if cli.server == "http://127.0.0.1:50051" && !config.server.is_empty() {
         cli.server = config.server.clone();
     }
     if matches!(cli.output, OutputFormat::Table) && !config.output_format.is_empty() {
-        cli.output = match config.output_format.as_str() {
-            "json" => OutputFormat::Json,
-            _ => OutputFormat::Table,
-        };
+        cli.output = OutputFormat::from_str(&config.output_format, true).unwrap_or(cli.output);
+    }
+
+    cli.timeout.get_or_insert(config.timeout);
+    cli.retries.get_or_insert(config.retries);
+    if cli.api_key.is_none() {
+        cli.api_key = config.api_key.clone();
+    }
+    if cli.tls_cert.is_none() && cli.tls_key.is_none() {
+        cli.tls_cert = config.tls_cert.clone();
+        cli.tls_key = config.tls_key.clone();
+    }
+    if cli.tls_ca.is_none() {
+        cli.tls_ca = config.tls_ca.clone();
+    }
+    if cli.tls_server_name.is_none() {
+        cli.tls_server_name = config.tls_server_name.clone();
+    }
+    cli.tls |= config.tls;
+    cli.insecure_skip_verify |= config.insecure_skip_verify;
+    if cli.api_key.is_none() {
+        cli.api_key = credentials::load_token(&cli.server);
     }
 
-    if cli.no_color || !config.color {
+    if cli.no_color || cli.porcelain || !config.color {
         owo_colors::set_override(false);
     }
 
@@ -138,29 +688,169 @@ async fn main() -> Result<()> {
         }
 
         if let Command::Setup = command {
-            return setup_wizard().await;
+            return setup_wizard(&config_path).await;
+        }
+
+        if let Command::Config { command } = command {
+            return config_command(command, &config_path);
+        }
+
+        match command {
+            Command::Login { token_stdin } => return login(&cli, &config_path, *token_stdin),
+            Command::Logout => return logout(&cli, &config_path),
+            _ => {}
         }
     }
 
-    let result = execute_command(&cli, &config).await;
+    let result = execute_command(&cli, &config, config_path).await;
     result
 }
 
-async fn execute_command(cli: &Cli, _config: &CliConfig) -> Result<()> {
+async fn execute_command(cli: &Cli, config: &CliConfig, config_path: PathBuf) -> Result<()> {
     let client = create_client_with_retry(cli).await?;
     let ctx = CliContext {
         client,
-        output_format: cli.output.clone(),
+        server: cli.server.clone(),
+        output_format: cli.output,
         headers: parse_headers(cli.headers.clone()),
-        timeout: Duration::from_secs(cli.timeout),
+        timeout: Duration::from_secs(cli.timeout.unwrap_or(DEFAULT_TIMEOUT_SECS)),
+        api_key: cli.api_key.clone(),
+        retries: cli.retries.unwrap_or(DEFAULT_RETRIES),
+        porcelain: cli.porcelain,
+        pager: pager::resolve(cli.no_pager || !config.paging, config.pager.as_deref()),
+        aliases: config.alias.clone(),
+        config_path,
+        cache: ResponseCache::new(&cli.server, cli.api_key.as_deref()),
+        no_cache: cli.no_cache,
     };
 
+    if matches!(&cli.command, Some(command) if !matches!(command, Command::Version { .. })) {
+        check_server_version(&ctx).await;
+    }
+
     match &cli.command {
         None => interactive_mode(&ctx).await,
         Some(command) => match command {
-            Command::List => list_channels(&ctx).await,
-            Command::Track { name } => track_channel(&ctx, name).await,
-            Command::Untrack { name, yes } => untrack_channel(&ctx, name, *yes).await,
+            Command::List {
+                filter,
+                live_only,
+                sort,
+                watch: None,
+            } => list_channels(&ctx, filter.as_deref(), *live_only, *sort).await,
+            Command::List {
+                filter,
+                live_only,
+                sort,
+                watch: Some(interval),
+            } => {
+                watch_channels(
+                    &ctx,
+                    filter.as_deref(),
+                    *live_only,
+                    *sort,
+                    Duration::from_secs(*interval),
+                )
+                .await
+            }
+            Command::Track {
+                names,
+                from_file,
+                dry_run,
+            } => {
+                let names = match from_file {
+                    Some(path) => read_channel_names(path)?,
+                    None => names.iter().map(|n| ctx.resolve(n).to_string()).collect(),
+                };
+                if *dry_run {
+                    return dry_run_channels(&ctx, &names, false).await;
+                }
+                let result = match (names.as_slice(), from_file) {
+                    ([name], None) => track_channel(&ctx, name).await,
+                    (names, _) => track_many(&ctx, names.iter().map(String::as_str)).await,
+                };
+                ctx.invalidate_cache();
+                result
+            }
+            Command::Untrack {
+                names,
+                yes,
+                dry_run,
+            } => {
+                let names = match names.as_slice() {
+                    [] => pick_channels(&ctx, &t!("pick-untrack")).await?,
+                    names => names.iter().map(|n| ctx.resolve(n).to_string()).collect(),
+                };
+                if *dry_run && !names.is_empty() {
+                    return dry_run_channels(&ctx, &names, true).await;
+                }
+                let result = match names.as_slice() {
+                    [] => {
+                        print_info(&t!("no-channels-selected"));
+                        Ok(())
+                    }
+                    [name] => untrack_channel(&ctx, name, *yes).await,
+                    names => untrack_many(&ctx, names, *yes).await,
+                };
+                ctx.invalidate_cache();
+                result
+            }
+            Command::Prune { days, yes, dry_run } => {
+                let result = prune_channels(&ctx, *days, *yes, *dry_run).await;
+                ctx.invalidate_cache();
+                result
+            }
+            Command::Status { name } => channel_status(&ctx, ctx.resolve(name)).await,
+            Command::Open { name } => open_channel(&ctx, ctx.resolve(name)).await,
+            Command::Filter {
+                name,
+                categories,
+                muted_keywords,
+                clear,
+            } => channel_filter(&ctx, ctx.resolve(name), categories, muted_keywords, *clear).await,
+            Command::Live { sort, json } => live_streams(&ctx, *sort, *json).await,
+            Command::History {
+                name,
+                limit,
+                since,
+                json,
+            } => stream_history(&ctx, ctx.resolve(name), *limit, *since, *json).await,
+            Command::Stats {
+                channel,
+                since,
+                json,
+                chart,
+            } => stream_stats(&ctx, channel.as_deref(), *since, *json, *chart).await,
+            Command::Top {
+                since,
+                limit,
+                json,
+                chart,
+            } => top_categories(&ctx, *since, *limit, *json, *chart).await,
+            Command::Logs {
+                lines,
+                follow,
+                level,
+                target,
+                json,
+            } => {
+                let request = TailLogsRequest {
+                    backlog: *lines,
+                    min_level: level.map_or(LogLevel::Unspecified, LogLevel::from).into(),
+                    target: target.clone().unwrap_or_default(),
+                    follow: *follow,
+                };
+                tail_logs(&ctx, request, *json).await
+            }
+            Command::Watch { names, json } => watch_events(&ctx, names, *json).await,
+            Command::Export { file } => export_data(&ctx, file.as_deref()).await,
+            Command::Subscriptions { name, failing } => {
+                list_subscriptions(&ctx, name.as_deref(), *failing).await
+            }
+            Command::Resync => resync_subscriptions(&ctx).await,
+            Command::NotifyTest { message } => notify_test(&ctx, message.as_deref()).await,
+            Command::Version { json } => show_version(&ctx, *json).await,
+            Command::Config { .. } => unreachable!(),
+            Command::Login { .. } | Command::Logout => unreachable!(),
             Command::Completions { .. } => unreachable!(),
             Command::Setup => unreachable!(),
         },
@@ -170,12 +860,37 @@ async fn execute_command(cli: &Cli, _config: &CliConfig) -> Result<()> {
 #[derive(Clone)]
 struct CliContext {
     client: StitchServiceClient<Channel>,
+    /// The server URL as given, for display.
+    server: String,
     output_format: OutputFormat,
     headers: HashMap<String, String>,
     timeout: Duration,
+    api_key: Option<String>,
+    retries: u32,
+    porcelain: bool,
+    pager: Option<String>,
+    aliases: BTreeMap<String, String>,
+    config_path: PathBuf,
+    cache: Option<ResponseCache>,
+    /// Skips cached responses, while still storing fresh ones and clearing
+    /// the cache after changes.
+    no_cache: bool,
 }
 
 impl CliContext {
+    /// The login an alias stands for, or `name` itself.
+    fn resolve<'a>(&'a self, name: &'a str) -> &'a str {
+        self.aliases.get(name).map_or(name, String::as_str)
+    }
+
+    /// `shown` with the alias for `login` appended, if there is one.
+    fn with_alias(&self, shown: String, login: &str) -> String {
+        match alias_for(&self.aliases, login) {
+            Some(alias) => format!("{} ({})", shown, alias),
+            None => shown,
+        }
+    }
+
     fn create_request<T>(&self, request: T) -> Request<T> {
         let mut req = Request::new(request);
         req.set_timeout(self.timeout);
@@ -189,8 +904,120 @@ impl CliContext {
             }
         }
 
+        if let Some(key) = &self.api_key {
+            if let Ok(v) = tonic::metadata::MetadataValue::try_from(format!("Bearer {key}")) {
+                req.metadata_mut().insert("authorization", v);
+            }
+        }
+
         req
     }
+
+    /// Sends a read-only call, retrying transient failures.
+    async fn call<T, R, F, Fut>(&self, message: T, call: F) -> Result<R, tonic::Status>
+    where
+        T: Clone,
+        F: FnMut(Request<T>) -> Fut,
+        Fut: std::future::Future<Output = Result<tonic::Response<R>, tonic::Status>>,
+    {
+        self.with_retries(|| self.create_request(message.clone()), call)
+            .await
+    }
+
+    /// Like [`call`](Self::call), but answered from the response cache while
+    /// a fresh copy is there.
+    async fn cached_call<T, R, F, Fut>(
+        &self,
+        method: &str,
+        message: T,
+        call: F,
+    ) -> Result<R, tonic::Status>
+    where
+        T: prost::Message + Clone,
+        R: prost::Message + Default,
+        F: FnMut(Request<T>) -> Fut,
+        Fut: std::future::Future<Output = Result<tonic::Response<R>, tonic::Status>>,
+    {
+        let cached = self.cache.as_ref().filter(|_| !self.no_cache);
+        if let Some(response) = cached.and_then(|c| c.get(method, &message)) {
+            return Ok(response);
+        }
+        let response = self.call(message.clone(), call).await?;
+        if let Some(cache) = &self.cache {
+            cache.put(method, &message, &response);
+        }
+        Ok(response)
+    }
+
+    fn invalidate_cache(&self) {
+        if let Some(cache) = &self.cache {
+            cache.clear();
+        }
+    }
+
+    /// Sends a track/untrack style call, retrying transient failures under a
+    /// single `idempotency-key` so the server applies it at most once.
+    async fn call_idempotent<T, R, F, Fut>(&self, message: T, call: F) -> Result<R, tonic::Status>
+    where
+        T: Clone,
+        F: FnMut(Request<T>) -> Fut,
+        Fut: std::future::Future<Output = Result<tonic::Response<R>, tonic::Status>>,
+    {
+        let key = tonic::metadata::MetadataValue::try_from(uuid::Uuid::new_v4().to_string())
+            .expect("UUIDs are valid metadata values");
+        let request = || {
+            let mut request = self.create_request(message.clone());
+            request
+                .metadata_mut()
+                .insert("idempotency-key", key.clone());
+            request
+        };
+        self.with_retries(request, call).await
+    }
+
+    /// Makes up to `retries` attempts at a call, backing off exponentially
+    /// while the server is unavailable or slow to answer.
+    async fn with_retries<T, R, M, F, Fut>(
+        &self,
+        mut request: M,
+        mut call: F,
+    ) -> Result<R, tonic::Status>
+    where
+        M: FnMut() -> Request<T>,
+        F: FnMut(Request<T>) -> Fut,
+        Fut: std::future::Future<Output = Result<tonic::Response<R>, tonic::Status>>,
+    {
+        let attempts = self.retries.max(1);
+        let mut attempt = 1;
+        loop {
+            match call(request()).await {
+                Ok(response) => return Ok(response.into_inner()),
+                Err(e) if attempt < attempts && is_transient(&e) => {
+                    let delay = backoff(attempt);
+                    print_warning(&t!(
+                        "request-retrying",
+                        error = e.message(),
+                        delay = format!("{:.1}", delay.as_secs_f32()),
+                        left = attempts - attempt
+                    ));
+                    attempt += 1;
+                    sleep(delay).await;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+fn is_transient(status: &tonic::Status) -> bool {
+    matches!(status.code(), Code::Unavailable | Code::DeadlineExceeded)
+}
+
+/// Delay before the retry following `attempt`: 500ms, doubling up to 10s.
+fn backoff(attempt: u32) -> Duration {
+    Duration::from_millis(500)
+        .saturating_mul(1 << attempt.saturating_sub(1).min(16))
+        .min(Duration::from_secs(10))
 }
 
 fn parse_headers(headers: Option<Vec<String>>) -> HashMap<String, String> {
@@ -208,6 +1035,131 @@ fn parse_headers(headers: Option<Vec<String>>) -> HashMap<String, String> {
         .collect()
 }
 
+/// The status message, suffixed with the server-assigned request ID so
+/// failures can be matched against server logs.
+fn describe_status(status: &tonic::Status) -> String {
+    let mut message = status.message().to_string();
+    if let Some(id) = status
+        .metadata()
+        .get("x-request-id")
+        .and_then(|v| v.to_str().ok())
+    {
+        message.push_str(&format!(" ({})", t!("request-id", id = id)));
+    }
+    let details = status.get_error_details();
+    if let Some(bad_request) = details.bad_request() {
+        for violation in &bad_request.field_violations {
+            if violation.description == status.message() {
+                continue;
+            }
+            message.push_str(&format!(
+                "\n  {}: {}",
+                violation.field, violation.description
+            ));
+        }
+    }
+    if let Some(hint) = details
+        .error_info()
+        .and_then(|info| error_hint(&info.reason))
+    {
+        message.push_str(&format!("\n  {}", t!("hint", hint = hint)));
+    }
+    message
+}
+
+/// Suggests a next step for the server's `ErrorInfo` reasons.
+fn error_hint(reason: &str) -> Option<String> {
+    let hint = match reason {
+        errors::CHANNEL_NOT_TRACKED => t!("hint-channel-not-tracked"),
+        errors::TWITCH_USER_NOT_FOUND => t!("hint-twitch-user-not-found"),
+        errors::TWITCH_RATE_LIMITED => t!("hint-twitch-rate-limited"),
+        errors::EVENTSUB_QUOTA_EXCEEDED => t!("hint-eventsub-quota-exceeded"),
+        errors::DATABASE_UNAVAILABLE => t!("hint-database-unavailable"),
+        errors::DISCORD_PERMISSION_DENIED => t!("hint-discord-permission-denied"),
+        errors::DISCORD_UNAVAILABLE => t!("hint-discord-unavailable"),
+        _ => return None,
+    };
+    Some(hint)
+}
+
+/// Compact "3h 12m" style rendering of a stream's uptime.
+fn format_uptime(uptime: &prost_types::Duration) -> String {
+    format_seconds(uptime.seconds)
+}
+
+fn format_seconds(seconds: i64) -> String {
+    let minutes = seconds.max(0) / 60;
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{m}m"),
+        (h, m) => format!("{h}h {m}m"),
+    }
+}
+
+fn to_timestamp(dt: DateTime<Utc>) -> prost_types::Timestamp {
+    prost_types::Timestamp {
+        seconds: dt.timestamp(),
+        nanos: dt.timestamp_subsec_nanos() as i32,
+    }
+}
+
+/// Parses `--since` as a date, an RFC 3339 time, or a span back from now such
+/// as `12h`, `7d` or `2w`.
+fn parse_since(value: &str) -> Result<DateTime<Utc>, String> {
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        let midnight = date.and_time(NaiveTime::MIN);
+        return Local
+            .from_local_datetime(&midnight)
+            .earliest()
+            .map(|dt| dt.with_timezone(&Utc))
+            .ok_or_else(|| format!("invalid local date: {value}"));
+    }
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+    let split = value.char_indices().last().map_or(0, |(i, _)| i);
+    let (amount, unit) = value.split_at(split);
+    let amount: i64 = amount
+        .parse()
+        .map_err(|_| format!("expected a date (2025-08-01) or a span like 7d, got '{value}'"))?;
+    let span = match unit {
+        "m" => TimeDelta::try_minutes(amount),
+        "h" => TimeDelta::try_hours(amount),
+        "d" => TimeDelta::try_days(amount),
+        "w" => TimeDelta::try_weeks(amount),
+        _ => None,
+    }
+    .ok_or_else(|| format!("unknown span '{value}', use m, h, d or w"))?;
+    Ok(Utc::now() - span)
+}
+
+/// "Tracked <name>", noting the stream if the channel is already live.
+fn describe_tracked(name: &str, response: &TrackChannelResponse) -> String {
+    let name = response
+        .channel
+        .as_ref()
+        .map_or(name, |c| c.display_name.as_str());
+    match &response.stream {
+        Some(stream) if response.live && !stream.category.is_empty() => {
+            t!(
+                "tracked-live-playing",
+                name = name,
+                category = stream.category.as_str()
+            )
+        }
+        _ if response.live => t!("tracked-live", name = name),
+        _ => t!("tracked", name = name),
+    }
+}
+
+/// A table of `rows` with its column headers in the user's language.
+fn table<T: Tabled>(rows: &[T]) -> Table {
+    let mut table = Table::new(rows);
+    table
+        .with(TableStyle::modern())
+        .with(Modify::new(Rows::first()).with(Format::content(i18n::column)));
+    table
+}
+
 fn print_success(message: &str) {
     println!("{}", message.green());
 }
@@ -225,162 +1177,2024 @@ fn print_info(message: &str) {
 }
 
 async fn create_client_with_retry(cli: &Cli) -> Result<StitchServiceClient<Channel>> {
-    let endpoint = Endpoint::from_shared(cli.server.clone()).context("Invalid server URL")?;
+    let (endpoint, insecure) = if cli.insecure_skip_verify {
+        print_warning(&t!("tls-not-verifying"));
+        // The connector does the handshake itself; tonic must see plain HTTP.
+        let server = cli.server.replacen("https://", "http://", 1);
+        let identity = cli.tls_cert.as_deref().zip(cli.tls_key.as_deref());
+        let connector = tls::InsecureConnector::new(cli.tls_server_name.as_deref(), identity)?;
+        let endpoint = Endpoint::from_shared(server).context(t!("invalid-server-url"))?;
+        (endpoint, Some(connector))
+    } else {
+        let tls = client_tls_config(cli)?;
+        // tonic only does TLS for https:// URLs.
+        let server = match tls {
+            Some(_) => cli.server.replacen("http://", "https://", 1),
+            None => cli.server.clone(),
+        };
+        let mut endpoint = Endpoint::from_shared(server).context(t!("invalid-server-url"))?;
+        if let Some(tls) = tls {
+            endpoint = endpoint.tls_config(tls).context(t!("invalid-tls-config"))?;
+        }
+        (endpoint, None)
+    };
 
-    let mut retries = cli.retries;
+    let attempts = cli.retries.unwrap_or(DEFAULT_RETRIES).max(1);
     let mut last_error = None;
 
-    while retries > 0 {
-        match StitchServiceClient::connect(endpoint.clone()).await {
-            Ok(client) => return Ok(client),
+    for attempt in 1..=attempts {
+        let channel = match &insecure {
+            Some(connector) => {
+                let connector = connector.clone();
+                endpoint
+                    .connect_with_connector(tower::service_fn(move |uri| {
+                        connector.clone().connect(uri)
+                    }))
+                    .await
+            }
+            None => endpoint.connect().await,
+        };
+        match channel.map(StitchServiceClient::new) {
+            Ok(client) => {
+                return Ok(match cli.compression.encoding() {
+                    Some(encoding) => client.send_compressed(encoding).accept_compressed(encoding),
+                    None => client,
+                })
+            }
             Err(e) => {
                 last_error = Some(e);
-                retries -= 1;
-                if retries > 0 {
-                    print_warning(&format!(
-                        "Connection failed, retrying... ({} attempts left)",
-                        retries
+                if attempt < attempts {
+                    let delay = backoff(attempt);
+                    print_warning(&t!(
+                        "connect-retrying",
+                        delay = format!("{:.1}", delay.as_secs_f32()),
+                        left = attempts - attempt
                     ));
-                    sleep(Duration::from_secs(2)).await;
+                    sleep(delay).await;
                 }
             }
         }
     }
 
-    print_error(&format!(
-        "Failed to connect to Stitch server at {}",
-        cli.server
-    ));
+    print_error(&t!("connect-failed", server = cli.server.as_str()));
     eprintln!(
-        "\n{}:\n  • Start the server: {}\n  • Check if server is running on a different port\n  • Verify network connectivity\n\nYou can specify a different server with: {}",
-        "Possible solutions".bold(),
-        "cargo run --bin server".cyan(),
-        "--server http://HOST:PORT".cyan()
+        "\n{}",
+        t!(
+            "connect-solutions",
+            heading = t!("connect-solutions-heading").bold().to_string(),
+            command = "cargo run --bin server".cyan().to_string(),
+            flag = "--server http://HOST:PORT".cyan().to_string()
+        )
     );
 
     Err(last_error.unwrap().into())
 }
 
-async fn list_channels(ctx: &CliContext) -> Result<()> {
-    let mut client = ctx.client.clone();
-
-    let request = ctx.create_request(ListChannelsRequest {});
+/// TLS settings for the connection, if the server URL is `https://` or any TLS
+/// flag is given.
+fn client_tls_config(cli: &Cli) -> Result<Option<ClientTlsConfig>> {
+    let wants_tls = cli.tls
+        || cli.server.starts_with("https://")
+        || cli.tls_ca.is_some()
+        || cli.tls_cert.is_some()
+        || cli.tls_server_name.is_some();
+    if !wants_tls {
+        return Ok(None);
+    }
 
-    let response = client
-        .list_channels(request)
-        .await
-        .context("Failed to list channels")?;
-    let channels = response.into_inner().channels;
-    let total_channels = channels.len();
+    let read = |path: &Path| {
+        std::fs::read(path).with_context(|| t!("read-failed", path = path.display().to_string()))
+    };
+    let mut tls = ClientTlsConfig::new().with_native_roots();
+    if let Some(ca) = &cli.tls_ca {
+        tls = tls.ca_certificate(Certificate::from_pem(read(ca)?));
+    }
+    if let (Some(cert), Some(key)) = (&cli.tls_cert, &cli.tls_key) {
+        tls = tls.identity(Identity::from_pem(read(cert)?, read(key)?));
+    }
+    if let Some(name) = &cli.tls_server_name {
+        tls = tls.domain_name(name);
+    }
+    Ok(Some(tls))
+}
 
-    match ctx.output_format {
-        OutputFormat::Json => {
-            println!("{{");
-            println!("  \"channels\": [");
-            for (i, channel) in channels.iter().enumerate() {
-                println!("    {{");
-                println!("      \"id\": {},", channel.id);
-                println!("      \"name\": \"{}\",", channel.name);
-                print!("    }}");
-                if i < channels.len() - 1 {
-                    println!(",");
-                } else {
-                    println!();
-                }
-            }
-            println!("  ],");
-            println!("  \"total\": {}", total_channels);
-            println!("}}");
+/// The tracked channels, from the response cache if `cached` and it has a
+/// fresh copy.
+async fn fetch_channels(
+    ctx: &CliContext,
+    filter: Option<&str>,
+    live_only: bool,
+    sort: ListSort,
+    cached: bool,
+) -> Result<Vec<proto::stitch::Channel>> {
+    let request = ListChannelsRequest {
+        filter: filter.unwrap_or_default().to_string(),
+        live_only,
+        sort: match sort {
+            ListSort::Name => ChannelSort::Name,
+            // Uptime only exists in memory on the server, so sort that here.
+            ListSort::Id | ListSort::Uptime => ChannelSort::Id,
         }
-        OutputFormat::Table => {
-            if channels.is_empty() {
-                print_info("No channels found");
-                return Ok(());
-            }
-
-            let display_channels: Vec<ChannelDisplay> = channels
-                .into_iter()
-                .map(|c| ChannelDisplay {
-                    id: c.id,
-                    name: c.name,
-                })
-                .collect();
-
-            let table = Table::new(&display_channels)
-                .with(TableStyle::modern())
-                .to_string();
-
-            println!("{}", table);
+        .into(),
+        ..Default::default()
+    };
 
-            print_info(&format!("Total channels: {}", total_channels));
-        }
+    let call = |req| {
+        let mut client = ctx.client.clone();
+        async move { client.list_channels(req).await }
+    };
+    let response = match cached {
+        true => ctx.cached_call("ListChannels", request, call).await,
+        false => ctx.call(request, call).await,
+    };
+    let mut channels = response.context(t!("list-failed"))?.channels;
+    if let ListSort::Uptime = sort {
+        channels.sort_by_key(|c| std::cmp::Reverse(c.uptime.as_ref().map(|d| d.seconds)));
+    }
+    Ok(channels)
+}
+
+/// A table row for `channel`. With `previous`, the channels from the last
+/// refresh of `list --watch`, whatever changed since is highlighted.
+fn channel_display(
+    ctx: &CliContext,
+    channel: proto::stitch::Channel,
+    previous: Option<&HashMap<i32, proto::stitch::Channel>>,
+) -> ChannelDisplay {
+    let before = previous.map(|p| p.get(&channel.id));
+    let was_live = before.flatten().is_some_and(|c| c.is_live);
+    let live = match (channel.is_live, was_live) {
+        (true, false) if before.is_some() => {
+            format!("● {} ↑", t!("live-badge")).red().bold().to_string()
+        }
+        (true, _) => format!("● {}", t!("live-badge")).red().to_string(),
+        (false, true) => format!("{} ↓", t!("stream-ended")).yellow().to_string(),
+        (false, false) if !channel.broken_reason.is_empty() => {
+            format!("⚠ {}", t!("channel-broken")).red().to_string()
+        }
+        (false, false) => String::new(),
+    };
+    let category_changed = before
+        .flatten()
+        .is_some_and(|c| channel.is_live && was_live && c.category != channel.category);
+    ChannelDisplay {
+        id: channel.id,
+        name: match before {
+            Some(None) => ctx.with_alias(channel.name.green().bold().to_string(), &channel.name),
+            _ => ctx.with_alias(channel.name.clone(), &channel.name),
+        },
+        live,
+        uptime: channel
+            .uptime
+            .as_ref()
+            .map(format_uptime)
+            .unwrap_or_default(),
+        category: match category_changed {
+            true => channel.category.yellow().bold().to_string(),
+            false => channel.category,
+        },
+    }
+}
+
+/// The channel table, with the extra columns of `--output wide` if chosen.
+fn channel_table(
+    ctx: &CliContext,
+    channels: Vec<proto::stitch::Channel>,
+    previous: Option<&HashMap<i32, proto::stitch::Channel>>,
+) -> String {
+    if ctx.output_format != OutputFormat::Wide {
+        let rows: Vec<ChannelDisplay> = channels
+            .into_iter()
+            .map(|c| channel_display(ctx, c, previous))
+            .collect();
+        return table(&rows).to_string();
+    }
+
+    let rows: Vec<ChannelWideDisplay> = channels
+        .into_iter()
+        .map(|c| {
+            let display_name = c.display_name.clone();
+            let twitch_id = c.twitch_id.clone();
+            let tracked_since = c
+                .created_at
+                .as_ref()
+                .and_then(to_datetime)
+                .map(|t| t.with_timezone(&Local).format("%Y-%m-%d").to_string())
+                .unwrap_or_default();
+            let row = channel_display(ctx, c, previous);
+            ChannelWideDisplay {
+                id: row.id,
+                name: row.name,
+                display_name,
+                twitch_id,
+                live: row.live,
+                category: row.category,
+                uptime: row.uptime,
+                tracked_since,
+            }
+        })
+        .collect();
+    table(&rows).to_string()
+}
+
+async fn list_channels(
+    ctx: &CliContext,
+    filter: Option<&str>,
+    live_only: bool,
+    sort: ListSort,
+) -> Result<()> {
+    let channels = fetch_channels(ctx, filter, live_only, sort, true).await?;
+    let total_channels = channels.len();
+
+    if ctx.porcelain {
+        let records: Vec<ChannelRecord> = channels.iter().map(ChannelRecord::from).collect();
+        print_porcelain(&records);
+        return Ok(());
+    }
+
+    match ctx.output_format {
+        OutputFormat::Json | OutputFormat::Yaml => {
+            let list = ChannelList {
+                channels: channels.iter().map(ChannelRecord::from).collect(),
+                total: total_channels,
+            };
+            print_record(ctx.output_format, &list)?;
+        }
+        OutputFormat::Ndjson | OutputFormat::Csv => {
+            let records: Vec<ChannelRecord> = channels.iter().map(ChannelRecord::from).collect();
+            print_records(ctx.output_format, &records)?;
+        }
+        OutputFormat::Table | OutputFormat::Wide => {
+            if channels.is_empty() {
+                print_info(&t!("no-channels-found"));
+                return Ok(());
+            }
+
+            let table = channel_table(ctx, channels, None);
+            pager::page(
+                ctx.pager.as_deref(),
+                &format!(
+                    "{}\n{}",
+                    table,
+                    t!("total-channels", count = total_channels)
+                ),
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Redraws the channel table every `interval` until Ctrl-C, like
+/// `watch stitch list` but with changes since the last refresh highlighted.
+async fn watch_channels(
+    ctx: &CliContext,
+    filter: Option<&str>,
+    live_only: bool,
+    sort: ListSort,
+    interval: Duration,
+) -> Result<()> {
+    use crossterm::{cursor::MoveTo, execute, terminal};
+
+    if ctx.porcelain || ctx.output_format.is_structured() {
+        anyhow::bail!(t!("watch-needs-table"));
+    }
+
+    let mut previous: Option<HashMap<i32, proto::stitch::Channel>> = None;
+    loop {
+        let mut screen = format!(
+            "{}{:>30}\n\n",
+            t!("watch-heading", seconds = interval.as_secs()),
+            Local::now().format("%H:%M:%S")
+        );
+        match fetch_channels(ctx, filter, live_only, sort, false).await {
+            Ok(channels) => {
+                let current: HashMap<i32, proto::stitch::Channel> =
+                    channels.iter().map(|c| (c.id, c.clone())).collect();
+                let total = channels.len();
+                screen.push_str(&channel_table(ctx, channels, previous.as_ref()));
+                screen.push_str(&format!("\n{}", t!("total-channels", count = total)));
+                previous = Some(current);
+            }
+            // Keep watching through blips; the next refresh may succeed.
+            Err(e) => screen.push_str(&format!("{:#}", e).red().to_string()),
+        }
+
+        execute!(
+            io::stdout(),
+            terminal::Clear(terminal::ClearType::All),
+            MoveTo(0, 0)
+        )?;
+        println!("{}", screen);
+
+        tokio::select! {
+            _ = sleep(interval) => {}
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        }
     }
-
-    Ok(())
 }
 
 async fn track_channel(ctx: &CliContext, name: &str) -> Result<()> {
-    let mut client = ctx.client.clone();
+    let request = TrackChannelRequest {
+        name: name.to_string(),
+    };
+    let result = ctx
+        .call_idempotent(request, |req| {
+            let mut client = ctx.client.clone();
+            async move { client.track_channel(req).await }
+        })
+        .await;
+
+    match result {
+        Ok(response) => {
+            print_success(&describe_tracked(name, &response));
+        }
+        Err(e) => {
+            if e.code() == Code::AlreadyExists {
+                print_info(&t!("already-tracked", name = name));
+            } else {
+                print_error(&t!(
+                    "track-failed",
+                    name = name,
+                    error = describe_status(&e)
+                ));
+                return Err(e.into());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Channel names listed in a file, one per line, skipping blank lines and
+/// `#` comments. `-` reads stdin.
+fn read_channel_names(path: &Path) -> Result<Vec<String>> {
+    let contents = if path == Path::new("-") {
+        io::read_to_string(io::stdin()).context(t!("read-stdin-failed"))?
+    } else {
+        std::fs::read_to_string(path)
+            .with_context(|| t!("read-failed", path = path.display().to_string()))?
+    };
+
+    Ok(contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect())
+}
+
+/// Shows what tracking (or untracking) `names` would change, down to the
+/// EventSub subscriptions, without changing anything.
+async fn dry_run_channels(ctx: &CliContext, names: &[String], untrack: bool) -> Result<()> {
+    if names.is_empty() {
+        print_info(&t!("no-channel-names"));
+        return Ok(());
+    }
+
+    let mut results = Vec::with_capacity(names.len());
+    // The server checks at most 100 names per call.
+    for batch in names.chunks(100) {
+        let request = ValidateChannelsRequest {
+            names: batch.to_vec(),
+            untrack,
+        };
+        match ctx
+            .call(request, |req| {
+                let mut client = ctx.client.clone();
+                async move { client.validate_channels(req).await }
+            })
+            .await
+        {
+            Ok(response) => results.extend(response.results),
+            Err(e) => {
+                print_error(&t!("validate-failed", error = describe_status(&e)));
+                return Err(e.into());
+            }
+        }
+    }
+
+    let failed = results
+        .iter()
+        .filter(|r| {
+            matches!(
+                r.outcome(),
+                ValidationOutcome::NotFound
+                    | ValidationOutcome::Invalid
+                    | ValidationOutcome::LookupFailed
+                    | ValidationOutcome::NotTracked
+            )
+        })
+        .count();
+
+    if ctx.output_format.is_structured() {
+        let records: Vec<ValidationRecord> = results.iter().map(ValidationRecord::from).collect();
+        print_records(ctx.output_format, &records)?;
+    } else {
+        let sign = if untrack { "-" } else { "+" };
+        let rows: Vec<BulkResultDisplay> = results
+            .iter()
+            .map(|r| {
+                let outcome = outcome_label(r.outcome());
+                BulkResultDisplay {
+                    channel: match r.login.as_str() {
+                        "" => r.name.clone(),
+                        login => ctx.with_alias(login.to_string(), login),
+                    },
+                    result: match r.outcome() {
+                        ValidationOutcome::WouldTrack => outcome.green().to_string(),
+                        ValidationOutcome::WouldUntrack => outcome.yellow().to_string(),
+                        ValidationOutcome::AlreadyTracked => outcome.dimmed().to_string(),
+                        _ => outcome.red().to_string(),
+                    },
+                    details: match r.error.as_str() {
+                        "" => r
+                            .subscriptions
+                            .iter()
+                            .map(|s| format!("{}{}", sign, s.r#type))
+                            .collect::<Vec<_>>()
+                            .join(" "),
+                        error => error.to_string(),
+                    },
+                }
+            })
+            .collect();
+        println!("{}", table(&rows));
+
+        let changing: Vec<&ChannelValidation> = results
+            .iter()
+            .filter(|r| {
+                matches!(
+                    r.outcome(),
+                    ValidationOutcome::WouldTrack | ValidationOutcome::WouldUntrack
+                )
+            })
+            .collect();
+        let subscriptions: usize = changing.iter().map(|r| r.subscriptions.len()).sum();
+        print_info(&match untrack {
+            true => t!(
+                "dry-run-untrack",
+                channels = changing.len(),
+                subscriptions = subscriptions
+            ),
+            false => t!(
+                "dry-run-track",
+                channels = changing.len(),
+                subscriptions = subscriptions
+            ),
+        });
+    }
+
+    if failed > 0 {
+        anyhow::bail!(t!(
+            "dry-run-failures",
+            failed = failed,
+            total = results.len()
+        ));
+    }
+    Ok(())
+}
+
+/// Tracks each of `names` in turn, carrying on past failures, and prints a
+/// summary table at the end.
+async fn track_many<'a>(ctx: &CliContext, names: impl IntoIterator<Item = &'a str>) -> Result<()> {
+    let mut seen = std::collections::HashSet::new();
+    let names: Vec<&str> = names
+        .into_iter()
+        .filter(|name| seen.insert(name.to_lowercase()))
+        .collect();
+    if names.is_empty() {
+        print_info(&t!("no-channel-names"));
+        return Ok(());
+    }
+
+    let bar = ProgressBar::new(names.len() as u64).with_style(
+        ProgressStyle::with_template("{bar:30.cyan/blue} {pos}/{len} {msg}")
+            .expect("valid progress template"),
+    );
+    let (mut tracked, mut skipped, mut failed) = (0, 0, 0);
+    let mut rows = Vec::with_capacity(names.len());
+    for name in names {
+        bar.set_message(name.to_string());
+        let (result, details) = match track_with_backoff(ctx, name, &bar).await {
+            Ok(response) => {
+                tracked += 1;
+                (
+                    t!("result-tracked").green().to_string(),
+                    describe_tracked(name, &response),
+                )
+            }
+            Err(e) if e.code() == Code::AlreadyExists => {
+                skipped += 1;
+                (
+                    t!("result-skipped").yellow().to_string(),
+                    t!("outcome-already-tracked"),
+                )
+            }
+            Err(e) => {
+                failed += 1;
+                (t!("result-failed").red().to_string(), describe_status(&e))
+            }
+        };
+        rows.push(BulkResultDisplay {
+            channel: name.to_string(),
+            result,
+            details,
+        });
+        bar.inc(1);
+    }
+    bar.finish_and_clear();
+
+    let table = table(&rows).to_string();
+    println!("{}", table);
+    print_info(&t!(
+        "track-summary",
+        tracked = tracked,
+        skipped = skipped,
+        failed = failed
+    ));
+
+    if failed > 0 {
+        anyhow::bail!(t!("track-failures", count = failed));
+    }
+    Ok(())
+}
+
+/// Tracks `name`, waiting out the server's rate limit instead of failing.
+async fn track_with_backoff(
+    ctx: &CliContext,
+    name: &str,
+    bar: &ProgressBar,
+) -> Result<TrackChannelResponse, tonic::Status> {
+    loop {
+        let request = TrackChannelRequest {
+            name: name.to_string(),
+        };
+        let result = ctx
+            .call_idempotent(request, |req| {
+                let mut client = ctx.client.clone();
+                async move { client.track_channel(req).await }
+            })
+            .await;
+        let delay = match &result {
+            Err(e) if e.code() == Code::ResourceExhausted => e
+                .get_error_details()
+                .retry_info()
+                .and_then(|info| info.retry_delay),
+            _ => None,
+        };
+        match delay {
+            Some(delay) => {
+                bar.set_message(t!(
+                    "rate-limited-waiting",
+                    name = name,
+                    seconds = delay.as_secs() + 1
+                ));
+                sleep(delay).await;
+            }
+            None => return result,
+        }
+    }
+}
+
+/// `outcome` as shown in the dry-run table.
+fn outcome_label(outcome: ValidationOutcome) -> String {
+    match outcome {
+        ValidationOutcome::WouldTrack => t!("outcome-would-track"),
+        ValidationOutcome::WouldUntrack => t!("outcome-would-untrack"),
+        ValidationOutcome::AlreadyTracked => t!("outcome-already-tracked"),
+        ValidationOutcome::NotTracked => t!("outcome-not-tracked"),
+        ValidationOutcome::NotFound => t!("outcome-not-found"),
+        ValidationOutcome::Invalid => t!("outcome-invalid"),
+        ValidationOutcome::LookupFailed => t!("outcome-lookup-failed"),
+        ValidationOutcome::Unspecified => String::new(),
+    }
+}
+
+/// Lets the user fuzzy-search the tracked channels and toggle any number of
+/// them, until they choose "Done" or press Esc.
+async fn pick_channels(ctx: &CliContext, prompt: &str) -> Result<Vec<String>> {
+    use dialoguer::{theme::ColorfulTheme, FuzzySelect};
+
+    if !io::stdin().is_terminal() {
+        anyhow::bail!(t!("no-channel-names-given"));
+    }
+    let channels = fetch_channels(ctx, None, false, ListSort::Name, true).await?;
+    if channels.is_empty() {
+        anyhow::bail!(t!("no-channels-tracked"));
+    }
+
+    let theme = ColorfulTheme::default();
+    let mut selected = vec![false; channels.len()];
+    let mut cursor = 0;
+    loop {
+        let count = selected.iter().filter(|s| **s).count();
+        let items: Vec<String> = std::iter::once(t!("picker-done", count = count))
+            .chain(channels.iter().zip(&selected).map(|(c, picked)| {
+                let mark = if *picked { "[x]" } else { "[ ]" };
+                match c.is_live {
+                    true => format!("{} {} ● {}", mark, c.name, t!("picker-live")),
+                    false => format!("{} {}", mark, c.name),
+                }
+            }))
+            .collect();
+
+        let choice = FuzzySelect::with_theme(&theme)
+            .with_prompt(t!("picker-prompt", prompt = prompt))
+            .items(&items)
+            .default(cursor)
+            .interact_opt()?;
+        match choice {
+            None | Some(0) => break,
+            Some(i) => {
+                selected[i - 1] = !selected[i - 1];
+                cursor = i;
+            }
+        }
+    }
+
+    Ok(channels
+        .into_iter()
+        .zip(selected)
+        .filter(|(_, picked)| *picked)
+        .map(|(c, _)| c.name)
+        .collect())
+}
+
+fn confirm(prompt: &str) -> Result<bool> {
+    print!("{} {} ", prompt, t!("confirm-choices"));
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+
+    // "y" works in every language, so scripts piping it in keep working.
+    let input = input.trim();
+    Ok(input.eq_ignore_ascii_case("y") || input.eq_ignore_ascii_case(&t!("confirm-yes")))
+}
+
+async fn untrack_channel(ctx: &CliContext, name: &str, yes: bool) -> Result<()> {
+    if !yes && !confirm(&t!("untrack-confirm", name = name))? {
+        print_info(&t!("operation-cancelled"));
+        return Ok(());
+    }
+
+    let request = UntrackChannelRequest {
+        name: name.to_string(),
+    };
+    let result = ctx
+        .call_idempotent(request, |req| {
+            let mut client = ctx.client.clone();
+            async move { client.untrack_channel(req).await }
+        })
+        .await;
+
+    match result {
+        Ok(_) => {
+            print_success(&t!("untracked", name = name));
+        }
+        Err(e) => {
+            print_error(&t!(
+                "untrack-failed",
+                name = name,
+                error = describe_status(&e)
+            ));
+            return Err(e.into());
+        }
+    }
+
+    Ok(())
+}
+
+async fn untrack_many(ctx: &CliContext, names: &[String], yes: bool) -> Result<()> {
+    let prompt = t!(
+        "untrack-many-confirm",
+        count = names.len(),
+        names = names.join(", ")
+    );
+    if !yes && !confirm(&prompt)? {
+        print_info(&t!("operation-cancelled"));
+        return Ok(());
+    }
+
+    let request = UntrackChannelsRequest {
+        names: names.to_vec(),
+    };
+    let result = ctx
+        .call_idempotent(request, |req| {
+            let mut client = ctx.client.clone();
+            async move { client.untrack_channels(req).await }
+        })
+        .await;
+    let results = match result {
+        Ok(response) => response.results,
+        Err(e) => {
+            print_error(&t!("untrack-many-failed", error = describe_status(&e)));
+            return Err(e.into());
+        }
+    };
+
+    let failed = results.iter().filter(|r| !r.success).count();
+    let rows: Vec<BulkResultDisplay> = results
+        .into_iter()
+        .map(|result| BulkResultDisplay {
+            channel: result.name,
+            result: if result.success {
+                t!("result-untracked").green().to_string()
+            } else {
+                t!("result-failed").red().to_string()
+            },
+            details: result.error,
+        })
+        .collect();
+
+    let table = table(&rows).to_string();
+    println!("{}", table);
+    print_info(&t!(
+        "untrack-summary",
+        untracked = rows.len() - failed,
+        failed = failed
+    ));
+
+    if failed > 0 {
+        anyhow::bail!(t!("untrack-failures", count = failed));
+    }
+    Ok(())
+}
+
+/// Lists channels without a stream in `days` and untracks the ones the user
+/// keeps selected, or all of them with `yes`.
+async fn prune_channels(ctx: &CliContext, days: u32, yes: bool, dry_run: bool) -> Result<()> {
+    use dialoguer::{theme::ColorfulTheme, MultiSelect};
+
+    let inactive = match ctx
+        .call(ListInactiveChannelsRequest { days }, |req| {
+            let mut client = ctx.client.clone();
+            async move { client.list_inactive_channels(req).await }
+        })
+        .await
+    {
+        Ok(response) => response.channels,
+        Err(e) => {
+            print_error(&t!("prune-failed", error = describe_status(&e)));
+            return Err(e.into());
+        }
+    };
+
+    if ctx.output_format.is_structured() && (dry_run || !yes) {
+        let records: Vec<InactiveRecord> = inactive.iter().map(InactiveRecord::from).collect();
+        return print_records(ctx.output_format, &records);
+    }
+    if inactive.is_empty() {
+        print_success(&t!("prune-none", days = days));
+        return Ok(());
+    }
+
+    let date = |ts: Option<&prost_types::Timestamp>| {
+        ts.and_then(to_datetime)
+            .map(|t| t.with_timezone(&Local).format("%Y-%m-%d").to_string())
+    };
+    let now = Utc::now();
+    let channels: Vec<_> = inactive
+        .iter()
+        .filter_map(|c| {
+            let channel = c.channel.as_ref()?;
+            let last_stream = match c.last_stream_at.as_ref().and_then(to_datetime) {
+                Some(at) => t!(
+                    "prune-last-stream",
+                    date = date(c.last_stream_at.as_ref()).unwrap_or_default(),
+                    days = (now - at).num_days()
+                ),
+                None => t!("prune-never-streamed"),
+            };
+            Some((channel, last_stream))
+        })
+        .collect();
+
+    if dry_run || !yes {
+        let rows: Vec<InactiveDisplay> = channels
+            .iter()
+            .map(|(channel, last_stream)| InactiveDisplay {
+                channel: ctx.with_alias(channel.name.clone(), &channel.name),
+                last_stream: last_stream.clone(),
+                tracked_since: date(channel.created_at.as_ref()).unwrap_or_default(),
+            })
+            .collect();
+        println!("{}", table(&rows));
+        print_info(&t!("prune-found", count = rows.len(), days = days));
+    }
+    if dry_run {
+        return Ok(());
+    }
+
+    let names: Vec<String> = if yes {
+        channels
+            .iter()
+            .map(|(channel, _)| channel.name.clone())
+            .collect()
+    } else {
+        if !io::stdin().is_terminal() {
+            anyhow::bail!(t!("prune-needs-yes"));
+        }
+        let items: Vec<String> = channels
+            .iter()
+            .map(|(channel, last_stream)| format!("{} ({last_stream})", channel.name))
+            .collect();
+        let picked = MultiSelect::with_theme(&ColorfulTheme::default())
+            .with_prompt(t!("prune-prompt"))
+            .items(&items)
+            .defaults(&vec![true; items.len()])
+            .interact_opt()?;
+        match picked {
+            Some(picked) => picked
+                .into_iter()
+                .map(|i| channels[i].0.name.clone())
+                .collect(),
+            None => Vec::new(),
+        }
+    };
+
+    match names.as_slice() {
+        [] => {
+            print_info(&t!("no-channels-selected"));
+            Ok(())
+        }
+        // Picking the channels was the confirmation.
+        names => untrack_many(ctx, names, true).await,
+    }
+}
+
+async fn channel_status(ctx: &CliContext, name: &str) -> Result<()> {
+    let request = GetChannelStatusRequest {
+        name: name.to_string(),
+    };
+    let status = match ctx
+        .cached_call("GetChannelStatus", request, |req| {
+            let mut client = ctx.client.clone();
+            async move { client.get_channel_status(req).await }
+        })
+        .await
+    {
+        Ok(response) => response,
+        Err(e) => {
+            print_error(&t!(
+                "status-failed",
+                name = name,
+                error = describe_status(&e)
+            ));
+            return Err(e.into());
+        }
+    };
+
+    if ctx.porcelain {
+        print_porcelain(&[StatusRecord::from(&status)]);
+        return Ok(());
+    }
+
+    match ctx.output_format {
+        OutputFormat::Table | OutputFormat::Wide => match status.stream.filter(|_| status.live) {
+            Some(stream) => {
+                let shown = ctx.with_alias(stream.display_name.bold().to_string(), name);
+                let state = t!("live-badge").red().bold().to_string();
+                println!("{}", t!("status-is", name = shown, state = state));
+                println!("  {} {}", t!("label-title").bold(), stream.title);
+                println!("  {} {}", t!("label-category").bold(), stream.category);
+                if let Some(uptime) = &stream.uptime {
+                    println!("  {} {}", t!("label-uptime").bold(), format_uptime(uptime));
+                }
+            }
+            None => println!(
+                "{}",
+                t!(
+                    "status-is",
+                    name = ctx.with_alias(status.name.bold().to_string(), &status.name),
+                    state = t!("status-offline").dimmed().to_string()
+                )
+            ),
+        },
+        format => print_record(format, &StatusRecord::from(&status))?,
+    }
+
+    Ok(())
+}
+
+/// Shows a channel's Discord filters, after adding `categories` and
+/// `muted_keywords` to them (or replacing them with `clear`) when given any.
+async fn channel_filter(
+    ctx: &CliContext,
+    name: &str,
+    categories: &[String],
+    muted_keywords: &[String],
+    clear: bool,
+) -> Result<()> {
+    let request = GetChannelSettingsRequest {
+        name: name.to_string(),
+    };
+    let mut settings = match ctx
+        .call(request, |req| {
+            let mut client = ctx.client.clone();
+            async move { client.get_channel_settings(req).await }
+        })
+        .await
+    {
+        Ok(response) => response.settings.unwrap_or_default(),
+        Err(e) => {
+            print_error(&t!(
+                "filter-failed",
+                name = name,
+                error = describe_status(&e)
+            ));
+            return Err(e.into());
+        }
+    };
+
+    if clear || !categories.is_empty() || !muted_keywords.is_empty() {
+        if clear {
+            settings.categories.clear();
+            settings.muted_keywords.clear();
+        }
+        settings.categories.extend_from_slice(categories);
+        settings.muted_keywords.extend_from_slice(muted_keywords);
+        let request = UpdateChannelSettingsRequest {
+            name: name.to_string(),
+            settings: Some(settings),
+        };
+        settings = match ctx
+            .call_idempotent(request, |req| {
+                let mut client = ctx.client.clone();
+                async move { client.update_channel_settings(req).await }
+            })
+            .await
+        {
+            Ok(response) => response.settings.unwrap_or_default(),
+            Err(e) => {
+                print_error(&t!(
+                    "filter-update-failed",
+                    name = name,
+                    error = describe_status(&e)
+                ));
+                return Err(e.into());
+            }
+        };
+        if matches!(ctx.output_format, OutputFormat::Table | OutputFormat::Wide) {
+            print_success(&t!("filter-updated", name = name));
+        }
+    }
+
+    match ctx.output_format {
+        OutputFormat::Table | OutputFormat::Wide => {
+            let list = |items: &[String], empty: String| match items {
+                [] => empty.dimmed().to_string(),
+                items => items.join(", "),
+            };
+            println!(
+                "  {} {}",
+                t!("label-categories").bold(),
+                list(&settings.categories, t!("filter-any-category"))
+            );
+            println!(
+                "  {} {}",
+                t!("label-muted-keywords").bold(),
+                list(&settings.muted_keywords, t!("filter-no-keywords"))
+            );
+        }
+        format => print_record(
+            format,
+            &FilterRecord {
+                name: name.to_string(),
+                categories: settings.categories,
+                muted_keywords: settings.muted_keywords,
+            },
+        )?,
+    }
+    Ok(())
+}
+
+async fn open_channel(ctx: &CliContext, name: &str) -> Result<()> {
+    let request = GetChannelStatusRequest {
+        name: name.to_string(),
+    };
+    let status = match ctx
+        .cached_call("GetChannelStatus", request, |req| {
+            let mut client = ctx.client.clone();
+            async move { client.get_channel_status(req).await }
+        })
+        .await
+    {
+        Ok(response) => response,
+        Err(e) => {
+            print_error(&t!("open-failed", name = name, error = describe_status(&e)));
+            return Err(e.into());
+        }
+    };
+
+    let url = channel_url(&status.name);
+    open_url(&url)?;
+    print_info(&t!("opened", url = url.as_str()));
+    Ok(())
+}
+
+/// The configured alias for `login`, if any.
+fn alias_for<'a>(aliases: &'a BTreeMap<String, String>, login: &str) -> Option<&'a str> {
+    aliases
+        .iter()
+        .find(|(_, target)| target.eq_ignore_ascii_case(login))
+        .map(|(alias, _)| alias.as_str())
+}
+
+fn channel_url(login: &str) -> String {
+    format!("https://twitch.tv/{}", login)
+}
+
+/// Opens `url` with the platform's default handler.
+fn open_url(url: &str) -> Result<()> {
+    let mut command = if cfg!(target_os = "windows") {
+        let mut command = std::process::Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else if cfg!(target_os = "macos") {
+        std::process::Command::new("open")
+    } else {
+        std::process::Command::new("xdg-open")
+    };
+    let status = command
+        .arg(url)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .with_context(|| t!("browser-launch-failed", url = url))?;
+    if !status.success() {
+        anyhow::bail!(t!("browser-failed", url = url, status = status.to_string()));
+    }
+    Ok(())
+}
+
+async fn live_streams(ctx: &CliContext, sort: LiveSort, json: bool) -> Result<()> {
+    let mut streams = ctx
+        .call(ListLiveStreamsRequest {}, |req| {
+            let mut client = ctx.client.clone();
+            async move { client.list_live_streams(req).await }
+        })
+        .await
+        .context(t!("live-failed"))?
+        .streams;
+
+    let uptime = |s: &LiveStream| s.uptime.as_ref().map_or(0, |d| d.seconds);
+    match sort {
+        LiveSort::Uptime => streams.sort_by_key(|s| std::cmp::Reverse(uptime(s))),
+        LiveSort::Viewers => streams.sort_by_key(|s| std::cmp::Reverse(s.viewer_count)),
+        LiveSort::Name => streams.sort_by(|a, b| a.name.cmp(&b.name)),
+    }
+
+    let format = if json {
+        OutputFormat::Json
+    } else {
+        ctx.output_format
+    };
+    if format.is_structured() {
+        let records: Vec<LiveRecord> = streams.iter().map(LiveRecord::from).collect();
+        return print_records(format, &records);
+    }
+
+    if streams.is_empty() {
+        print_info(&t!("nobody-live"));
+        return Ok(());
+    }
 
-    let request = ctx.create_request(TrackChannelRequest {
-        name: name.to_string(),
-    });
+    let total = streams.len();
+    let rows: Vec<LiveDisplay> = streams
+        .into_iter()
+        .map(|stream| LiveDisplay {
+            uptime: stream
+                .uptime
+                .as_ref()
+                .map(format_uptime)
+                .unwrap_or_default(),
+            channel: ctx.with_alias(stream.display_name, &stream.name),
+            viewers: stream.viewer_count,
+            category: stream.category,
+            title: stream.title,
+        })
+        .collect();
 
-    match client.track_channel(request).await {
-        Ok(_) => {
-            print_success(&format!("Successfully tracked channel: {}", name));
+    let table = table(&rows).to_string();
+    println!("{}", table);
+    print_info(&t!("live-total", count = total));
+
+    Ok(())
+}
+
+async fn list_subscriptions(ctx: &CliContext, name: Option<&str>, failing: bool) -> Result<()> {
+    let request = GetEventSubSubscriptionsRequest {
+        name: name.unwrap_or_default().to_string(),
+    };
+    let mut subscriptions = match ctx
+        .call(request, |req| {
+            let mut client = ctx.client.clone();
+            async move { client.get_event_sub_subscriptions(req).await }
+        })
+        .await
+    {
+        Ok(response) => response.subscriptions,
+        Err(e) => {
+            print_error(&t!("subscriptions-failed", error = describe_status(&e)));
+            return Err(e.into());
         }
+    };
+    if failing {
+        subscriptions.retain(|s| s.status != "enabled");
+    }
+    subscriptions.sort_by(|a, b| (&a.name, &a.r#type).cmp(&(&b.name, &b.r#type)));
+
+    if ctx.output_format.is_structured() {
+        let records: Vec<SubscriptionRecord> =
+            subscriptions.iter().map(SubscriptionRecord::from).collect();
+        return print_records(ctx.output_format, &records);
+    }
+
+    if subscriptions.is_empty() {
+        print_info(&t!("no-subscriptions"));
+        return Ok(());
+    }
+
+    let total = subscriptions.len();
+    let enabled = subscriptions
+        .iter()
+        .filter(|s| s.status == "enabled")
+        .count();
+    let rows: Vec<SubscriptionDisplay> = subscriptions
+        .into_iter()
+        .map(|sub| SubscriptionDisplay {
+            channel: if sub.name.is_empty() {
+                t!("subscription-untracked", id = sub.twitch_id)
+                    .dimmed()
+                    .to_string()
+            } else {
+                sub.name
+            },
+            kind: sub.r#type,
+            status: match sub.status.as_str() {
+                "enabled" => sub.status.green().to_string(),
+                s if s.ends_with("pending") => sub.status.yellow().to_string(),
+                _ => sub.status.red().to_string(),
+            },
+            created: sub
+                .created_at
+                .as_ref()
+                .and_then(to_datetime)
+                .map(|t| t.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_default(),
+        })
+        .collect();
+
+    let table = table(&rows).to_string();
+    println!("{}", table);
+    print_info(&t!("subscriptions-total", total = total, enabled = enabled));
+
+    Ok(())
+}
+
+async fn show_version(ctx: &CliContext, json: bool) -> Result<()> {
+    let info = match ctx
+        .call(GetServerInfoRequest {}, |req| {
+            let mut client = ctx.client.clone();
+            async move { client.get_server_info(req).await }
+        })
+        .await
+    {
+        Ok(info) => info,
         Err(e) => {
-            if e.code() == Code::AlreadyExists {
-                print_info(&format!("Channel '{}' is already being tracked", name));
+            println!("{} {}", t!("label-client").bold(), version::CLIENT_VERSION);
+            print_error(&t!("server-version-failed", error = describe_status(&e)));
+            return Err(e.into());
+        }
+    };
+
+    let record = VersionRecord {
+        client_version: version::CLIENT_VERSION,
+        client_proto_revision: PROTO_REVISION,
+        server_version: info.version,
+        server_proto_revision: info.proto_revision,
+    };
+    let format = if json {
+        OutputFormat::Json
+    } else {
+        ctx.output_format
+    };
+    if format.is_structured() {
+        return print_record(format, &record);
+    }
+
+    println!(
+        "{} {}",
+        t!("label-client").bold(),
+        t!(
+            "version-with-protocol",
+            version = record.client_version,
+            protocol = record.client_proto_revision
+        )
+    );
+    println!(
+        "{} {}",
+        t!("label-server").bold(),
+        t!(
+            "version-with-protocol",
+            version = record.server_version.as_str(),
+            protocol = match record.server_proto_revision.as_str() {
+                "" => t!("protocol-unknown"),
+                revision => revision.to_string(),
+            }
+        )
+    );
+    if !warn_version_mismatch(&record.server_version) {
+        if record.server_proto_revision != PROTO_REVISION {
+            print_warning(&t!("protocol-mismatch"));
+        } else {
+            print_success(&t!("versions-compatible"));
+        }
+    }
+    Ok(())
+}
+
+/// Warns about a server on another major version, returning whether it did.
+fn warn_version_mismatch(server_version: &str) -> bool {
+    if version::same_major(version::CLIENT_VERSION, server_version) {
+        return false;
+    }
+    print_warning(&t!(
+        "major-version-mismatch",
+        warning = t!("label-warning").bold().to_string(),
+        client = version::CLIENT_VERSION,
+        server = server_version
+    ));
+    true
+}
+
+/// Compares versions with the server at most once a day, so a mismatch is
+/// noticed without running `stitch version`. Failures are ignored; the
+/// command itself will report an unreachable server.
+async fn check_server_version(ctx: &CliContext) {
+    if !version::check_due() {
+        return;
+    }
+    let mut client = ctx.client.clone();
+    let request = ctx.create_request(GetServerInfoRequest {});
+    if let Ok(info) = client.get_server_info(request).await {
+        warn_version_mismatch(&info.get_ref().version);
+    }
+}
+
+async fn notify_test(ctx: &CliContext, message: Option<&str>) -> Result<()> {
+    let mut client = ctx.client.clone();
+
+    let request = ctx.create_request(SendTestNotificationRequest {
+        message: message.unwrap_or_default().to_string(),
+    });
+    match client.send_test_notification(request).await {
+        Ok(response) => {
+            let response = response.into_inner();
+            // Servers from before multiple channels only fill the legacy fields.
+            let sent = if response.messages.is_empty() {
+                vec![PostedMessage {
+                    discord_channel_id: response.discord_channel_id,
+                    message_id: response.message_id,
+                    message_url: response.message_url,
+                }]
             } else {
-                print_error(&format!(
-                    "Failed to track channel '{}': {}",
-                    name,
-                    e.message()
+                response.messages
+            };
+            for message in sent {
+                print_success(&t!(
+                    "notify-sent",
+                    channel = message.discord_channel_id.as_str()
                 ));
-                return Err(e.into());
+                print_info(&message.message_url);
             }
+            Ok(())
+        }
+        Err(e) => {
+            print_error(&t!("notify-failed", error = describe_status(&e)));
+            Err(e.into())
+        }
+    }
+}
+
+async fn resync_subscriptions(ctx: &CliContext) -> Result<()> {
+    let mut client = ctx.client.clone();
+
+    let spinner = (!ctx.output_format.is_structured()).then(|| {
+        let spinner = ProgressBar::new_spinner().with_message(t!("resyncing"));
+        spinner.enable_steady_tick(Duration::from_millis(100));
+        spinner
+    });
+    let request = ctx.create_request(ResyncSubscriptionsRequest {});
+    let result = client.resync_subscriptions(request).await;
+    if let Some(spinner) = spinner {
+        spinner.finish_and_clear();
+    }
+    let report = match result {
+        Ok(response) => response.into_inner(),
+        Err(e) => {
+            print_error(&t!("resync-failed", error = describe_status(&e)));
+            return Err(e.into());
+        }
+    };
+
+    if ctx.output_format.is_structured() {
+        print_record(ctx.output_format, &ResyncRecord::from(&report))?;
+    } else {
+        let sections = [
+            (t!("change-added").green().to_string(), &report.added),
+            (t!("change-removed").yellow().to_string(), &report.removed),
+            (t!("change-stale").yellow().to_string(), &report.stale),
+            (t!("result-failed").red().to_string(), &report.failed),
+        ];
+        let rows: Vec<ChangeDisplay> = sections
+            .iter()
+            .flat_map(|(change, changes)| {
+                changes.iter().map(move |c| ChangeDisplay {
+                    change: change.clone(),
+                    channel: if c.name.is_empty() {
+                        c.twitch_id.clone()
+                    } else {
+                        c.name.clone()
+                    },
+                    kind: c.r#type.clone(),
+                    details: c.error.clone(),
+                })
+            })
+            .collect();
+
+        if rows.is_empty() {
+            print_success(&t!("resync-in-sync"));
+        } else {
+            let table = table(&rows).to_string();
+            println!("{}", table);
         }
+        print_info(&t!(
+            "resync-summary",
+            added = report.added.len(),
+            removed = report.removed.len(),
+            stale = report.stale.len(),
+            failed = report.failed.len(),
+            kept = report.kept
+        ));
     }
 
+    if !report.failed.is_empty() {
+        anyhow::bail!(t!("resync-failures", count = report.failed.len()));
+    }
     Ok(())
 }
 
-async fn untrack_channel(ctx: &CliContext, name: &str, yes: bool) -> Result<()> {
-    if !yes {
-        print!("Are you sure you want to untrack '{}'? [y/N] ", name);
-        io::stdout().flush()?;
+async fn stream_history(
+    ctx: &CliContext,
+    name: &str,
+    limit: u32,
+    since: Option<DateTime<Utc>>,
+    json: bool,
+) -> Result<()> {
+    let request = GetStreamHistoryRequest {
+        name: name.to_string(),
+        limit,
+        offset: 0,
+        since: since.map(to_timestamp),
+    };
+    let streams = match ctx
+        .call(request, |req| {
+            let mut client = ctx.client.clone();
+            async move { client.get_stream_history(req).await }
+        })
+        .await
+    {
+        Ok(response) => response.streams,
+        Err(e) => {
+            print_error(&t!(
+                "history-failed",
+                name = name,
+                error = describe_status(&e)
+            ));
+            return Err(e.into());
+        }
+    };
 
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
+    let now = Utc::now();
+    if ctx.porcelain {
+        let records: Vec<StreamRecord> = streams
+            .iter()
+            .map(|stream| StreamRecord::new(stream, now))
+            .collect();
+        print_porcelain(&records);
+        return Ok(());
+    }
 
-        if !input.trim().eq_ignore_ascii_case("y") {
-            print_info("Operation cancelled");
-            return Ok(());
+    let format = if json {
+        OutputFormat::Json
+    } else {
+        ctx.output_format
+    };
+    if format.is_structured() {
+        let records: Vec<StreamRecord> = streams
+            .iter()
+            .map(|stream| StreamRecord::new(stream, now))
+            .collect();
+        return print_records(format, &records);
+    }
+
+    if streams.is_empty() {
+        print_info(&t!("no-streams-for", name = name));
+        return Ok(());
+    }
+
+    let rows: Vec<StreamDisplay> = streams
+        .iter()
+        .map(|stream| {
+            let record = StreamRecord::new(stream, now);
+            let date = record
+                .started_at
+                .map(|dt| {
+                    dt.with_timezone(&Local)
+                        .format("%Y-%m-%d %H:%M")
+                        .to_string()
+                })
+                .unwrap_or_default();
+            let duration = record
+                .duration_seconds
+                .map(format_seconds)
+                .unwrap_or_default();
+            StreamDisplay {
+                date,
+                duration: if stream.ended_at.is_none() {
+                    t!("duration-live", duration = duration)
+                } else {
+                    duration
+                },
+                title: stream.title.clone(),
+                categories: stream
+                    .categories
+                    .iter()
+                    .take(3)
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            }
+        })
+        .collect();
+
+    let table = table(&rows).to_string();
+    pager::page(ctx.pager.as_deref(), &table);
+
+    Ok(())
+}
+
+async fn stream_stats(
+    ctx: &CliContext,
+    channel: Option<&str>,
+    since: Option<DateTime<Utc>>,
+    json: bool,
+    chart: bool,
+) -> Result<()> {
+    let request = GetStreamStatsRequest {
+        name: channel.unwrap_or_default().to_string(),
+        since: since.map(to_timestamp),
+    };
+    let stats = match ctx
+        .call(request, |req| {
+            let mut client = ctx.client.clone();
+            async move { client.get_stream_stats(req).await }
+        })
+        .await
+    {
+        Ok(response) => response.channels,
+        Err(e) => {
+            print_error(&t!("stats-failed", error = describe_status(&e)));
+            return Err(e.into());
+        }
+    };
+
+    let seconds = |d: &Option<prost_types::Duration>| d.as_ref().map_or(0, |d| d.seconds);
+
+    let format = if json {
+        OutputFormat::Json
+    } else {
+        ctx.output_format
+    };
+    if format.is_structured() {
+        let records: Vec<StatsRecord> = stats.iter().map(StatsRecord::from).collect();
+        return print_records(format, &records);
+    }
+
+    if stats.iter().all(|c| c.total_streams == 0) {
+        print_info(&t!("no-streams"));
+        return Ok(());
+    }
+    if chart {
+        pager::page(ctx.pager.as_deref(), &stats_chart(&stats, since));
+        return Ok(());
+    }
+
+    let rows: Vec<StatsDisplay> = stats
+        .into_iter()
+        .map(|channel| StatsDisplay {
+            hours: format!("{:.1}", seconds(&channel.total_duration) as f64 / 3600.0),
+            average: format_seconds(seconds(&channel.average_duration)),
+            // Average and peak, left blank before any were sampled.
+            viewers: match channel.peak_viewers {
+                0 => String::new(),
+                peak => format!("{} / {}", channel.average_viewers, peak),
+            },
+            top_games: channel
+                .top_categories
+                .iter()
+                .take(3)
+                .map(|c| c.category.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+            channel: channel.display_name,
+            streams: channel.total_streams,
+        })
+        .collect();
+
+    let table = table(&rows).to_string();
+    pager::page(ctx.pager.as_deref(), &table);
+
+    Ok(())
+}
+
+/// `stats --chart`: per channel, a sparkline of hours per week and bars for
+/// the top categories' share of the time streamed.
+fn stats_chart(stats: &[proto::stitch::ChannelStats], since: Option<DateTime<Utc>>) -> String {
+    const BAR_WIDTH: usize = 24;
+
+    let now = Utc::now();
+    let seconds = |d: &Option<prost_types::Duration>| d.as_ref().map_or(0, |d| d.seconds);
+    let weeks = |c: &proto::stitch::ChannelStats| -> Vec<(DateTime<Utc>, i64)> {
+        c.weekly
+            .iter()
+            .filter_map(|w| Some((to_datetime(w.week_start.as_ref()?)?, seconds(&w.duration))))
+            .collect()
+    };
+    let streamed: Vec<_> = stats.iter().filter(|c| c.total_streams > 0).collect();
+
+    let mut out = String::new();
+    if streamed.len() > 1 {
+        let all: Vec<_> = streamed.iter().flat_map(|c| weeks(c)).collect();
+        out.push_str(&weekly_chart(
+            &t!("chart-all-channels"),
+            &chart::weekly_hours(&all, since, now),
+        ));
+        out.push('\n');
+    }
+    for channel in streamed {
+        out.push_str(&weekly_chart(
+            &channel.display_name,
+            &chart::weekly_hours(&weeks(channel), since, now),
+        ));
+        let total = seconds(&channel.total_duration) as f64;
+        let width = channel
+            .top_categories
+            .iter()
+            .map(|c| c.category.width())
+            .max()
+            .unwrap_or(0);
+        for category in &channel.top_categories {
+            let secs = seconds(&category.duration) as f64;
+            out.push_str(&format!(
+                "  {}  {}  {:>3.0}%  {:.1}h\n",
+                chart::pad(&category.category, width),
+                chart::pad(&chart::bar(secs, total, BAR_WIDTH), BAR_WIDTH).magenta(),
+                secs / total.max(1.0) * 100.0,
+                secs / 3600.0
+            ));
+        }
+        out.push('\n');
+    }
+    out.trim_end().to_string()
+}
+
+/// A label over a sparkline of `series`, with its total and busiest week.
+fn weekly_chart(label: &str, series: &[(DateTime<Utc>, f64)]) -> String {
+    let hours: Vec<f64> = series.iter().map(|(_, h)| *h).collect();
+    let total: f64 = hours.iter().sum();
+    let busiest = series
+        .iter()
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .filter(|(_, h)| *h > 0.0);
+    let mut line = format!(
+        "{}\n  {}  {}",
+        label.bold(),
+        chart::sparkline(&hours).cyan(),
+        t!(
+            "chart-weekly-total",
+            hours = format!("{total:.1}"),
+            weeks = series.len()
+        )
+    );
+    if let Some((week, peak)) = busiest {
+        line.push_str(&t!(
+            "chart-busiest",
+            hours = format!("{peak:.1}"),
+            week = week.format("%Y-%m-%d").to_string()
+        ));
+    }
+    line.push('\n');
+    line
+}
+
+/// `top --chart`: each category's share of the time the listed ones add up to.
+fn top_chart(categories: &[CategoryStats]) -> String {
+    const BAR_WIDTH: usize = 30;
+
+    let seconds = |c: &CategoryStats| c.duration.as_ref().map_or(0, |d| d.seconds) as f64;
+    let total: f64 = categories.iter().map(seconds).sum();
+    let longest = categories.iter().map(seconds).fold(0.0, f64::max);
+    let width = categories
+        .iter()
+        .map(|c| c.category.width())
+        .max()
+        .unwrap_or(0);
+
+    let mut out = String::new();
+    for (i, category) in categories.iter().enumerate() {
+        out.push_str(&format!(
+            "{:>3}. {}  {}  {:>3.0}%  {:.1}h\n",
+            i + 1,
+            chart::pad(&category.category, width),
+            chart::pad(
+                &chart::bar(seconds(category), longest, BAR_WIDTH),
+                BAR_WIDTH
+            )
+            .magenta(),
+            seconds(category) / total.max(1.0) * 100.0,
+            seconds(category) / 3600.0
+        ));
+    }
+    out.push('\n');
+    out.push_str(&t!(
+        "chart-top-total",
+        hours = format!("{:.1}", total / 3600.0),
+        count = categories.len()
+    ));
+    out
+}
+
+async fn top_categories(
+    ctx: &CliContext,
+    since: Option<DateTime<Utc>>,
+    limit: u32,
+    json: bool,
+    chart: bool,
+) -> Result<()> {
+    const BAR_WIDTH: i64 = 20;
+
+    let request = GetTopCategoriesRequest {
+        since: since.map(to_timestamp),
+        limit,
+    };
+    let categories = match ctx
+        .call(request, |req| {
+            let mut client = ctx.client.clone();
+            async move { client.get_top_categories(req).await }
+        })
+        .await
+    {
+        Ok(response) => response.categories,
+        Err(e) => {
+            print_error(&t!("top-failed", error = describe_status(&e)));
+            return Err(e.into());
         }
+    };
+
+    let format = if json {
+        OutputFormat::Json
+    } else {
+        ctx.output_format
+    };
+    if format.is_structured() {
+        let records: Vec<TopCategoryRecord> =
+            categories.iter().map(TopCategoryRecord::from).collect();
+        return print_records(format, &records);
+    }
+
+    if categories.is_empty() {
+        print_info(&t!("no-streams"));
+        return Ok(());
+    }
+    if chart {
+        pager::page(ctx.pager.as_deref(), &top_chart(&categories));
+        return Ok(());
     }
 
+    let seconds = |c: &CategoryStats| c.duration.as_ref().map_or(0, |d| d.seconds);
+    let longest = categories.iter().map(seconds).max().unwrap_or(0).max(1);
+    let rows: Vec<TopCategoryDisplay> = categories
+        .iter()
+        .enumerate()
+        .map(|(i, category)| TopCategoryDisplay {
+            rank: i + 1,
+            category: category.category.clone(),
+            hours: format!("{:.1}", seconds(category) as f64 / 3600.0),
+            bar: "█"
+                .repeat((seconds(category) * BAR_WIDTH / longest).max(1) as usize)
+                .magenta()
+                .to_string(),
+            channels: category.channels,
+            streams: category.streams,
+        })
+        .collect();
+
+    let table = table(&rows).to_string();
+    pager::page(ctx.pager.as_deref(), &table);
+
+    Ok(())
+}
+
+async fn watch_events(ctx: &CliContext, names: &[String], json: bool) -> Result<()> {
     let mut client = ctx.client.clone();
 
-    let request = ctx.create_request(UntrackChannelRequest {
-        name: name.to_string(),
+    let mut request = ctx.create_request(WatchEventsRequest {
+        names: names.to_vec(),
     });
+    // The call is meant to stay open, so drop the usual deadline.
+    request.metadata_mut().remove("grpc-timeout");
 
-    match client.untrack_channel(request).await {
-        Ok(_) => {
-            print_success(&format!("Successfully untracked channel: {}", name));
+    let mut events = match client.watch_events(request).await {
+        Ok(response) => response.into_inner(),
+        Err(e) => {
+            print_error(&t!("events-failed", error = describe_status(&e)));
+            return Err(e.into());
+        }
+    };
+
+    if !json {
+        print_info(&t!("events-watching"));
+    }
+
+    loop {
+        let event = tokio::select! {
+            event = events.message() => event.context(t!("events-interrupted"))?,
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        };
+        let Some(event) = event else {
+            print_warning(&t!("events-closed"));
+            return Ok(());
+        };
+
+        if json || ctx.output_format.is_structured() {
+            // Events arrive one at a time, so JSON output is always NDJSON.
+            print_record(OutputFormat::Ndjson, &EventRecord::from(&event))?;
+        } else {
+            println!("{}", describe_event(&event));
         }
+    }
+}
+
+async fn tail_logs(ctx: &CliContext, request: TailLogsRequest, json: bool) -> Result<()> {
+    let mut client = ctx.client.clone();
+
+    let follow = request.follow;
+    let mut request = ctx.create_request(request);
+    if follow {
+        // Following is meant to stay open, so drop the usual deadline.
+        request.metadata_mut().remove("grpc-timeout");
+    }
+
+    let mut entries = match client.tail_logs(request).await {
+        Ok(response) => response.into_inner(),
         Err(e) => {
-            print_error(&format!(
-                "Failed to untrack channel '{}': {}",
-                name,
-                e.message()
-            ));
+            print_error(&t!("logs-failed", error = describe_status(&e)));
             return Err(e.into());
         }
+    };
+
+    loop {
+        let entry = tokio::select! {
+            entry = entries.message() => entry.context(t!("logs-interrupted"))?,
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        };
+        let Some(entry) = entry else {
+            return Ok(());
+        };
+
+        if json || ctx.output_format.is_structured() {
+            print_record(OutputFormat::Ndjson, &LogRecord::from(&entry))?;
+        } else {
+            println!("{}", describe_log_entry(&entry));
+        }
+    }
+}
+
+/// `time LEVEL target: message key=value ...`, like the server's own output.
+fn describe_log_entry(entry: &LogEntry) -> String {
+    let time = entry
+        .timestamp
+        .as_ref()
+        .and_then(to_datetime)
+        .unwrap_or_else(Utc::now)
+        .with_timezone(&Local)
+        .format("%H:%M:%S%.3f");
+    let name = format!("{:>5}", log_level_name(entry.level()));
+    let level = match entry.level() {
+        LogLevel::Error => name.red().to_string(),
+        LogLevel::Warn => name.yellow().to_string(),
+        LogLevel::Info => name.green().to_string(),
+        _ => name.dimmed().to_string(),
+    };
+    let mut fields: Vec<_> = entry.fields.iter().collect();
+    fields.sort();
+    let fields: String = fields
+        .into_iter()
+        .map(|(key, value)| format!(" {}={}", key.italic(), value))
+        .collect();
+    format!(
+        "{} {} {}: {}{}",
+        time.to_string().dimmed(),
+        level,
+        entry.target.dimmed(),
+        entry.message,
+        fields
+    )
+}
+
+/// One line per event: time, kind, channel, and what it's streaming.
+fn describe_event(event: &Event) -> String {
+    let time = event
+        .timestamp
+        .as_ref()
+        .and_then(to_datetime)
+        .unwrap_or_else(Utc::now)
+        .with_timezone(&Local)
+        .format("%H:%M:%S");
+    let details = format!("{} [{}]", event.title, event.category);
+    let labels = [t!("event-online"), t!("event-offline"), t!("event-update")];
+    let width = labels.iter().map(|l| l.width()).max().unwrap_or(0);
+    let [online, offline, update] = labels.map(|l| chart::pad(&l, width));
+    match event.kind() {
+        EventKind::Online => format!(
+            "{time} {} {} — {details}",
+            online.red().bold(),
+            event.display_name.bold()
+        ),
+        EventKind::Offline => format!("{time} {} {}", offline.dimmed(), event.display_name.bold()),
+        EventKind::Update | EventKind::Unspecified => format!(
+            "{time} {} {} — {details}",
+            update.yellow(),
+            event.display_name.bold()
+        ),
+    }
+}
+
+fn login(cli: &Cli, config_path: &Path, token_stdin: bool) -> Result<()> {
+    let token = if token_stdin {
+        let mut token = String::new();
+        io::stdin()
+            .read_line(&mut token)
+            .context(t!("token-stdin-failed"))?;
+        token
+    } else {
+        dialoguer::Password::new()
+            .with_prompt(t!("token-prompt", server = cli.server.as_str()))
+            .interact()?
+    };
+    let token = token.trim();
+    if token.is_empty() {
+        anyhow::bail!(t!("no-token"));
+    }
+
+    match credentials::store_token(&cli.server, token) {
+        Ok(()) => print_success(&t!("token-saved-keyring", server = cli.server.as_str())),
+        Err(e) => {
+            print_warning(&t!("keyring-fallback", error = e.to_string()));
+            let mut config = CliConfig::load(config_path)?;
+            match &cli.profile {
+                Some(name) => {
+                    config.profiles.entry(name.clone()).or_default().api_key =
+                        Some(token.to_string())
+                }
+                None => config.api_key = Some(token.to_string()),
+            }
+            config.save(config_path)?;
+            print_success(&t!(
+                "token-saved-file",
+                path = config_path.display().to_string()
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn logout(cli: &Cli, config_path: &Path) -> Result<()> {
+    let in_keyring = credentials::delete_token(&cli.server).unwrap_or_else(|e| {
+        print_warning(&t!("keyring-unavailable", error = e.to_string()));
+        false
+    });
+
+    let mut config = CliConfig::load(config_path)?;
+    let saved = match &cli.profile {
+        Some(name) => config.profiles.get_mut(name).and_then(|p| p.api_key.take()),
+        None => config.api_key.take(),
+    };
+    if saved.is_some() {
+        config.save(config_path)?;
+    }
+
+    if in_keyring || saved.is_some() {
+        print_success(&t!("token-removed", server = cli.server.as_str()));
+    } else {
+        print_info(&t!("no-saved-token", server = cli.server.as_str()));
+    }
+    Ok(())
+}
+
+fn config_command(command: &ConfigCommand, path: &Path) -> Result<()> {
+    // Read the file directly so a broken config is reported, not replaced.
+    let load = || {
+        if path.exists() {
+            CliConfig::load_from(path)
+        } else {
+            Ok(CliConfig::default())
+        }
+    };
+
+    match command {
+        ConfigCommand::Show => {
+            let mut config = load()?;
+            let keys = std::iter::once(&mut config.api_key)
+                .chain(config.profiles.values_mut().map(|p| &mut p.api_key));
+            for key in keys.filter(|k| k.is_some()) {
+                *key = Some("********".to_string());
+            }
+            print!("{}", toml::to_string_pretty(&config)?);
+        }
+        ConfigCommand::Get { key } => println!("{}", load()?.get(key)?),
+        ConfigCommand::Set { key, value } => {
+            let mut config = load()?;
+            config.set(key, value)?;
+            config.save(path)?;
+            print_success(&t!(
+                "config-set",
+                key = key.as_str(),
+                value = config.get(key)?
+            ));
+        }
+        ConfigCommand::Edit => {
+            if !path.exists() {
+                CliConfig::default().save(path)?;
+            }
+            let editor = std::env::var("VISUAL")
+                .or_else(|_| std::env::var("EDITOR"))
+                .unwrap_or_else(|_| "vi".to_string());
+            let status = std::process::Command::new(&editor)
+                .arg(path)
+                .status()
+                .with_context(|| t!("editor-launch-failed", editor = editor.as_str()))?;
+            if !status.success() {
+                anyhow::bail!(t!("editor-failed", status = status.to_string()));
+            }
+            match load().and_then(|config| config.validate()) {
+                Ok(()) => print_success(&t!("config-saved")),
+                Err(e) => {
+                    print_warning(&t!("config-problems", error = format!("{:#}", e)));
+                    print_info(&t!("config-edit-again"));
+                }
+            }
+        }
+        ConfigCommand::Path => println!("{}", path.display()),
+        ConfigCommand::Validate => {
+            load()?.validate()?;
+            print_success(&t!("config-valid", path = path.display().to_string()));
+        }
+    }
+
+    Ok(())
+}
+
+async fn export_data(ctx: &CliContext, file: Option<&Path>) -> Result<()> {
+    let mut client = ctx.client.clone();
+
+    let request = ctx.create_request(ExportDataRequest {});
+    let mut chunks = client
+        .export_data(request)
+        .await
+        .context(t!("export-failed"))?
+        .into_inner();
+
+    let mut out: Box<dyn Write> = match file {
+        Some(path) => Box::new(io::BufWriter::new(
+            std::fs::File::create(path)
+                .with_context(|| t!("create-failed", path = path.display().to_string()))?,
+        )),
+        None => Box::new(io::stdout().lock()),
+    };
+    while let Some(chunk) = chunks.message().await.context(t!("export-interrupted"))? {
+        out.write_all(&chunk.data)?;
     }
+    out.flush()?;
 
+    if let Some(path) = file {
+        print_success(&t!("exported", path = path.display().to_string()));
+    }
     Ok(())
 }
 
@@ -398,28 +3212,30 @@ fn generate_completions(shell: clap_complete::Shell) {
     generate(shell, &mut cmd, name, &mut io::stdout());
 }
 
-async fn setup_wizard() -> Result<()> {
+async fn setup_wizard(config_path: &Path) -> Result<()> {
     use dialoguer::{theme::ColorfulTheme, Confirm, Select};
 
-    println!("{}", "Welcome to Stitch Setup Wizard!".bold().cyan());
-    println!("This wizard will help you configure Stitch for first-time use.\n");
+    println!("{}", t!("setup-welcome").bold().cyan());
+    println!("{}\n", t!("setup-intro"));
 
-    let config_path = CliConfig::config_path()?;
     if config_path.exists() {
         let overwrite = Confirm::with_theme(&ColorfulTheme::default())
-            .with_prompt("Configuration file already exists. Overwrite?")
+            .with_prompt(t!("setup-overwrite"))
             .default(false)
             .interact()?;
 
         if !overwrite {
-            print_info("Setup cancelled. Your existing configuration was preserved.");
+            print_info(&t!("setup-cancelled"));
             return Ok(());
         }
     }
 
     // Use simple stdin for server address to avoid paste glitches
-    println!("{}:", "Stitch server address".bold());
-    println!("{}", "(default: http://127.0.0.1:50051)".bright_black());
+    println!("{}:", t!("setup-server").bold());
+    println!(
+        "{}",
+        t!("setup-server-default", server = "http://127.0.0.1:50051").bright_black()
+    );
     print!("> ");
     io::stdout().flush()?;
 
@@ -430,121 +3246,144 @@ async fn setup_wizard() -> Result<()> {
         "http://127.0.0.1:50051".to_string()
     } else {
         if !server.starts_with("http://") && !server.starts_with("https://") {
-            print_warning("Server address should start with http:// or https://");
-            return Err(anyhow::anyhow!("Invalid server address"));
+            print_warning(&t!("setup-server-scheme"));
+            return Err(anyhow::anyhow!(t!("setup-server-invalid")));
         }
         server.to_string()
     };
 
     let formats = vec!["table", "json"];
     let output_idx = Select::with_theme(&ColorfulTheme::default())
-        .with_prompt("Default output format")
+        .with_prompt(t!("setup-output"))
         .default(0)
         .items(&formats)
         .interact()?;
     let output_format = formats[output_idx].to_string();
 
     let color = Confirm::with_theme(&ColorfulTheme::default())
-        .with_prompt("Enable colored output?")
+        .with_prompt(t!("setup-color"))
         .default(true)
         .interact()?;
 
-    let mut config = CliConfig::default();
-    config.server = server;
-    config.output_format = output_format;
-    config.color = color;
+    let config = CliConfig {
+        server,
+        output_format,
+        color,
+        // Keep everything the wizard doesn't ask about, profiles included.
+        ..CliConfig::load(config_path).unwrap_or_default()
+    };
 
-    config.save()?;
+    config.save(config_path)?;
 
-    print_success(&format!("Configuration saved to {:?}", config_path));
+    print_success(&t!("setup-saved", path = format!("{:?}", config_path)));
 
-    let shells = vec!["bash", "zsh", "fish", "powershell", "skip"];
+    let skip = t!("setup-skip");
+    let shells = vec!["bash", "zsh", "fish", "powershell", skip.as_str()];
     let shell_idx = Select::with_theme(&ColorfulTheme::default())
-        .with_prompt("Generate shell completions for")
+        .with_prompt(t!("setup-completions"))
         .default(4)
         .items(&shells)
         .interact()?;
 
     if shell_idx < 4 {
         let shell_name = shells[shell_idx];
-        print_info(&format!("\nTo install completions for {}:", shell_name));
+        print_info(&format!(
+            "\n{}",
+            t!("setup-completions-install", shell = shell_name)
+        ));
 
         match shell_name {
             "bash" => {
-                println!("  # Create directory if it doesn't exist:");
+                println!("  # {}", t!("setup-step-mkdir"));
                 println!("  mkdir -p ~/.local/share/bash-completion/completions/\n");
-                println!("  # Generate and install completions:");
+                println!("  # {}", t!("setup-step-generate"));
                 println!("  stitch completions bash > ~/.local/share/bash-completion/completions/stitch\n");
-                println!("  # Reload your shell:");
+                println!("  # {}", t!("setup-step-reload"));
                 println!("  source ~/.bashrc\n");
             }
             "zsh" => {
-                println!("  # Create directory if it doesn't exist:");
+                println!("  # {}", t!("setup-step-mkdir"));
                 println!("  mkdir -p ~/.zsh/completions/\n");
-                println!("  # Generate and install completions:");
+                println!("  # {}", t!("setup-step-generate"));
                 println!("  stitch completions zsh > ~/.zsh/completions/_stitch\n");
-                println!("  # Add to ~/.zshrc if not already present:");
+                println!("  # {}", t!("setup-step-zshrc"));
                 println!("  echo 'fpath=(~/.zsh/completions $fpath)' >> ~/.zshrc\n");
-                println!("  # Reload your shell:");
+                println!("  # {}", t!("setup-step-reload"));
                 println!("  source ~/.zshrc\n");
             }
             "fish" => {
-                println!("  # Fish automatically creates the directory, just run:");
+                println!("  # {}", t!("setup-step-fish"));
                 println!("  stitch completions fish > ~/.config/fish/completions/stitch.fish\n");
             }
             "powershell" => {
-                println!("  # Add to your PowerShell profile:");
+                println!("  # {}", t!("setup-step-powershell"));
                 println!("  stitch completions powershell >> $PROFILE\n");
-                println!("  # Then reload your profile:");
+                println!("  # {}", t!("setup-step-powershell-reload"));
                 println!("  . $PROFILE\n");
             }
             _ => {}
         }
 
-        println!(
-            "{}",
-            "After installation, try pressing TAB after typing 'stitch' to see completions!"
-                .bright_black()
-        );
+        println!("{}", t!("setup-completions-try").bright_black());
     }
 
     let test = Confirm::with_theme(&ColorfulTheme::default())
-        .with_prompt("Test connection to server?")
+        .with_prompt(t!("setup-test"))
         .default(true)
         .interact()?;
 
     if test {
-        let spinner = animations::show_spinner("Testing connection...");
+        let spinner = animations::show_spinner(&t!("setup-testing"));
 
         match create_client_with_retry(&Cli {
             server: config.server.clone(),
+            config: Some(config_path.to_path_buf()),
             output: OutputFormat::Table,
             verbose: 0,
             no_color: false,
-            timeout: 5,
-            retries: 1,
+            porcelain: false,
+            no_pager: false,
+            no_cache: false,
+            timeout: Some(5),
+            retries: Some(1),
             headers: None,
+            api_key: None,
+            tls_cert: None,
+            tls_key: None,
+            tls_ca: None,
+            compression: Compression::None,
+            profile: None,
+            tls: false,
+            tls_server_name: None,
+            insecure_skip_verify: false,
             command: Some(Command::Setup),
         })
         .await
         {
             Ok(_) => {
-                spinner.success("Successfully connected to server!");
+                spinner.success(&t!("setup-connected"));
             }
             Err(e) => {
-                spinner.error(&format!("Failed to connect: {}", e));
-                print_warning("\nMake sure the Stitch server is running.");
-                print_info("Start the server with: cargo run --bin server\n");
+                spinner.error(&t!("setup-connect-failed", error = e.to_string()));
+                print_warning(&format!("\n{}", t!("setup-server-running")));
+                print_info(&format!(
+                    "{}\n",
+                    t!("setup-start-server", command = "cargo run --bin server")
+                ));
             }
         }
     }
 
-    println!("{}", "Setup complete!".green().bold());
-    println!("\nGet started with:");
-    println!("  {} - List all channels", "stitch list".cyan());
-    println!("  {} - Track a new channel", "stitch track <name>".cyan());
-    println!("  {} - Interactive mode", "stitch -i".cyan());
-    println!("  {} - Show help\n", "stitch --help".cyan());
+    println!("{}", t!("setup-complete").green().bold());
+    println!("\n{}", t!("setup-get-started"));
+    println!("  {} - {}", "stitch list".cyan(), t!("setup-cmd-list"));
+    println!(
+        "  {} - {}",
+        "stitch track <name>".cyan(),
+        t!("setup-cmd-track")
+    );
+    println!("  {} - {}", "stitch -i".cyan(), t!("setup-cmd-interactive"));
+    println!("  {} - {}\n", "stitch --help".cyan(), t!("setup-cmd-help"));
 
     Ok(())
 }
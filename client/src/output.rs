@@ -0,0 +1,836 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use clap::ValueEnum;
+use proto::stitch::{
+    CategoryStats, CategoryTime, Channel, ChannelStats, ChannelValidation, Event,
+    EventSubSubscription, GetChannelStatusResponse, InactiveChannel, LiveStream, LogEntry,
+    ResyncSubscriptionsResponse, Stream, SubscriptionChange, ValidationOutcome,
+};
+use serde::ser::{self, Impossible};
+use serde::Serialize;
+use serde_json::Value;
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum OutputFormat {
+    Json,
+    /// One JSON object per line
+    Ndjson,
+    Yaml,
+    /// Comma-separated values with a header row
+    Csv,
+    Table,
+    /// A table with extra columns, where the command has any
+    Wide,
+}
+
+impl OutputFormat {
+    /// Whether output is meant for other programs rather than people.
+    pub fn is_structured(self) -> bool {
+        !matches!(self, OutputFormat::Table | OutputFormat::Wide)
+    }
+}
+
+/// Prints a single record in `format`.
+pub fn print_record<T: Serialize>(format: OutputFormat, record: &T) -> Result<()> {
+    write_record(std::io::stdout().lock(), format, record)
+}
+
+fn write_record<T: Serialize>(
+    mut out: impl std::io::Write,
+    format: OutputFormat,
+    record: &T,
+) -> Result<()> {
+    match format {
+        OutputFormat::Ndjson => writeln!(out, "{}", serde_json::to_string(record)?)?,
+        OutputFormat::Yaml => write!(out, "{}", serde_yaml::to_string(record)?)?,
+        OutputFormat::Csv => write_csv(out, std::slice::from_ref(record))?,
+        OutputFormat::Json | OutputFormat::Table | OutputFormat::Wide => {
+            writeln!(out, "{}", serde_json::to_string_pretty(record)?)?
+        }
+    }
+    Ok(())
+}
+
+/// Prints `records` as a JSON or YAML list, one JSON object per line for
+/// NDJSON, or one CSV row each.
+pub fn print_records<T: Serialize>(format: OutputFormat, records: &[T]) -> Result<()> {
+    write_records(std::io::stdout().lock(), format, records)
+}
+
+fn write_records<T: Serialize>(
+    mut out: impl std::io::Write,
+    format: OutputFormat,
+    records: &[T],
+) -> Result<()> {
+    match format {
+        OutputFormat::Ndjson => {
+            for record in records {
+                writeln!(out, "{}", serde_json::to_string(record)?)?;
+            }
+        }
+        OutputFormat::Yaml => write!(out, "{}", serde_yaml::to_string(records)?)?,
+        OutputFormat::Csv => write_csv(out, records)?,
+        OutputFormat::Json | OutputFormat::Table | OutputFormat::Wide => {
+            writeln!(out, "{}", serde_json::to_string_pretty(records)?)?
+        }
+    }
+    Ok(())
+}
+
+/// Writes `records` as CSV with a header taken from the first record's
+/// fields. Lists of plain values are joined with `;`, anything more nested
+/// is embedded as JSON.
+fn write_csv<T: Serialize>(out: impl std::io::Write, records: &[T]) -> Result<()> {
+    let mut writer = csv::Writer::from_writer(out);
+    for (i, record) in records.iter().enumerate() {
+        let row = record.serialize(CsvRowSerializer)?;
+        if i == 0 {
+            writer.write_record(row.iter().map(|(column, _)| column))?;
+        }
+        writer.write_record(row.iter().map(|(_, cell)| cell))?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Turns a record into its CSV cells by column. Unlike going through a JSON
+/// value, it sees the fields left out when `None`, which keep their column
+/// as an empty cell, so every row of a record type lines up.
+struct CsvRowSerializer;
+
+/// Cells of a record so far, and the key of a map entry awaiting its value.
+#[derive(Default)]
+struct CsvRow {
+    cells: Vec<(String, String)>,
+    key: Option<String>,
+}
+
+fn not_a_record() -> serde_json::Error {
+    ser::Error::custom("CSV output needs records with named fields")
+}
+
+macro_rules! not_a_record {
+    ($($method:ident($($arg:ty),*);)*) => {$(
+        fn $method(self, $(_: $arg),*) -> Result<Self::Ok, Self::Error> {
+            Err(not_a_record())
+        }
+    )*};
+}
+
+impl ser::Serializer for CsvRowSerializer {
+    type Ok = Vec<(String, String)>;
+    type Error = serde_json::Error;
+    type SerializeSeq = Impossible<Self::Ok, Self::Error>;
+    type SerializeTuple = Impossible<Self::Ok, Self::Error>;
+    type SerializeTupleStruct = Impossible<Self::Ok, Self::Error>;
+    type SerializeTupleVariant = Impossible<Self::Ok, Self::Error>;
+    type SerializeMap = CsvRow;
+    type SerializeStruct = CsvRow;
+    type SerializeStructVariant = Impossible<Self::Ok, Self::Error>;
+
+    not_a_record! {
+        serialize_bool(bool);
+        serialize_i8(i8);
+        serialize_i16(i16);
+        serialize_i32(i32);
+        serialize_i64(i64);
+        serialize_u8(u8);
+        serialize_u16(u16);
+        serialize_u32(u32);
+        serialize_u64(u64);
+        serialize_f32(f32);
+        serialize_f64(f64);
+        serialize_char(char);
+        serialize_str(&str);
+        serialize_bytes(&[u8]);
+        serialize_none();
+        serialize_unit();
+        serialize_unit_struct(&'static str);
+        serialize_unit_variant(&'static str, u32, &'static str);
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        Err(not_a_record())
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Err(not_a_record())
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Err(not_a_record())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Err(not_a_record())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(not_a_record())
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Ok(CsvRow::default())
+    }
+
+    fn serialize_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Ok(CsvRow::default())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(not_a_record())
+    }
+}
+
+impl ser::SerializeStruct for CsvRow {
+    type Ok = Vec<(String, String)>;
+    type Error = serde_json::Error;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        let cell = csv_cell(&serde_json::to_value(value)?);
+        self.cells.push((key.to_string(), cell));
+        Ok(())
+    }
+
+    fn skip_field(&mut self, key: &'static str) -> Result<(), Self::Error> {
+        self.cells.push((key.to_string(), String::new()));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(self.cells)
+    }
+}
+
+impl ser::SerializeMap for CsvRow {
+    type Ok = Vec<(String, String)>;
+    type Error = serde_json::Error;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), Self::Error> {
+        self.key = Some(csv_cell(&serde_json::to_value(key)?));
+        Ok(())
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        let cell = csv_cell(&serde_json::to_value(value)?);
+        self.cells.push((self.key.take().unwrap_or_default(), cell));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(self.cells)
+    }
+}
+
+fn csv_cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Array(items) if items.iter().all(|v| !v.is_object() && !v.is_array()) => {
+            items.iter().map(csv_cell).collect::<Vec<_>>().join(";")
+        }
+        other => other.to_string(),
+    }
+}
+
+/// Stable, tab-separated fields for `--porcelain`. Unlike the table and the
+/// structured formats, the columns are a contract with scripts: they never
+/// change order or go missing, and new ones are only appended.
+pub trait Porcelain {
+    fn porcelain(&self) -> Vec<String>;
+}
+
+/// Prints one line per record with its porcelain fields separated by tabs.
+pub fn print_porcelain<T: Porcelain>(records: &[T]) {
+    for record in records {
+        let fields: Vec<_> = record
+            .porcelain()
+            .into_iter()
+            .map(|field| field.replace(['\t', '\n', '\r'], " "))
+            .collect();
+        println!("{}", fields.join("\t"));
+    }
+}
+
+fn porcelain_time(time: Option<DateTime<Utc>>) -> String {
+    time.map(|t| t.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+        .unwrap_or_default()
+}
+
+fn porcelain_opt<T: ToString>(value: &Option<T>) -> String {
+    value.as_ref().map(T::to_string).unwrap_or_default()
+}
+
+pub fn to_datetime(ts: &prost_types::Timestamp) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp(ts.seconds, ts.nanos.max(0) as u32)
+}
+
+fn seconds(duration: &Option<prost_types::Duration>) -> Option<i64> {
+    duration.as_ref().map(|d| d.seconds)
+}
+
+#[derive(Serialize)]
+pub struct ChannelRecord {
+    pub id: i32,
+    pub name: String,
+    pub display_name: String,
+    pub twitch_id: String,
+    pub paused: bool,
+    pub tags: Vec<String>,
+    pub is_live: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uptime_seconds: Option<i64>,
+    pub created_at: Option<DateTime<Utc>>,
+    /// Why the server gets no events for the channel, if it doesn't.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub broken_reason: Option<String>,
+}
+
+impl From<&Channel> for ChannelRecord {
+    fn from(channel: &Channel) -> Self {
+        let live = channel.is_live;
+        Self {
+            id: channel.id,
+            name: channel.name.clone(),
+            display_name: channel.display_name.clone(),
+            twitch_id: channel.twitch_id.clone(),
+            paused: channel.paused,
+            tags: channel.tags.clone(),
+            is_live: live,
+            category: live.then(|| channel.category.clone()),
+            uptime_seconds: seconds(&channel.uptime).filter(|_| live),
+            created_at: channel.created_at.as_ref().and_then(to_datetime),
+            broken_reason: Some(channel.broken_reason.clone()).filter(|r| !r.is_empty()),
+        }
+    }
+}
+
+/// `id name display_name twitch_id paused tags is_live category
+/// uptime_seconds created_at broken_reason`
+impl Porcelain for ChannelRecord {
+    fn porcelain(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            self.name.clone(),
+            self.display_name.clone(),
+            self.twitch_id.clone(),
+            self.paused.to_string(),
+            self.tags.join(","),
+            self.is_live.to_string(),
+            porcelain_opt(&self.category),
+            porcelain_opt(&self.uptime_seconds),
+            porcelain_time(self.created_at),
+            porcelain_opt(&self.broken_reason),
+        ]
+    }
+}
+
+#[derive(Serialize)]
+pub struct ChannelList {
+    pub channels: Vec<ChannelRecord>,
+    pub total: usize,
+}
+
+#[derive(Serialize)]
+pub struct StatusRecord {
+    pub name: String,
+    pub live: bool,
+    pub title: Option<String>,
+    pub category: Option<String>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub uptime_seconds: Option<i64>,
+}
+
+#[derive(Serialize)]
+pub struct FilterRecord {
+    pub name: String,
+    pub categories: Vec<String>,
+    pub muted_keywords: Vec<String>,
+}
+
+impl From<&GetChannelStatusResponse> for StatusRecord {
+    fn from(status: &GetChannelStatusResponse) -> Self {
+        let stream = status.stream.as_ref().filter(|_| status.live);
+        Self {
+            name: status.name.clone(),
+            live: status.live,
+            title: stream.map(|s| s.title.clone()),
+            category: stream.map(|s| s.category.clone()),
+            started_at: stream
+                .and_then(|s| s.started_at.as_ref())
+                .and_then(to_datetime),
+            uptime_seconds: stream.and_then(|s| seconds(&s.uptime)),
+        }
+    }
+}
+
+/// `name live title category started_at uptime_seconds`
+impl Porcelain for StatusRecord {
+    fn porcelain(&self) -> Vec<String> {
+        vec![
+            self.name.clone(),
+            self.live.to_string(),
+            porcelain_opt(&self.title),
+            porcelain_opt(&self.category),
+            porcelain_time(self.started_at),
+            porcelain_opt(&self.uptime_seconds),
+        ]
+    }
+}
+
+#[derive(Serialize)]
+pub struct StreamRecord {
+    pub stream_id: String,
+    pub title: String,
+    pub started_at: Option<DateTime<Utc>>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub duration_seconds: Option<i64>,
+    pub categories: Vec<String>,
+}
+
+impl StreamRecord {
+    /// Ongoing streams are measured up to `now`.
+    pub fn new(stream: &Stream, now: DateTime<Utc>) -> Self {
+        let started_at = stream.started_at.as_ref().and_then(to_datetime);
+        let ended_at = stream.ended_at.as_ref().and_then(to_datetime);
+        Self {
+            stream_id: stream.stream_id.clone(),
+            title: stream.title.clone(),
+            started_at,
+            ended_at,
+            duration_seconds: started_at
+                .map(|start| (ended_at.unwrap_or(now) - start).num_seconds()),
+            categories: stream.categories.clone(),
+        }
+    }
+}
+
+/// `stream_id started_at ended_at duration_seconds title categories`
+impl Porcelain for StreamRecord {
+    fn porcelain(&self) -> Vec<String> {
+        vec![
+            self.stream_id.clone(),
+            porcelain_time(self.started_at),
+            porcelain_time(self.ended_at),
+            porcelain_opt(&self.duration_seconds),
+            self.title.clone(),
+            self.categories.join(","),
+        ]
+    }
+}
+
+#[derive(Serialize)]
+pub struct LiveRecord {
+    pub name: String,
+    pub display_name: String,
+    pub title: String,
+    pub category: String,
+    pub started_at: Option<DateTime<Utc>>,
+    pub uptime_seconds: Option<i64>,
+    pub viewer_count: u32,
+}
+
+impl From<&LiveStream> for LiveRecord {
+    fn from(stream: &LiveStream) -> Self {
+        Self {
+            name: stream.name.clone(),
+            display_name: stream.display_name.clone(),
+            title: stream.title.clone(),
+            category: stream.category.clone(),
+            started_at: stream.started_at.as_ref().and_then(to_datetime),
+            uptime_seconds: seconds(&stream.uptime),
+            viewer_count: stream.viewer_count,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct CategoryRecord {
+    pub category: String,
+    pub seconds: i64,
+}
+
+impl From<&CategoryTime> for CategoryRecord {
+    fn from(category: &CategoryTime) -> Self {
+        Self {
+            category: category.category.clone(),
+            seconds: seconds(&category.duration).unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct TopCategoryRecord {
+    pub category: String,
+    pub seconds: i64,
+    pub channels: u32,
+    pub streams: u32,
+}
+
+impl From<&CategoryStats> for TopCategoryRecord {
+    fn from(category: &CategoryStats) -> Self {
+        Self {
+            category: category.category.clone(),
+            seconds: seconds(&category.duration).unwrap_or_default(),
+            channels: category.channels,
+            streams: category.streams,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct VersionRecord {
+    pub client_version: &'static str,
+    pub client_proto_revision: &'static str,
+    pub server_version: String,
+    pub server_proto_revision: String,
+}
+
+#[derive(Serialize)]
+pub struct StatsRecord {
+    pub name: String,
+    pub display_name: String,
+    pub total_streams: u32,
+    pub total_seconds: i64,
+    pub average_seconds: i64,
+    pub average_viewers: u32,
+    pub peak_viewers: u32,
+    pub top_categories: Vec<CategoryRecord>,
+    pub weekly: Vec<WeeklyRecord>,
+}
+
+#[derive(Serialize)]
+pub struct WeeklyRecord {
+    pub week_start: Option<DateTime<Utc>>,
+    pub seconds: i64,
+}
+
+impl From<&ChannelStats> for StatsRecord {
+    fn from(stats: &ChannelStats) -> Self {
+        Self {
+            name: stats.name.clone(),
+            display_name: stats.display_name.clone(),
+            total_streams: stats.total_streams,
+            total_seconds: seconds(&stats.total_duration).unwrap_or_default(),
+            average_seconds: seconds(&stats.average_duration).unwrap_or_default(),
+            average_viewers: stats.average_viewers,
+            peak_viewers: stats.peak_viewers,
+            top_categories: stats.top_categories.iter().map(Into::into).collect(),
+            weekly: stats
+                .weekly
+                .iter()
+                .map(|week| WeeklyRecord {
+                    week_start: week.week_start.as_ref().and_then(to_datetime),
+                    seconds: seconds(&week.duration).unwrap_or_default(),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct LogRecord {
+    pub timestamp: Option<DateTime<Utc>>,
+    pub level: &'static str,
+    pub target: String,
+    pub message: String,
+    pub fields: std::collections::BTreeMap<String, String>,
+}
+
+impl From<&LogEntry> for LogRecord {
+    fn from(entry: &LogEntry) -> Self {
+        Self {
+            timestamp: entry.timestamp.as_ref().and_then(to_datetime),
+            level: log_level_name(entry.level()),
+            target: entry.target.clone(),
+            message: entry.message.clone(),
+            fields: entry.fields.clone().into_iter().collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct InactiveRecord {
+    pub name: String,
+    pub display_name: String,
+    pub twitch_id: String,
+    pub last_stream_at: Option<DateTime<Utc>>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl From<&InactiveChannel> for InactiveRecord {
+    fn from(inactive: &InactiveChannel) -> Self {
+        let channel = inactive.channel.clone().unwrap_or_default();
+        Self {
+            name: channel.name,
+            display_name: channel.display_name,
+            twitch_id: channel.twitch_id,
+            last_stream_at: inactive.last_stream_at.as_ref().and_then(to_datetime),
+            created_at: channel.created_at.as_ref().and_then(to_datetime),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ValidationRecord {
+    pub name: String,
+    pub outcome: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub login: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub twitch_id: Option<String>,
+    pub subscriptions: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl From<&ChannelValidation> for ValidationRecord {
+    fn from(result: &ChannelValidation) -> Self {
+        let known = |s: &String| Some(s.clone()).filter(|s| !s.is_empty());
+        Self {
+            name: result.name.clone(),
+            outcome: validation_outcome_name(result.outcome()),
+            login: known(&result.login),
+            display_name: known(&result.display_name),
+            twitch_id: known(&result.twitch_id),
+            subscriptions: result
+                .subscriptions
+                .iter()
+                .map(|s| s.r#type.clone())
+                .collect(),
+            error: known(&result.error),
+        }
+    }
+}
+
+pub fn validation_outcome_name(outcome: ValidationOutcome) -> &'static str {
+    match outcome {
+        ValidationOutcome::WouldTrack => "would track",
+        ValidationOutcome::WouldUntrack => "would untrack",
+        ValidationOutcome::AlreadyTracked => "already tracked",
+        ValidationOutcome::NotTracked => "not tracked",
+        ValidationOutcome::NotFound => "not found",
+        ValidationOutcome::Invalid => "invalid",
+        ValidationOutcome::LookupFailed => "lookup failed",
+        ValidationOutcome::Unspecified => "",
+    }
+}
+
+pub fn log_level_name(level: proto::stitch::LogLevel) -> &'static str {
+    use proto::stitch::LogLevel;
+    match level {
+        LogLevel::Trace => "TRACE",
+        LogLevel::Debug => "DEBUG",
+        LogLevel::Info => "INFO",
+        LogLevel::Warn => "WARN",
+        LogLevel::Error => "ERROR",
+        LogLevel::Unspecified => "",
+    }
+}
+
+#[derive(Serialize)]
+pub struct EventRecord {
+    pub kind: &'static str,
+    pub name: String,
+    pub display_name: String,
+    pub title: String,
+    pub category: String,
+    pub timestamp: Option<DateTime<Utc>>,
+}
+
+impl From<&Event> for EventRecord {
+    fn from(event: &Event) -> Self {
+        Self {
+            kind: event.kind().as_str_name(),
+            name: event.name.clone(),
+            display_name: event.display_name.clone(),
+            title: event.title.clone(),
+            category: event.category.clone(),
+            timestamp: event.timestamp.as_ref().and_then(to_datetime),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct SubscriptionRecord {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub status: String,
+    pub twitch_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl From<&EventSubSubscription> for SubscriptionRecord {
+    fn from(sub: &EventSubSubscription) -> Self {
+        Self {
+            id: sub.id.clone(),
+            kind: sub.r#type.clone(),
+            status: sub.status.clone(),
+            twitch_id: sub.twitch_id.clone(),
+            name: Some(sub.name.clone()).filter(|n| !n.is_empty()),
+            created_at: sub.created_at.as_ref().and_then(to_datetime),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ChangeRecord {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub twitch_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl From<&SubscriptionChange> for ChangeRecord {
+    fn from(change: &SubscriptionChange) -> Self {
+        Self {
+            kind: change.r#type.clone(),
+            twitch_id: change.twitch_id.clone(),
+            name: Some(change.name.clone()).filter(|n| !n.is_empty()),
+            error: Some(change.error.clone()).filter(|e| !e.is_empty()),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct ResyncRecord {
+    pub added: Vec<ChangeRecord>,
+    pub removed: Vec<ChangeRecord>,
+    pub stale: Vec<ChangeRecord>,
+    pub failed: Vec<ChangeRecord>,
+    pub kept: u32,
+}
+
+impl From<&ResyncSubscriptionsResponse> for ResyncRecord {
+    fn from(report: &ResyncSubscriptionsResponse) -> Self {
+        let records = |changes: &[SubscriptionChange]| changes.iter().map(Into::into).collect();
+        Self {
+            added: records(&report.added),
+            removed: records(&report.removed),
+            stale: records(&report.stale),
+            failed: records(&report.failed),
+            kept: report.kept,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn channel(name: &str, live: bool) -> ChannelRecord {
+        ChannelRecord {
+            id: 1,
+            name: name.to_string(),
+            display_name: name.to_string(),
+            twitch_id: "1337".to_string(),
+            paused: false,
+            tags: vec!["a".to_string(), "b".to_string()],
+            is_live: live,
+            category: live.then(|| "Celeste".to_string()),
+            uptime_seconds: live.then_some(60),
+            created_at: None,
+            broken_reason: None,
+        }
+    }
+
+    fn encode(format: OutputFormat, records: &[ChannelRecord]) -> String {
+        let mut out = Vec::new();
+        write_records(&mut out, format, records).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_json_escapes_strings() {
+        let mut record = channel("live", true);
+        record.category = Some("\"Quoted\"\ttabs\nand lines \\ ✓".to_string());
+        let json: Value = serde_json::from_str(&encode(OutputFormat::Json, &[record])).unwrap();
+        assert_eq!(json[0]["category"], "\"Quoted\"\ttabs\nand lines \\ ✓");
+        assert_eq!(json[0]["uptime_seconds"], 60);
+        assert_eq!(json[0]["tags"], serde_json::json!(["a", "b"]));
+
+        let mut out = Vec::new();
+        write_record(&mut out, OutputFormat::Json, &channel("offline", false)).unwrap();
+        let json: Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(json["name"], "offline");
+        assert!(json.get("category").is_none());
+    }
+
+    #[test]
+    fn test_ndjson_is_one_object_per_line() {
+        let mut record = channel("live", true);
+        record.category = Some("multi\nline".to_string());
+        let out = encode(OutputFormat::Ndjson, &[channel("offline", false), record]);
+        let lines: Vec<Value> = out
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["name"], "offline");
+        assert_eq!(lines[1]["category"], "multi\nline");
+    }
+
+    #[test]
+    fn test_csv_mixes_live_and_offline() {
+        let records = [channel("offline", false), channel("live", true)];
+        assert_eq!(
+            encode(OutputFormat::Csv, &records),
+            "id,name,display_name,twitch_id,paused,tags,is_live,category,uptime_seconds,\
+             created_at,broken_reason\n\
+             1,offline,offline,1337,false,a;b,false,,,,\n\
+             1,live,live,1337,false,a;b,true,Celeste,60,,\n"
+        );
+    }
+}
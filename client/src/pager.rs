@@ -0,0 +1,63 @@
+use std::io::{self, IsTerminal, Write};
+use std::process::{Command, Stdio};
+
+/// Pager used when neither the config nor `$PAGER` names one. `-F` makes
+/// less exit on its own if the text fits after all, `-R` keeps colors and
+/// `-X` leaves the text on screen afterwards.
+const DEFAULT_PAGER: &str = "less -FRX";
+
+/// The pager command to use, or `None` if paging is turned off. `configured`
+/// is the config file's `pager`; an empty `$PAGER` disables paging.
+pub fn resolve(disabled: bool, configured: Option<&str>) -> Option<String> {
+    if disabled {
+        return None;
+    }
+    let command = match configured {
+        Some(command) => command.to_string(),
+        None => std::env::var("PAGER").unwrap_or_else(|_| DEFAULT_PAGER.to_string()),
+    };
+    Some(command).filter(|c| !c.trim().is_empty())
+}
+
+/// Prints `text`, through `pager` if stdout is a terminal that `text` doesn't
+/// fit on. Falls back to printing directly if the pager can't be started.
+pub fn page(pager: Option<&str>, text: &str) {
+    let Some(pager) = pager.filter(|_| needs_paging(text)) else {
+        println!("{}", text);
+        return;
+    };
+    if run(pager, text).is_err() {
+        println!("{}", text);
+    }
+}
+
+fn needs_paging(text: &str) -> bool {
+    if !io::stdout().is_terminal() {
+        return false;
+    }
+    match crossterm::terminal::size() {
+        Ok((_, rows)) => text.lines().count() >= rows as usize,
+        Err(_) => false,
+    }
+}
+
+fn run(pager: &str, text: &str) -> io::Result<()> {
+    let mut parts = pager.split_whitespace();
+    let program = parts.next().unwrap_or_default();
+    let mut command = Command::new(program);
+    command.args(parts).stdin(Stdio::piped());
+    if std::env::var_os("LESS").is_none() {
+        command.env("LESS", "FRX");
+    }
+    let mut child = command.spawn()?;
+
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    match writeln!(stdin, "{}", text) {
+        // The user quit the pager before reading everything.
+        Err(e) if e.kind() == io::ErrorKind::BrokenPipe => {}
+        result => result?,
+    }
+    drop(stdin);
+    child.wait()?;
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use hyper_util::rt::TokioIo;
+use tokio::net::TcpStream;
+use tokio_rustls::rustls::client::danger::{
+    HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier,
+};
+use tokio_rustls::rustls::crypto::{self, ring, CryptoProvider};
+use tokio_rustls::rustls::pki_types::pem::PemObject;
+use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime};
+use tokio_rustls::rustls::{ClientConfig, DigitallySignedStruct, SignatureScheme};
+use tokio_rustls::TlsConnector;
+use tonic::codegen::http::Uri;
+
+/// Accepts any server certificate. Handshake signatures are still checked,
+/// so this only gives up on *who* the server is, not on encryption.
+#[derive(Debug)]
+struct SkipVerification(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for SkipVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, tokio_rustls::rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, tokio_rustls::rustls::Error> {
+        crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, tokio_rustls::rustls::Error> {
+        crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+/// A connector for `Endpoint::connect_with_connector` that speaks TLS without
+/// verifying the server certificate, for `--insecure-skip-verify`. tonic's
+/// own TLS support has no such switch, so the handshake happens here and the
+/// endpoint itself is given a plain `http://` URL.
+#[derive(Clone)]
+pub struct InsecureConnector {
+    tls: TlsConnector,
+    server_name: Option<ServerName<'static>>,
+}
+
+impl InsecureConnector {
+    /// `identity` is a PEM certificate and key for mutual TLS.
+    pub fn new(server_name: Option<&str>, identity: Option<(&Path, &Path)>) -> Result<Self> {
+        let provider = Arc::new(ring::default_provider());
+        let builder = ClientConfig::builder_with_provider(provider.clone())
+            .with_safe_default_protocol_versions()?
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(SkipVerification(provider)));
+        let mut config = match identity {
+            Some((cert, key)) => {
+                let certs = CertificateDer::pem_file_iter(cert)
+                    .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+                    .with_context(|| format!("Failed to read {}", cert.display()))?;
+                let key = PrivateKeyDer::from_pem_file(key)
+                    .with_context(|| format!("Failed to read {}", key.display()))?;
+                builder
+                    .with_client_auth_cert(certs, key)
+                    .context("Invalid client certificate")?
+            }
+            None => builder.with_no_client_auth(),
+        };
+        config.alpn_protocols = vec![b"h2".to_vec()];
+
+        let server_name = server_name
+            .map(|name| ServerName::try_from(name.to_string()))
+            .transpose()
+            .context("Invalid TLS server name")?;
+        Ok(Self {
+            tls: TlsConnector::from(Arc::new(config)),
+            server_name,
+        })
+    }
+
+    pub async fn connect(
+        self,
+        uri: Uri,
+    ) -> std::io::Result<TokioIo<tokio_rustls::client::TlsStream<TcpStream>>> {
+        let invalid =
+            |msg: &str| std::io::Error::new(std::io::ErrorKind::InvalidInput, msg.to_string());
+        let host = uri
+            .host()
+            .ok_or_else(|| invalid("server URL has no host"))?;
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        let server_name = match self.server_name {
+            Some(name) => name,
+            None => ServerName::try_from(host.to_string())
+                .map_err(|_| invalid("server URL host is not a valid TLS server name"))?,
+        };
+
+        let tcp = TcpStream::connect((host, uri.port_u16().unwrap_or(443))).await?;
+        let tls = self.tls.connect(server_name, tcp).await?;
+        Ok(TokioIo::new(tls))
+    }
+}
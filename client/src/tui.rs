@@ -1,39 +1,289 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, Utc};
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
-    execute,
+    cursor::MoveTo,
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, KeyModifiers,
+        MouseButton, MouseEvent, MouseEventKind,
+    },
+    execute, queue,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{
     backend::{Backend, CrosstermBackend},
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Position, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Clear, Borders, List, ListItem, ListState, Paragraph, Tabs, Wrap},
+    widgets::{
+        Block, Borders, Cell, Clear, List, ListItem, ListState, Padding, Paragraph, Row, Sparkline,
+        Table, TableState, Tabs, Wrap,
+    },
     Frame, Terminal,
 };
+use serde::{Deserialize, Serialize};
 use std::{
-    io,
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
+    fs,
+    future::Future,
+    io::{self, Write},
+    ops::Range,
+    path::{Path, PathBuf},
+    rc::Rc,
     sync::Arc,
     time::{Duration, Instant},
 };
-use tokio::sync::Mutex;
+use tokio::sync::mpsc::{self, error::TryRecvError};
+use unicode_width::UnicodeWidthStr;
 
-use crate::CliContext;
+use crate::config::{parse_key, CliConfig, FixedAction, KeyAction, KeyBindings, PaneLayout};
+use crate::graphics::{self, Protocol};
+use crate::i18n::{self, t};
+use crate::output::{to_datetime, StreamRecord};
+use crate::{
+    alias_for, backoff, channel_url, chart, describe_status, describe_tracked, format_seconds,
+    format_uptime, open_url, to_timestamp, CliContext,
+};
 use proto::stitch::*;
 
 pub struct App {
     pub channels: Vec<Channel>,
+    /// Where the next page of channels starts, while there's one.
+    next_page_token: Option<String>,
+    /// Counts reloads of the channel list, so pages of an older list are
+    /// dropped.
+    generation: u64,
+    /// Keep fetching pages until there are none left.
+    load_all: bool,
+    /// Keep the last channel selected as pages arrive.
+    jump_to_end: bool,
+    /// Channel to select once the first page arrives, as left last run.
+    restore_channel: Option<String>,
     pub selected_tab: usize,
     pub channel_list_state: ListState,
     pub search_query: String,
     pub is_searching: bool,
-    pub status_message: Option<(String, Instant)>,
+    /// Whether searches go to `SearchChannels`, matching the titles and
+    /// categories of live streams too.
+    server_search: bool,
+    /// The last server search's query and matches.
+    search_results: Option<(String, Vec<Channel>)>,
+    /// Results of recent operations, oldest first.
+    pub toasts: VecDeque<Toast>,
     pub show_help: bool,
-    pub loading: bool,
+    help_scroll: usize,
+    /// Narrows the help overlay down to the keys containing it.
+    help_filter: String,
+    help_filtering: bool,
     pub input_mode: InputMode,
     pub input_buffer: String,
-    ctx: Arc<Mutex<CliContext>>,
+    aliases: BTreeMap<String, String>,
+    config_path: PathBuf,
+    /// The config file as written, edited from the settings tab.
+    settings: CliConfig,
+    settings_state: ListState,
+    last_refresh: Instant,
+    /// When the channel list last loaded, to tell how stale it is.
+    last_loaded: Option<DateTime<Local>>,
+    connection: Connection,
+    server_version: Option<String>,
+    history: Option<HistoryView>,
+    show_events: bool,
+    /// The most recent events, oldest first.
+    events: VecDeque<proto::stitch::Event>,
+    /// Events from the `WatchEvents` subscription, which stays open while
+    /// the feed pane is hidden so nothing is missed in between.
+    event_feed: Option<mpsc::UnboundedReceiver<Result<proto::stitch::Event, tonic::Status>>>,
+    /// Details beyond what the channel list carries, by channel name.
+    extras: HashMap<String, ChannelExtras>,
+    /// How the terminal draws images, if it can.
+    graphics: Option<Protocol>,
+    /// Profile images by URL; `None` for the ones that failed to load.
+    avatars: HashMap<String, Option<Arc<image::RgbaImage>>>,
+    /// The avatar drawn last and where, to tell when it needs redrawing.
+    shown_avatar: Option<(String, Rect)>,
+    /// This week's server-wide numbers, or why they couldn't be loaded.
+    dashboard: Option<Result<Dashboard, String>>,
+    last_dashboard: Option<Instant>,
+    pending: HashSet<Operation>,
+    /// The channel untracked last and when, for [`App::undo_untrack`].
+    undo: Option<(String, Instant)>,
+    /// Kept open once used, since on X11 copied text is gone when the
+    /// clipboard that set it is dropped.
+    clipboard: Option<arboard::Clipboard>,
+    tx: mpsc::UnboundedSender<Message>,
+    rx: mpsc::UnboundedReceiver<Message>,
+    ctx: Arc<CliContext>,
+}
+
+/// The latest stream and all-time totals of a channel, fetched the first time
+/// it's selected.
+pub struct ChannelExtras {
+    last_stream: Option<Stream>,
+    total_streams: u32,
+    total_seconds: i64,
+    /// Seconds streamed in each of the latest streams, oldest first.
+    recent: Vec<u64>,
+    /// Seconds streamed per weekday, Monday first.
+    weekdays: Vec<u64>,
+}
+
+/// Whether the server answered the last call, going by transport errors
+/// only; a call the server rejects still shows it's reachable.
+enum Connection {
+    /// Nothing has come back yet.
+    Connecting,
+    Connected,
+    /// Calls failed to reach the server. Another `GetServerInfo` goes out at
+    /// `retry_at`, backing off as `attempts` grows.
+    Lost {
+        attempts: u32,
+        retry_at: Instant,
+        error: String,
+    },
+}
+
+/// Server-wide numbers for the dashboard tab. Times cover the current week.
+pub struct Dashboard {
+    tracked: usize,
+    live: usize,
+    week_seconds: i64,
+    categories: Vec<CategoryStats>,
+    /// Display names and seconds streamed, most first.
+    streamers: Vec<(String, i64)>,
+}
+
+/// Streams fetched for the history pane in pages of [`HISTORY_PAGE`]
+/// streams, the next page loading as the selection nears the end.
+pub struct HistoryView {
+    channel: String,
+    streams: Vec<Stream>,
+    state: TableState,
+    exhausted: bool,
+}
+
+const HISTORY_PAGE: u32 = 20;
+
+const CHANNELS_TAB: usize = 0;
+const DASHBOARD_TAB: usize = 1;
+const SETTINGS_TAB: usize = 2;
+
+/// Categories and streamers ranked on the dashboard.
+const DASHBOARD_TOP: u32 = 5;
+
+/// Config keys editable from the settings tab, in display order.
+const SETTINGS: &[&str] = &["server", "output_format", "color", "refresh_interval"];
+
+/// Channels fetched per `ListChannels` call.
+const CHANNEL_PAGE: u32 = 200;
+
+/// How close to the last loaded channel the selection gets before the next
+/// page loads.
+const LOAD_AHEAD: usize = 20;
+
+/// Rows moved by PageUp and PageDown.
+const PAGE_STEP: usize = 10;
+
+/// Toasts shown at once.
+const MAX_TOASTS: usize = 4;
+
+/// Events kept for the feed pane.
+const EVENT_BACKLOG: usize = 200;
+
+/// How long to wait for input before looking for finished operations.
+const TICK: Duration = Duration::from_millis(100);
+
+/// Most matches asked of a server search.
+const SEARCH_LIMIT: u32 = 100;
+
+/// Cells the profile image takes in the details pane, columns first.
+const AVATAR_CELLS: (u16, u16) = (12, 6);
+
+/// Largest side of a profile image as it's kept.
+const AVATAR_PIXELS: u32 = 128;
+
+/// Percent the channel list grows or shrinks by with `<` and `>`.
+const SPLIT_STEP: u16 = 5;
+
+/// How long an untracked channel can be tracked again with `u`.
+const UNDO_WINDOW: Duration = Duration::from_secs(10);
+
+/// Where the session is kept between runs, next to the config file.
+const SESSION_FILE: &str = "tui-state.toml";
+
+/// What the TUI picks up again on its next run. A missing or unreadable
+/// file just means a fresh start.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct Session {
+    tab: usize,
+    search: String,
+    server_search: bool,
+    /// Login of the selected channel.
+    channel: Option<String>,
+}
+
+impl Session {
+    fn path(config_path: &Path) -> PathBuf {
+        config_path.with_file_name(SESSION_FILE)
+    }
+
+    fn load(config_path: &Path) -> Self {
+        fs::read_to_string(Self::path(config_path))
+            .ok()
+            .and_then(|contents| toml::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, config_path: &Path) -> Result<()> {
+        let path = Self::path(config_path);
+        let contents = toml::to_string(self).context("Failed to serialize TUI state")?;
+        fs::write(&path, contents)
+            .with_context(|| format!("Failed to write TUI state to {:?}", path))
+    }
+}
+
+pub struct Toast {
+    message: String,
+    level: ToastLevel,
+    shown_at: Instant,
+    lifetime: Duration,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ToastLevel {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl ToastLevel {
+    /// How long a toast stays up. Problems get longer to be read.
+    fn lifetime(self) -> Duration {
+        match self {
+            ToastLevel::Info | ToastLevel::Success => Duration::from_secs(4),
+            ToastLevel::Warning => Duration::from_secs(6),
+            ToastLevel::Error => Duration::from_secs(10),
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            ToastLevel::Info => Color::Cyan,
+            ToastLevel::Success => Color::Green,
+            ToastLevel::Warning => Color::Yellow,
+            ToastLevel::Error => Color::Red,
+        }
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            ToastLevel::Info => "ℹ",
+            ToastLevel::Success => "✓",
+            ToastLevel::Warning => "!",
+            ToastLevel::Error => "✗",
+        }
+    }
 }
 
 #[derive(Clone, Copy, PartialEq)]
@@ -41,75 +291,540 @@ pub enum InputMode {
     Normal,
     AddingChannel,
     ConfirmingDelete,
+    ViewingHistory,
+    EditingSetting,
 }
 
 impl App {
     pub fn new(ctx: CliContext) -> Self {
         let mut list_state = ListState::default();
         list_state.select(Some(0));
+        let settings = CliConfig::load_from(&ctx.config_path).unwrap_or_default();
+        let session = Session::load(&ctx.config_path);
+        let (tx, rx) = mpsc::unbounded_channel();
 
         Self {
             channels: Vec::new(),
-            selected_tab: 0,
+            next_page_token: None,
+            generation: 0,
+            load_all: false,
+            jump_to_end: false,
+            restore_channel: session.channel,
+            selected_tab: session.tab.min(tab_titles().len() - 1),
             channel_list_state: list_state,
-            search_query: String::new(),
+            search_query: session.search,
             is_searching: false,
-            status_message: None,
+            server_search: session.server_search,
+            search_results: None,
+            toasts: VecDeque::new(),
             show_help: false,
-            loading: true,
+            help_scroll: 0,
+            help_filter: String::new(),
+            help_filtering: false,
             input_mode: InputMode::Normal,
             input_buffer: String::new(),
-            ctx: Arc::new(Mutex::new(ctx)),
+            aliases: ctx.aliases.clone(),
+            config_path: ctx.config_path.clone(),
+            settings,
+            settings_state: ListState::default().with_selected(Some(0)),
+            last_refresh: Instant::now(),
+            last_loaded: None,
+            connection: Connection::Connecting,
+            server_version: None,
+            history: None,
+            show_events: false,
+            events: VecDeque::new(),
+            event_feed: None,
+            extras: HashMap::new(),
+            graphics: graphics::detect(),
+            avatars: HashMap::new(),
+            shown_avatar: None,
+            dashboard: None,
+            last_dashboard: None,
+            pending: HashSet::new(),
+            undo: None,
+            clipboard: None,
+            tx,
+            rx,
+            ctx: Arc::new(ctx),
+        }
+    }
+
+    /// Runs `task` on its own task, marking `operation` as in flight until
+    /// its message arrives. Does nothing while the same operation is still
+    /// running.
+    fn spawn<F>(&mut self, operation: Operation, task: impl FnOnce(Arc<CliContext>) -> F)
+    where
+        F: Future<Output = Message> + Send + 'static,
+    {
+        if !self.pending.insert(operation) {
+            return;
         }
+        let tx = self.tx.clone();
+        let future = task(self.ctx.clone());
+        tokio::spawn(async move {
+            let _ = tx.send(future.await);
+        });
     }
 
-    pub async fn load_channels(&mut self) -> Result<()> {
-        self.loading = true;
+    fn is_pending(&self, operation: &Operation) -> bool {
+        self.pending.contains(operation)
+    }
 
-        let channels_result = {
-            let ctx = self.ctx.lock().await;
-            let mut client = ctx.client.clone();
+    /// Applies the results of operations that finished since the last call.
+    pub fn drain_messages(&mut self) {
+        while let Ok(message) = self.rx.try_recv() {
+            self.handle_message(message);
+        }
+    }
+
+    fn handle_message(&mut self, message: Message) {
+        self.pending.remove(&message.operation());
+        let was_lost = matches!(self.connection, Connection::Lost { .. });
+        if let Some(outcome) = message.outcome() {
+            self.record_outcome(outcome);
+        }
+        match message {
+            Message::Channels {
+                page,
+                append,
+                announce,
+                generation,
+            } => self.apply_channels(page, append, announce, generation),
+            Message::Tracked { name, result } => match result {
+                Ok(response) => {
+                    self.notify(ToastLevel::Success, describe_tracked(&name, &response));
+                    self.refresh(false);
+                }
+                Err(e) if e.code() == tonic::Code::AlreadyExists => {
+                    self.notify(
+                        ToastLevel::Warning,
+                        t!("already-tracked", name = name.as_str()),
+                    );
+                }
+                Err(e) => {
+                    self.notify(
+                        ToastLevel::Error,
+                        t!("tui-track-failed", error = e.message()),
+                    );
+                }
+            },
+            Message::Untracked { name, result } => match result {
+                Ok(()) => {
+                    self.notify_for(
+                        ToastLevel::Success,
+                        t!("tui-untracked-undo", name = name.as_str()),
+                        UNDO_WINDOW,
+                    );
+                    self.undo = Some((name, Instant::now()));
+                    self.refresh(false);
+                }
+                Err(e) => {
+                    self.notify(
+                        ToastLevel::Error,
+                        t!("tui-untrack-failed", error = e.message()),
+                    );
+                }
+            },
+            Message::Extras { name, extras } => {
+                self.extras.insert(name, extras);
+            }
+            Message::History { channel, page } => self.apply_history_page(channel, page),
+            Message::Search { query, result } => self.apply_search(query, result),
+            Message::Avatar { url, image } => {
+                self.avatars.insert(url, image);
+            }
+            Message::ServerInfo(info) => {
+                if let Ok(info) = info {
+                    self.server_version = Some(info.version);
+                    // Whatever loaded while the server was away is stale.
+                    if was_lost {
+                        self.notify(ToastLevel::Success, t!("tui-reconnected"));
+                        self.refresh(false);
+                    }
+                }
+            }
+            Message::Dashboard {
+                dashboard,
+                announce,
+            } => match dashboard {
+                Ok(dashboard) => self.dashboard = Some(Ok(dashboard)),
+                Err(e) => {
+                    if announce {
+                        self.notify(
+                            ToastLevel::Error,
+                            t!("tui-dashboard-failed", error = e.message()),
+                        );
+                    }
+                    // Numbers already shown stay up rather than the error.
+                    if !matches!(self.dashboard, Some(Ok(_))) {
+                        self.dashboard = Some(Err(e.message().to_string()));
+                    }
+                }
+            },
+        }
+    }
 
-            let request = ctx.create_request(ListChannelsRequest {});
+    /// Reloads the channels, fetching as many as are already loaded so the
+    /// selection doesn't fall off the end. `announce` reports the outcome
+    /// with a toast; background refreshes fail quietly and try again later.
+    pub fn refresh(&mut self, announce: bool) {
+        self.last_refresh = Instant::now();
+        if self.is_pending(&Operation::Refresh) {
+            return;
+        }
+        // Pages still on their way belong to the list being replaced.
+        self.generation += 1;
+        let generation = self.generation;
+        let page_size = (self.channels.len() as u32).max(CHANNEL_PAGE);
+        self.spawn(Operation::Refresh, move |ctx| async move {
+            Message::Channels {
+                page: list_channels(&ctx, page_size, String::new()).await,
+                append: false,
+                announce,
+                generation,
+            }
+        });
+    }
 
-            client.list_channels(request).await
+    /// Fetches the next page of channels, if there is one.
+    fn load_more_channels(&mut self) {
+        let Some(token) = self.next_page_token.clone() else {
+            return;
         };
-
-        match channels_result {
-            Ok(response) => {
-                self.channels = response.into_inner().channels;
-                self.loading = false;
-                self.set_status("Channels loaded successfully");
-                Ok(())
+        let generation = self.generation;
+        self.spawn(Operation::LoadMore, move |ctx| async move {
+            Message::Channels {
+                page: list_channels(&ctx, CHANNEL_PAGE, token).await,
+                append: true,
+                announce: false,
+                generation,
             }
+        });
+    }
+
+    fn apply_channels(
+        &mut self,
+        page: Result<ListChannelsResponse, tonic::Status>,
+        append: bool,
+        announce: bool,
+        generation: u64,
+    ) {
+        if generation != self.generation {
+            return;
+        }
+        let page = match page {
+            Ok(page) => page,
             Err(e) => {
-                self.loading = false;
-                self.set_status(&format!("Error loading channels: {}", e.message()));
-                Err(e.into())
+                // Stop paging rather than retrying on every key press; a
+                // refresh starts over.
+                if append {
+                    self.next_page_token = None;
+                }
+                self.load_all = false;
+                self.jump_to_end = false;
+                if announce || append {
+                    self.notify(
+                        ToastLevel::Error,
+                        t!("tui-load-failed", error = e.message()),
+                    );
+                }
+                return;
+            }
+        };
+
+        self.last_loaded = Some(Local::now());
+        if append {
+            self.channels.extend(page.channels);
+        } else {
+            self.channels = page.channels;
+            self.extras.clear();
+        }
+        self.next_page_token = Some(page.next_page_token).filter(|t| !t.is_empty());
+        if announce {
+            self.notify(ToastLevel::Success, t!("tui-loaded"));
+        }
+
+        if let Some(name) = self.restore_channel.take() {
+            if let Some(i) = self.filtered_channels().iter().position(|c| c.name == name) {
+                self.channel_list_state.select(Some(i));
+            }
+        }
+        if self.jump_to_end {
+            let len = self.filtered_channels().len();
+            self.channel_list_state.select(Some(len.saturating_sub(1)));
+        }
+        if self.next_page_token.is_none() {
+            self.load_all = false;
+            self.jump_to_end = false;
+        } else if self.load_all {
+            self.load_more_channels();
+        }
+    }
+
+    /// Loads every page not loaded yet, e.g. so a search covers them all.
+    fn load_all_channels(&mut self) {
+        if self.next_page_token.is_some() {
+            self.load_all = true;
+            self.load_more_channels();
+        }
+    }
+
+    /// Loads the next page if moving the selection `delta` rows down would
+    /// get close to the end of the channels loaded so far.
+    fn load_ahead(&mut self, delta: usize) {
+        let target = self.channel_list_state.selected().unwrap_or(0) + delta;
+        if self.next_page_token.is_some() && target + LOAD_AHEAD >= self.filtered_channels().len() {
+            self.load_more_channels();
+        }
+    }
+
+    /// Whether `refresh_interval` has passed since the channel list was last
+    /// loaded. Refreshes wait while a prompt is open.
+    fn refresh_due(&self) -> bool {
+        let interval = self.settings.refresh_interval;
+        interval > 0
+            && self.input_mode == InputMode::Normal
+            && self.last_refresh.elapsed() >= Duration::from_secs(interval)
+    }
+
+    /// Notes whether a call reached the server, scheduling a reconnect
+    /// attempt while it doesn't.
+    fn record_outcome(&mut self, outcome: Result<(), &tonic::Status>) {
+        match outcome {
+            Err(e) if e.code() == tonic::Code::Unavailable => {
+                let attempts = match &self.connection {
+                    Connection::Lost { attempts, .. } => attempts + 1,
+                    _ => 1,
+                };
+                self.connection = Connection::Lost {
+                    attempts,
+                    retry_at: Instant::now() + backoff(attempts),
+                    error: e.message().to_string(),
+                };
             }
+            _ => self.connection = Connection::Connected,
         }
     }
 
-    pub fn set_status(&mut self, message: &str) {
-        self.status_message = Some((message.to_string(), Instant::now()));
+    /// Asks the server for its version, which doubles as a reconnect attempt
+    /// while the connection is lost.
+    pub fn check_server(&mut self) {
+        self.spawn(Operation::ServerInfo, |ctx| async move {
+            let mut client = ctx.client.clone();
+            let request = ctx.create_request(GetServerInfoRequest {});
+            Message::ServerInfo(
+                client
+                    .get_server_info(request)
+                    .await
+                    .map(|r| r.into_inner()),
+            )
+        });
+    }
+
+    fn reconnect_due(&self) -> bool {
+        matches!(self.connection, Connection::Lost { retry_at, .. } if Instant::now() >= retry_at)
+    }
+
+    /// Reloads the dashboard. `announce` reports a failure with a toast, as
+    /// with [`App::refresh`].
+    pub fn load_dashboard(&mut self, announce: bool) {
+        self.last_dashboard = Some(Instant::now());
+        self.spawn(Operation::Dashboard, move |ctx| async move {
+            Message::Dashboard {
+                dashboard: fetch_dashboard(&ctx).await,
+                announce,
+            }
+        });
+    }
+
+    /// Whether the dashboard tab is showing and hasn't been loaded yet, or
+    /// not within `refresh_interval`.
+    fn dashboard_due(&self) -> bool {
+        let interval = self.settings.refresh_interval;
+        self.selected_tab == DASHBOARD_TAB
+            && self
+                .last_dashboard
+                .is_none_or(|last| interval > 0 && last.elapsed() >= Duration::from_secs(interval))
+    }
+
+    /// Shows `message` as a toast on top of the ones still showing, pushing
+    /// out the oldest once there are [`MAX_TOASTS`].
+    pub fn notify(&mut self, level: ToastLevel, message: String) {
+        self.notify_for(level, message, level.lifetime());
+    }
+
+    /// Like [`App::notify`], but shows the toast for `lifetime` rather than
+    /// as long as its level says.
+    fn notify_for(&mut self, level: ToastLevel, message: String, lifetime: Duration) {
+        if self.toasts.len() == MAX_TOASTS {
+            self.toasts.pop_front();
+        }
+        self.toasts.push_back(Toast {
+            message,
+            level,
+            shown_at: Instant::now(),
+            lifetime,
+        });
+    }
+
+    pub fn open_help(&mut self) {
+        self.show_help = true;
+        self.help_scroll = 0;
+        self.help_filter.clear();
+        self.help_filtering = false;
+    }
+
+    fn set_help_filter(&mut self, filter: String) {
+        self.help_filter = filter;
+        self.help_scroll = 0;
+    }
+
+    /// Scrolls the help overlay by `delta` lines, stopping once its last
+    /// line is in view.
+    pub fn scroll_help(&mut self, delta: isize) {
+        let (width, height) = crossterm::terminal::size().unwrap_or_default();
+        let rows = help_layout(Rect::new(0, 0, width, height))[1].height as usize;
+        let len = help_lines(&self.settings.keys, &self.help_filter).len();
+        self.help_scroll = self
+            .help_scroll
+            .saturating_add_signed(delta)
+            .min(len.saturating_sub(rows));
+    }
+
+    /// Puts `text` on the system clipboard and says so in a toast.
+    pub fn copy(&mut self, text: String) {
+        let result = match self.clipboard.take() {
+            Some(clipboard) => Ok(clipboard),
+            None => arboard::Clipboard::new(),
+        }
+        .and_then(|mut clipboard| {
+            clipboard.set_text(text.as_str())?;
+            Ok(clipboard)
+        });
+        match result {
+            Ok(clipboard) => {
+                self.clipboard = Some(clipboard);
+                self.notify(ToastLevel::Success, t!("tui-copied", text = text.as_str()));
+            }
+            Err(e) => self.notify(
+                ToastLevel::Error,
+                t!("tui-copy-failed", error = e.to_string()),
+            ),
+        }
+    }
+
+    fn expire_toasts(&mut self) {
+        self.toasts
+            .retain(|toast| toast.shown_at.elapsed() < toast.lifetime);
+    }
+
+    /// The footer's `[key] action` hints, when that's what it shows.
+    fn footer_hints(&self) -> Option<String> {
+        match self.input_mode {
+            InputMode::ViewingHistory => Some(history_footer_text(&self.settings.keys)),
+            InputMode::Normal if !self.is_searching => Some(footer_text(&self.settings.keys)),
+            _ => None,
+        }
     }
 
     pub fn filtered_channels(&self) -> Vec<&Channel> {
         if self.search_query.is_empty() {
             self.channels.iter().collect()
+        } else if let Some(results) = self.server_results() {
+            results.iter().collect()
         } else {
+            let query = self.search_query.to_lowercase();
             self.channels
                 .iter()
                 .filter(|c| {
-                    c.name
-                        .to_lowercase()
-                        .contains(&self.search_query.to_lowercase())
+                    c.name.to_lowercase().contains(&query)
+                        || self
+                            .alias(&c.name)
+                            .is_some_and(|a| a.to_lowercase().contains(&query))
                 })
                 .collect()
         }
     }
 
+    /// The server's matches for the current query, once they've arrived.
+    /// Until then the loaded channels are searched by name.
+    fn server_results(&self) -> Option<&Vec<Channel>> {
+        match &self.search_results {
+            Some((query, channels)) if self.server_search && *query == self.search_query => {
+                Some(channels)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn toggle_server_search(&mut self) {
+        self.server_search = !self.server_search;
+        self.channel_list_state.select(Some(0));
+        self.search_server();
+    }
+
+    /// Sends the current query to `SearchChannels` when server search is on.
+    /// While a search is running the next one waits for its result.
+    fn search_server(&mut self) {
+        if !self.server_search || self.search_query.is_empty() {
+            return;
+        }
+        let query = self.search_query.clone();
+        self.spawn(Operation::Search, move |ctx| async move {
+            let mut client = ctx.client.clone();
+            let request = ctx.create_request(SearchChannelsRequest {
+                query: query.clone(),
+                limit: SEARCH_LIMIT,
+                include_streams: true,
+                ..Default::default()
+            });
+            let result = client
+                .search_channels(request)
+                .await
+                .map(|r| r.into_inner().channels);
+            Message::Search { query, result }
+        });
+    }
+
+    fn apply_search(&mut self, query: String, result: Result<Vec<Channel>, tonic::Status>) {
+        match result {
+            Ok(channels) => self.search_results = Some((query, channels)),
+            Err(e) => {
+                self.server_search = false;
+                self.notify(
+                    ToastLevel::Error,
+                    t!("tui-search-failed", error = e.message()),
+                );
+                return;
+            }
+        }
+        // The query changed while the search ran.
+        if self.search_query != self.search_results.as_ref().map_or("", |(q, _)| q) {
+            self.search_server();
+        }
+    }
+
+    /// What to pick up again next run.
+    fn session(&self) -> Session {
+        let channel = self
+            .channel_list_state
+            .selected()
+            .and_then(|i| self.filtered_channels().get(i).map(|c| c.name.clone()));
+        Session {
+            tab: self.selected_tab,
+            search: self.search_query.clone(),
+            server_search: self.server_search,
+            channel,
+        }
+    }
+
+    pub fn alias(&self, login: &str) -> Option<&str> {
+        alias_for(&self.aliases, login)
+    }
+
     pub fn next_channel(&mut self) {
+        self.load_ahead(1);
         let channels = self.filtered_channels();
         if channels.is_empty() {
             return;
@@ -117,10 +832,13 @@ impl App {
 
         let i = match self.channel_list_state.selected() {
             Some(i) => {
-                if i >= channels.len() - 1 {
-                    0
-                } else {
+                if i < channels.len() - 1 {
                     i + 1
+                } else if self.next_page_token.is_some() {
+                    // The next page is on its way; wrap once it's all here.
+                    i
+                } else {
+                    0
                 }
             }
             None => 0,
@@ -147,69 +865,640 @@ impl App {
         self.channel_list_state.select(Some(i));
     }
 
-    pub async fn track_channel(&mut self, name: String) -> Result<()> {
-        let result = {
-            let ctx = self.ctx.lock().await;
-            let mut client = ctx.client.clone();
+    /// Moves the selection `delta` rows down, stopping at the last channel.
+    pub fn page_down(&mut self, delta: usize) {
+        self.load_ahead(delta);
+        let len = self.filtered_channels().len();
+        if len > 0 {
+            let i = self.channel_list_state.selected().unwrap_or(0) + delta;
+            self.channel_list_state.select(Some(i.min(len - 1)));
+        }
+    }
 
-            let request = ctx.create_request(TrackChannelRequest { name: name.clone() });
+    pub fn page_up(&mut self, delta: usize) {
+        let i = self.channel_list_state.selected().unwrap_or(0);
+        self.channel_list_state
+            .select(Some(i.saturating_sub(delta)));
+    }
+
+    pub fn select_last_channel(&mut self) {
+        let len = self.filtered_channels().len();
+        self.channel_list_state.select(Some(len.saturating_sub(1)));
+        if self.next_page_token.is_some() {
+            self.jump_to_end = true;
+            self.load_all_channels();
+        }
+    }
+
+    /// Shows or hides the event feed, subscribing the first time it's shown
+    /// or after the previous subscription ended.
+    pub fn toggle_events(&mut self) {
+        self.show_events = !self.show_events;
+        if self.show_events && self.event_feed.is_none() {
+            self.subscribe_events();
+        }
+    }
+
+    fn subscribe_events(&mut self) {
+        let mut client = self.ctx.client.clone();
+        let mut request = self.ctx.create_request(WatchEventsRequest::default());
+        // The call is meant to stay open, so drop the usual deadline.
+        request.metadata_mut().remove("grpc-timeout");
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let mut events = match client.watch_events(request).await {
+                Ok(response) => response.into_inner(),
+                Err(e) => {
+                    let _ = tx.send(Err(e));
+                    return;
+                }
+            };
+            loop {
+                match events.message().await {
+                    Ok(Some(event)) => {
+                        if tx.send(Ok(event)).is_err() {
+                            return;
+                        }
+                    }
+                    // Dropping the sender tells the feed the server hung up.
+                    Ok(None) => return,
+                    Err(e) => {
+                        let _ = tx.send(Err(e));
+                        return;
+                    }
+                }
+            }
+        });
+        self.event_feed = Some(rx);
+    }
+
+    /// Moves events that arrived since the last call into the feed, and
+    /// reloads the channel list if any of them changed a live status.
+    pub fn drain_events(&mut self) {
+        let Some(feed) = &mut self.event_feed else {
+            return;
+        };
+        let mut live_changed = false;
+        let mut ended = None;
+        loop {
+            match feed.try_recv() {
+                Ok(Ok(event)) => {
+                    live_changed |= matches!(event.kind(), EventKind::Online | EventKind::Offline);
+                    if self.events.len() == EVENT_BACKLOG {
+                        self.events.pop_front();
+                    }
+                    self.events.push_back(event);
+                }
+                Ok(Err(e)) => {
+                    ended = Some(t!("events-failed", error = describe_status(&e)));
+                    break;
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    ended = Some(t!("events-closed"));
+                    break;
+                }
+            }
+        }
+
+        if let Some(message) = ended {
+            self.event_feed = None;
+            self.notify(ToastLevel::Warning, message);
+        }
+        if live_changed {
+            self.refresh(false);
+        }
+    }
+
+    pub fn next_setting(&mut self) {
+        let i = self
+            .settings_state
+            .selected()
+            .map_or(0, |i| (i + 1) % SETTINGS.len());
+        self.settings_state.select(Some(i));
+    }
+
+    pub fn previous_setting(&mut self) {
+        let i = self
+            .settings_state
+            .selected()
+            .map_or(0, |i| (i + SETTINGS.len() - 1) % SETTINGS.len());
+        self.settings_state.select(Some(i));
+    }
+
+    fn selected_setting(&self) -> Option<&'static str> {
+        self.settings_state
+            .selected()
+            .and_then(|i| SETTINGS.get(i).copied())
+    }
 
-            client.track_channel(request).await
+    /// Starts editing the selected setting, or flips it if it's a switch.
+    pub fn edit_setting(&mut self) {
+        let Some(key) = self.selected_setting() else {
+            return;
         };
+        let value = self.settings.get(key).unwrap_or_default();
+        if key == "color" {
+            self.save_setting(key, &(value != "true").to_string());
+        } else {
+            self.input_buffer = value;
+            self.input_mode = InputMode::EditingSetting;
+        }
+    }
 
+    /// Sets `key` and writes the config back to disk. Nothing changes if the
+    /// value is invalid or the file can't be written.
+    pub fn save_setting(&mut self, key: &str, value: &str) -> bool {
+        let mut settings = self.settings.clone();
+        let result = settings
+            .set(key, value.trim())
+            .and_then(|()| settings.save(&self.config_path));
         match result {
-            Ok(_) => {
-                self.set_status(&format!("Successfully tracked channel: {}", name));
-                self.load_channels().await?;
-                Ok(())
+            Ok(()) => {
+                self.settings = settings;
+                let message = match key {
+                    "server" => t!("tui-settings-saved-restart", key = key),
+                    _ => t!("tui-settings-saved", key = key),
+                };
+                self.notify(ToastLevel::Success, message);
+                true
             }
             Err(e) => {
-                if e.code() == tonic::Code::AlreadyExists {
-                    self.set_status(&format!("Channel '{}' is already being tracked", name));
-                } else {
-                    self.set_status(&format!("Failed to track channel: {}", e.message()));
-                }
-                Err(e.into())
+                self.notify(ToastLevel::Error, format!("{:#}", e));
+                false
             }
         }
     }
 
-    pub async fn untrack_channel(&mut self, name: String) -> Result<()> {
-        let result = {
-            let ctx = self.ctx.lock().await;
-            let mut client = ctx.client.clone();
+    pub fn open_history(&mut self, channel: String) {
+        self.history = Some(HistoryView {
+            channel,
+            streams: Vec::new(),
+            state: TableState::default(),
+            exhausted: false,
+        });
+        self.input_mode = InputMode::ViewingHistory;
+        self.load_history_page();
+    }
 
-            let request = ctx.create_request(UntrackChannelRequest { name: name.clone() });
+    /// Starts fetching the extra details of the selected channel unless
+    /// they're already known. Failures leave them empty rather than retrying
+    /// on every redraw; a refresh tries again.
+    pub fn ensure_extras(&mut self) {
+        let Some(name) = self
+            .channel_list_state
+            .selected()
+            .and_then(|i| self.filtered_channels().get(i).map(|c| c.name.clone()))
+        else {
+            return;
+        };
+        if self.extras.contains_key(&name) {
+            return;
+        }
+
+        self.spawn(Operation::Extras(name.clone()), move |ctx| async move {
+            let mut history_client = ctx.client.clone();
+            let mut stats_client = ctx.client.clone();
+            let history = ctx.create_request(GetStreamHistoryRequest {
+                name: name.clone(),
+                limit: 1,
+                ..Default::default()
+            });
+            let stats = ctx.create_request(GetStreamStatsRequest {
+                name: name.clone(),
+                since: None,
+            });
+            let (history, stats) = tokio::join!(
+                history_client.get_stream_history(history),
+                stats_client.get_stream_stats(stats)
+            );
 
-            client.untrack_channel(request).await
+            let last_stream = history
+                .ok()
+                .and_then(|r| r.into_inner().streams.into_iter().next());
+            let stats = stats
+                .ok()
+                .and_then(|r| r.into_inner().channels.into_iter().next());
+            let seconds = |durations: &[prost_types::Duration]| {
+                durations.iter().map(|d| d.seconds.max(0) as u64).collect()
+            };
+            let extras = ChannelExtras {
+                last_stream,
+                total_streams: stats.as_ref().map_or(0, |s| s.total_streams),
+                total_seconds: stats
+                    .as_ref()
+                    .and_then(|s| s.total_duration)
+                    .map_or(0, |d| d.seconds),
+                recent: stats.as_ref().map_or_else(Vec::new, |s| seconds(&s.recent)),
+                weekdays: stats
+                    .as_ref()
+                    .map_or_else(Vec::new, |s| seconds(&s.weekdays)),
+            };
+            Message::Extras { name, extras }
+        });
+    }
+
+    /// Starts fetching the selected channel's profile image, if the terminal
+    /// can draw it and it isn't loaded yet.
+    pub fn ensure_avatar(&mut self) {
+        if self.graphics.is_none() {
+            return;
+        }
+        let Some(url) = self.selected_avatar_url() else {
+            return;
         };
+        if self.avatars.contains_key(&url) {
+            return;
+        }
+        self.spawn(Operation::Avatar(url.clone()), move |_| async move {
+            let image = fetch_avatar(&url).await.ok().map(Arc::new);
+            Message::Avatar { url, image }
+        });
+    }
 
-        match result {
-            Ok(_) => {
-                self.set_status(&format!("Successfully untracked channel: {}", name));
-                self.load_channels().await?;
-                Ok(())
+    fn selected_avatar_url(&self) -> Option<String> {
+        let i = self.channel_list_state.selected()?;
+        let channel = self.filtered_channels().get(i).copied()?;
+        Some(channel.profile_image_url.clone()).filter(|url| !url.is_empty())
+    }
+
+    /// The loaded avatar of the selected channel and where it goes on a
+    /// screen of `area`, while nothing covers its spot.
+    fn wanted_avatar(&self, area: Rect) -> Option<(String, Rect)> {
+        let visible = self.selected_tab == CHANNELS_TAB
+            && self.history.is_none()
+            && !self.show_help
+            && self.input_mode == InputMode::Normal;
+        if !visible {
+            return None;
+        }
+        let url = self.selected_avatar_url()?;
+        self.avatars.get(&url)?.as_ref()?;
+        let (_, details, _) = channels_layout(
+            screen_layout(area)[1],
+            self.show_events,
+            &self.pane_layout(),
+        );
+        Some((url, avatar_area(details?)?))
+    }
+
+    /// Draws the selected channel's avatar over its spot in the details
+    /// pane after ratatui has drawn the rest, clearing the previous one
+    /// first. Nothing is written while the avatar stays put.
+    fn draw_avatar(&mut self, area: Rect) -> io::Result<()> {
+        let Some(protocol) = self.graphics else {
+            return Ok(());
+        };
+        let wanted = self.wanted_avatar(area);
+        if wanted == self.shown_avatar {
+            return Ok(());
+        }
+        let mut stdout = io::stdout();
+        if let Some((_, rect)) = self.shown_avatar.take() {
+            write!(stdout, "{}", graphics::clear(protocol))?;
+            // Sixels stay until the cells under them are drawn over, which
+            // ratatui won't do for cells it thinks are blank already.
+            if protocol == Protocol::Sixel {
+                for y in rect.top()..rect.bottom() {
+                    queue!(stdout, MoveTo(rect.x, y))?;
+                    write!(stdout, "{}", " ".repeat(rect.width as usize))?;
+                }
+            }
+        }
+        if let Some((url, rect)) = &wanted {
+            if let Some(Some(image)) = self.avatars.get(url) {
+                queue!(stdout, MoveTo(rect.x, rect.y))?;
+                write!(
+                    stdout,
+                    "{}",
+                    graphics::encode(protocol, image, rect.width, rect.height)
+                )?;
+            }
+        }
+        stdout.flush()?;
+        self.shown_avatar = wanted;
+        Ok(())
+    }
+
+    pub fn close_history(&mut self) {
+        self.history = None;
+        self.input_mode = InputMode::Normal;
+    }
+
+    /// Fetches the page after the streams already shown, if there may be one.
+    fn load_history_page(&mut self) {
+        let Some(history) = &self.history else {
+            return;
+        };
+        if history.exhausted {
+            return;
+        }
+        let channel = history.channel.clone();
+        let request = GetStreamHistoryRequest {
+            name: channel.clone(),
+            limit: HISTORY_PAGE,
+            offset: history.streams.len() as u32,
+            since: None,
+        };
+        self.spawn(Operation::History(channel.clone()), move |ctx| async move {
+            let mut client = ctx.client.clone();
+            let page = client
+                .get_stream_history(ctx.create_request(request))
+                .await
+                .map(|r| r.into_inner().streams);
+            Message::History { channel, page }
+        });
+    }
+
+    fn apply_history_page(&mut self, channel: String, page: Result<Vec<Stream>, tonic::Status>) {
+        // The pane may have been closed or moved on to another channel.
+        let Some(history) = self.history.as_mut().filter(|h| h.channel == channel) else {
+            return;
+        };
+        match page {
+            Ok(streams) => {
+                history.exhausted = streams.len() < HISTORY_PAGE as usize;
+                history.streams.extend(streams);
+                if history.state.selected().is_none() && !history.streams.is_empty() {
+                    history.state.select(Some(0));
+                }
             }
             Err(e) => {
-                self.set_status(&format!("Failed to untrack channel: {}", e.message()));
-                Err(e.into())
+                history.exhausted = true;
+                self.notify(
+                    ToastLevel::Error,
+                    t!("tui-history-failed", error = e.message()),
+                );
             }
         }
     }
+
+    /// Moves the history selection by `delta` rows, loading more streams
+    /// when it gets close to the last one fetched.
+    pub fn scroll_history(&mut self, delta: isize) {
+        let Some(history) = &mut self.history else {
+            return;
+        };
+        let len = history.streams.len();
+        if len == 0 {
+            return;
+        }
+        let current = history.state.selected().unwrap_or(0);
+        let next = current.saturating_add_signed(delta).min(len - 1);
+        history.state.select(Some(next));
+        if next + 5 >= len {
+            self.load_history_page();
+        }
+    }
+
+    pub fn track_channel(&mut self, name: String) {
+        self.spawn(Operation::Track(name.clone()), move |ctx| async move {
+            let mut client = ctx.client.clone();
+            let request = ctx.create_request(TrackChannelRequest { name: name.clone() });
+            let result = client
+                .track_channel(request)
+                .await
+                .map(|r| Box::new(r.into_inner()));
+            ctx.invalidate_cache();
+            Message::Tracked { name, result }
+        });
+    }
+
+    /// The channels tab's layout as configured, except that stream history
+    /// always gets the details pane.
+    fn pane_layout(&self) -> PaneLayout {
+        let mut layout = self.settings.layout;
+        layout.details |= self.history.is_some();
+        layout
+    }
+
+    /// Changes the channels tab's layout and saves it to the config, so the
+    /// next run starts out the same.
+    pub fn change_layout(&mut self, change: impl FnOnce(&mut PaneLayout)) {
+        let mut layout = self.settings.layout;
+        change(&mut layout);
+        if layout == self.settings.layout {
+            return;
+        }
+        self.settings.layout = layout;
+        if let Err(e) = self.settings.save(&self.config_path) {
+            self.notify(
+                ToastLevel::Error,
+                t!("tui-layout-save-failed", error = format!("{:#}", e)),
+            );
+        }
+    }
+
+    /// Tracks the channel untracked last again, if that was within the
+    /// [`UNDO_WINDOW`].
+    pub fn undo_untrack(&mut self) {
+        if let Some((name, _)) = self
+            .undo
+            .take()
+            .filter(|(_, at)| at.elapsed() < UNDO_WINDOW)
+        {
+            self.track_channel(name);
+        }
+    }
+
+    pub fn untrack_channel(&mut self, name: String) {
+        self.spawn(Operation::Untrack(name.clone()), move |ctx| async move {
+            let mut client = ctx.client.clone();
+            let request = ctx.create_request(UntrackChannelRequest { name: name.clone() });
+            let result = client.untrack_channel(request).await.map(|_| ());
+            ctx.invalidate_cache();
+            Message::Untracked { name, result }
+        });
+    }
+}
+
+/// Network calls that run on their own tasks, each with its own loading
+/// indicator.
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum Operation {
+    Refresh,
+    LoadMore,
+    Track(String),
+    Untrack(String),
+    Extras(String),
+    History(String),
+    Dashboard,
+    ServerInfo,
+    Search,
+    Avatar(String),
+}
+
+/// The result of an [`Operation`], sent back to the UI loop.
+enum Message {
+    Channels {
+        page: Result<ListChannelsResponse, tonic::Status>,
+        /// Whether this is the next page rather than a reload.
+        append: bool,
+        announce: bool,
+        /// The [`App::refresh`] the page belongs to.
+        generation: u64,
+    },
+    Tracked {
+        name: String,
+        result: Result<Box<TrackChannelResponse>, tonic::Status>,
+    },
+    Untracked {
+        name: String,
+        result: Result<(), tonic::Status>,
+    },
+    Extras {
+        name: String,
+        extras: ChannelExtras,
+    },
+    History {
+        channel: String,
+        page: Result<Vec<Stream>, tonic::Status>,
+    },
+    Dashboard {
+        dashboard: Result<Dashboard, tonic::Status>,
+        announce: bool,
+    },
+    ServerInfo(Result<GetServerInfoResponse, tonic::Status>),
+    Search {
+        query: String,
+        result: Result<Vec<Channel>, tonic::Status>,
+    },
+    Avatar {
+        url: String,
+        image: Option<Arc<image::RgbaImage>>,
+    },
+}
+
+impl Message {
+    fn operation(&self) -> Operation {
+        match self {
+            Message::Channels { append: true, .. } => Operation::LoadMore,
+            Message::Channels { append: false, .. } => Operation::Refresh,
+            Message::Tracked { name, .. } => Operation::Track(name.clone()),
+            Message::Untracked { name, .. } => Operation::Untrack(name.clone()),
+            Message::Extras { name, .. } => Operation::Extras(name.clone()),
+            Message::History { channel, .. } => Operation::History(channel.clone()),
+            Message::Dashboard { .. } => Operation::Dashboard,
+            Message::ServerInfo(_) => Operation::ServerInfo,
+            Message::Search { .. } => Operation::Search,
+            Message::Avatar { url, .. } => Operation::Avatar(url.clone()),
+        }
+    }
+
+    /// How the call went, for the variants that carry its result.
+    fn outcome(&self) -> Option<Result<(), &tonic::Status>> {
+        let status = match self {
+            Message::Channels { page, .. } => page.as_ref().err(),
+            Message::Tracked { result, .. } => result.as_ref().err(),
+            Message::Untracked { result, .. } => result.as_ref().err(),
+            Message::History { page, .. } => page.as_ref().err(),
+            Message::Dashboard { dashboard, .. } => dashboard.as_ref().err(),
+            Message::ServerInfo(info) => info.as_ref().err(),
+            Message::Search { result, .. } => result.as_ref().err(),
+            Message::Extras { .. } | Message::Avatar { .. } => return None,
+        };
+        Some(status.map_or(Ok(()), Err))
+    }
+}
+
+async fn list_channels(
+    ctx: &CliContext,
+    page_size: u32,
+    page_token: String,
+) -> Result<ListChannelsResponse, tonic::Status> {
+    let mut client = ctx.client.clone();
+    let request = ctx.create_request(ListChannelsRequest {
+        page_size,
+        page_token,
+        ..Default::default()
+    });
+    Ok(client.list_channels(request).await?.into_inner())
+}
+
+/// Gathers this week's stats of every channel, the top categories and the
+/// streams live right now.
+async fn fetch_dashboard(ctx: &CliContext) -> Result<Dashboard, tonic::Status> {
+    let since = Some(to_timestamp(chart::week_start(Utc::now())));
+    let mut stats_client = ctx.client.clone();
+    let mut categories_client = ctx.client.clone();
+    let mut live_client = ctx.client.clone();
+    let stats = ctx.create_request(GetStreamStatsRequest {
+        name: String::new(),
+        since,
+    });
+    let categories = ctx.create_request(GetTopCategoriesRequest {
+        since,
+        limit: DASHBOARD_TOP,
+    });
+    let live = ctx.create_request(ListLiveStreamsRequest {});
+    let (stats, categories, live) = tokio::try_join!(
+        stats_client.get_stream_stats(stats),
+        categories_client.get_top_categories(categories),
+        live_client.list_live_streams(live)
+    )?;
+
+    let channels = stats.into_inner().channels;
+    let seconds = |stats: &ChannelStats| stats.total_duration.map_or(0, |d| d.seconds);
+    Ok(Dashboard {
+        tracked: channels.len(),
+        live: live.into_inner().streams.len(),
+        week_seconds: channels.iter().map(seconds).sum(),
+        categories: categories.into_inner().categories,
+        // The server sorts channels by time streamed already.
+        streamers: channels
+            .iter()
+            .filter(|stats| seconds(stats) > 0)
+            .take(DASHBOARD_TOP as usize)
+            .map(|stats| {
+                let name = match stats.display_name.is_empty() {
+                    true => stats.name.clone(),
+                    false => stats.display_name.clone(),
+                };
+                (name, seconds(stats))
+            })
+            .collect(),
+    })
+}
+
+/// Downloads and decodes a profile image, shrunk to [`AVATAR_PIXELS`] as
+/// there's no use for more in a handful of cells.
+async fn fetch_avatar(url: &str) -> Result<image::RgbaImage> {
+    let bytes = reqwest::get(url).await?.error_for_status()?.bytes().await?;
+    let image = image::load_from_memory(&bytes)?;
+    Ok(image.thumbnail(AVATAR_PIXELS, AVATAR_PIXELS).to_rgba8())
+}
+
+/// A frame of the loading spinner, advancing every 100ms.
+fn spinner() -> &'static str {
+    const FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+    let millis = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    FRAMES[(millis / 100) as usize % FRAMES.len()]
 }
 
 pub async fn run_tui(ctx: CliContext) -> Result<()> {
+    let mut app = App::new(ctx);
+    app.settings.keys.validate()?;
+
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let mut app = App::new(ctx);
+    app.refresh(true);
+    app.check_server();
+    app.search_server();
 
-    let _ = app.load_channels().await;
+    let res = run_app(&mut terminal, &mut app);
+    let saved = app.session().save(&app.config_path);
 
-    let res = run_app(&mut terminal, &mut app).await;
+    if let Some(protocol) = app.graphics {
+        write!(io::stdout(), "{}", graphics::clear(protocol))?;
+    }
 
     disable_raw_mode()?;
     execute!(
@@ -219,114 +1508,497 @@ pub async fn run_tui(ctx: CliContext) -> Result<()> {
     )?;
     terminal.show_cursor()?;
 
-    res
+    res.and(saved)
 }
 
-async fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<()> {
+/// Draws and handles input on this thread while network calls run on their
+/// own tasks, their results arriving as [`Message`]s between frames.
+fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<()> {
     loop {
-        terminal.draw(|f| ui(f, app))?;
-
-        if let Event::Key(key) = event::read()? {
-            if key.kind == KeyEventKind::Press {
-                match app.input_mode {
-                    InputMode::Normal => match key.code {
-                        KeyCode::Char('q') if !app.is_searching => return Ok(()),
-                        KeyCode::Char('?') => app.show_help = !app.show_help,
-                        KeyCode::Tab => {
-                            app.selected_tab = (app.selected_tab + 1) % 2;
-                        }
-                        KeyCode::Char('/') if !app.is_searching => {
-                            app.is_searching = true;
-                            app.search_query.clear();
-                        }
-                        KeyCode::Esc if app.is_searching => {
-                            app.is_searching = false;
-                            app.search_query.clear();
-                        }
-                        KeyCode::Char(c) if app.is_searching => {
-                            app.search_query.push(c);
-                        }
-                        KeyCode::Backspace if app.is_searching => {
-                            app.search_query.pop();
-                        }
-                        KeyCode::Enter if app.is_searching => {
-                            app.is_searching = false;
-                        }
-                        KeyCode::Down | KeyCode::Char('j') if !app.is_searching => {
-                            app.next_channel();
-                        }
-                        KeyCode::Up | KeyCode::Char('k') if !app.is_searching => {
-                            app.previous_channel();
-                        }
-                        KeyCode::Char('r') if !app.is_searching => {
-                            let _ = app.load_channels().await;
-                        }
-                        KeyCode::Char('a') if !app.is_searching => {
-                            app.input_mode = InputMode::AddingChannel;
-                            app.input_buffer.clear();
-                        }
-                        KeyCode::Char('d') if !app.is_searching => {
-                            if let Some(i) = app.channel_list_state.selected() {
-                                if app.filtered_channels().get(i).is_some() {
-                                    app.input_mode = InputMode::ConfirmingDelete;
-                                }
-                            }
-                        }
-                        _ => {}
-                    },
-                    InputMode::AddingChannel => match key.code {
-                        KeyCode::Enter => {
-                            let name = app.input_buffer.clone();
-                            if !name.trim().is_empty() {
-                                app.input_mode = InputMode::Normal;
-                                let _ = app.track_channel(name).await;
-                            }
-                        }
-                        KeyCode::Esc => {
-                            app.input_mode = InputMode::Normal;
-                            app.input_buffer.clear();
-                        }
-                        KeyCode::Char(c) => {
-                            app.input_buffer.push(c);
-                        }
-                        KeyCode::Backspace => {
-                            app.input_buffer.pop();
-                        }
-                        _ => {}
-                    },
-                    InputMode::ConfirmingDelete => match key.code {
-                        KeyCode::Char('y') | KeyCode::Char('Y') => {
-                            if let Some(i) = app.channel_list_state.selected() {
-                                if let Some(channel) = app.filtered_channels().get(i) {
-                                    let name = channel.name.clone();
-                                    app.input_mode = InputMode::Normal;
-                                    let _ = app.untrack_channel(name).await;
-                                }
-                            }
-                        }
-                        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
-                            app.input_mode = InputMode::Normal;
-                        }
-                        _ => {}
-                    },
+        app.expire_toasts();
+        app.drain_messages();
+        app.drain_events();
+        if app.history.is_none() {
+            app.ensure_extras();
+            app.ensure_avatar();
+        }
+        if app.refresh_due() {
+            app.refresh(false);
+        }
+        if app.dashboard_due() {
+            app.load_dashboard(false);
+        }
+        if app.reconnect_due() {
+            app.check_server();
+        }
+        let frame = terminal.draw(|f| ui(f, app))?;
+        app.draw_avatar(frame.area)?;
+
+        if !event::poll(TICK)? {
+            continue;
+        }
+
+        let quit = match event::read()? {
+            Event::Key(key)
+                if key.kind == KeyEventKind::Press
+                    && key.modifiers.contains(KeyModifiers::CONTROL)
+                    && key.code == KeyCode::Char('s') =>
+            {
+                app.toggle_server_search();
+                false
+            }
+            Event::Key(key) if key.kind == KeyEventKind::Press => handle_key(app, key.code),
+            Event::Mouse(mouse) => {
+                let size = terminal.size()?;
+                let area = Rect::new(0, 0, size.width, size.height);
+                handle_mouse(app, mouse, area)
+            }
+            _ => false,
+        };
+        if quit {
+            return Ok(());
+        }
+    }
+}
+
+/// Applies a key press. Returns whether the app should quit.
+fn handle_key(app: &mut App, code: KeyCode) -> bool {
+    if app.show_help {
+        handle_help_key(app, code);
+        return false;
+    }
+    match app.input_mode {
+        InputMode::Normal if app.is_searching => match code {
+            KeyCode::Esc => {
+                app.is_searching = false;
+                app.search_query.clear();
+            }
+            KeyCode::Char(c) => {
+                app.search_query.push(c);
+                app.search_server();
+            }
+            KeyCode::Backspace => {
+                app.search_query.pop();
+                app.search_server();
+            }
+            KeyCode::Enter => {
+                app.is_searching = false;
+            }
+            _ => {}
+        },
+        InputMode::Normal => match app.settings.keys.action(code) {
+            Some(KeyAction::Quit) => return true,
+            Some(KeyAction::Tabs) => {
+                app.selected_tab = (app.selected_tab + 1) % tab_titles().len();
+            }
+            Some(KeyAction::Search) => {
+                app.load_all_channels();
+                app.is_searching = true;
+                app.search_query.clear();
+            }
+            Some(KeyAction::Refresh) if app.selected_tab == DASHBOARD_TAB => {
+                app.load_dashboard(true);
+            }
+            Some(KeyAction::Refresh) => {
+                app.refresh(true);
+                app.check_server();
+            }
+            Some(KeyAction::Add) => {
+                app.input_mode = InputMode::AddingChannel;
+                app.input_buffer.clear();
+            }
+            Some(KeyAction::Delete) => {
+                if let Some(i) = app.channel_list_state.selected() {
+                    if app.filtered_channels().get(i).is_some() {
+                        app.input_mode = InputMode::ConfirmingDelete;
+                    }
+                }
+            }
+            None => handle_fixed_key(app, code),
+        },
+        InputMode::AddingChannel => match code {
+            KeyCode::Enter => {
+                let name = app.input_buffer.clone();
+                if !name.trim().is_empty() {
+                    app.input_mode = InputMode::Normal;
+                    let name = app.aliases.get(name.trim()).cloned().unwrap_or(name);
+                    app.track_channel(name);
+                }
+            }
+            KeyCode::Esc => {
+                app.input_mode = InputMode::Normal;
+                app.input_buffer.clear();
+            }
+            KeyCode::Char(c) => {
+                app.input_buffer.push(c);
+            }
+            KeyCode::Backspace => {
+                app.input_buffer.pop();
+            }
+            _ => {}
+        },
+        InputMode::ConfirmingDelete => match code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => {
+                if let Some(i) = app.channel_list_state.selected() {
+                    if let Some(channel) = app.filtered_channels().get(i) {
+                        let name = channel.name.clone();
+                        app.input_mode = InputMode::Normal;
+                        app.untrack_channel(name);
+                    }
+                }
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                app.input_mode = InputMode::Normal;
+            }
+            _ => {}
+        },
+        InputMode::ViewingHistory => match code {
+            KeyCode::Esc | KeyCode::Char('h') => app.close_history(),
+            code if app.settings.keys.action(code) == Some(KeyAction::Quit) => return true,
+            KeyCode::Char('?') => app.open_help(),
+            KeyCode::Down | KeyCode::Char('j') => app.scroll_history(1),
+            KeyCode::Up | KeyCode::Char('k') => app.scroll_history(-1),
+            KeyCode::PageDown => app.scroll_history(10),
+            KeyCode::PageUp => app.scroll_history(-10),
+            _ => {}
+        },
+        InputMode::EditingSetting => match code {
+            KeyCode::Enter => {
+                if let Some(key) = app.selected_setting() {
+                    let value = app.input_buffer.clone();
+                    if app.save_setting(key, &value) {
+                        app.input_mode = InputMode::Normal;
+                        app.input_buffer.clear();
+                    }
                 }
             }
+            KeyCode::Esc => {
+                app.input_mode = InputMode::Normal;
+                app.input_buffer.clear();
+            }
+            KeyCode::Char(c) => app.input_buffer.push(c),
+            KeyCode::Backspace => {
+                app.input_buffer.pop();
+            }
+            _ => {}
+        },
+    }
+    false
+}
+
+/// Applies a key press while the help overlay is open, which takes them all
+/// until it's closed.
+fn handle_help_key(app: &mut App, code: KeyCode) {
+    if app.help_filtering {
+        match code {
+            KeyCode::Enter => app.help_filtering = false,
+            KeyCode::Esc => {
+                app.help_filtering = false;
+                app.set_help_filter(String::new());
+            }
+            KeyCode::Char(c) => {
+                let filter = format!("{}{c}", app.help_filter);
+                app.set_help_filter(filter);
+            }
+            KeyCode::Backspace => {
+                let mut filter = app.help_filter.clone();
+                filter.pop();
+                app.set_help_filter(filter);
+            }
+            _ => {}
         }
+        return;
+    }
+    match code {
+        KeyCode::Esc | KeyCode::Char('?') => app.show_help = false,
+        KeyCode::Char('/') => app.help_filtering = true,
+        KeyCode::Down | KeyCode::Char('j') => app.scroll_help(1),
+        KeyCode::Up | KeyCode::Char('k') => app.scroll_help(-1),
+        KeyCode::PageDown => app.scroll_help(PAGE_STEP as isize),
+        KeyCode::PageUp => app.scroll_help(-(PAGE_STEP as isize)),
+        KeyCode::Home => app.help_scroll = 0,
+        KeyCode::End => app.scroll_help(isize::MAX),
+        _ => {}
     }
 }
 
-fn ui(f: &mut Frame, app: &App) {
-    let chunks = Layout::default()
+/// Applies a key press in the normal mode that isn't bound to a remappable
+/// action.
+fn handle_fixed_key(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Enter if app.selected_tab == SETTINGS_TAB => app.edit_setting(),
+        KeyCode::Down => handle_fixed_action(app, FixedAction::Down),
+        KeyCode::Up => handle_fixed_action(app, FixedAction::Up),
+        KeyCode::PageDown => app.page_down(PAGE_STEP),
+        KeyCode::PageUp => app.page_up(PAGE_STEP),
+        KeyCode::Home => app.channel_list_state.select(Some(0)),
+        KeyCode::End => app.select_last_channel(),
+        code => {
+            if let Some(action) = FixedAction::of(code) {
+                handle_fixed_action(app, action);
+            }
+        }
+    }
+}
+
+fn handle_fixed_action(app: &mut App, action: FixedAction) {
+    match action {
+        FixedAction::Help => app.open_help(),
+        FixedAction::Down => match app.selected_tab {
+            CHANNELS_TAB => app.next_channel(),
+            SETTINGS_TAB => app.next_setting(),
+            _ => {}
+        },
+        FixedAction::Up => match app.selected_tab {
+            CHANNELS_TAB => app.previous_channel(),
+            SETTINGS_TAB => app.previous_setting(),
+            _ => {}
+        },
+        FixedAction::Open => {
+            if let Some(i) = app.channel_list_state.selected() {
+                if let Some(channel) = app.filtered_channels().get(i) {
+                    let url = channel_url(&channel.name);
+                    match open_url(&url) {
+                        Ok(()) => app.notify(ToastLevel::Info, t!("opened", url = url.as_str())),
+                        Err(e) => app.notify(ToastLevel::Error, e.to_string()),
+                    }
+                }
+            }
+        }
+        FixedAction::CopyUrl | FixedAction::CopyName => {
+            if let Some(i) = app.channel_list_state.selected() {
+                if let Some(channel) = app.filtered_channels().get(i) {
+                    let text = if action == FixedAction::CopyUrl {
+                        channel_url(&channel.name)
+                    } else {
+                        channel.name.clone()
+                    };
+                    app.copy(text);
+                }
+            }
+        }
+        FixedAction::History => {
+            if let Some(i) = app.channel_list_state.selected() {
+                if let Some(channel) = app.filtered_channels().get(i) {
+                    let name = channel.name.clone();
+                    app.open_history(name);
+                }
+            }
+        }
+        FixedAction::Events => app.toggle_events(),
+        FixedAction::Undo => app.undo_untrack(),
+        FixedAction::Details => app.change_layout(|layout| layout.details = !layout.details),
+        FixedAction::Compact => app.change_layout(|layout| layout.compact = !layout.compact),
+        FixedAction::Narrower => app.change_layout(|layout| {
+            layout.split = layout
+                .split
+                .saturating_sub(SPLIT_STEP)
+                .max(*PaneLayout::SPLIT_RANGE.start())
+        }),
+        FixedAction::Wider => app.change_layout(|layout| {
+            layout.split = (layout.split + SPLIT_STEP).min(*PaneLayout::SPLIT_RANGE.end())
+        }),
+    }
+}
+
+/// Applies a click or scroll, with `area` the whole screen. Clicking a
+/// footer hint acts like pressing its key. Returns whether the app should
+/// quit.
+fn handle_mouse(app: &mut App, mouse: MouseEvent, area: Rect) -> bool {
+    let position = Position::new(mouse.column, mouse.row);
+    let screen = screen_layout(area);
+    match mouse.kind {
+        MouseEventKind::ScrollDown | MouseEventKind::ScrollUp => {
+            let down = mouse.kind == MouseEventKind::ScrollDown;
+            if app.show_help {
+                app.scroll_help(if down { 1 } else { -1 });
+                return false;
+            }
+            match app.input_mode {
+                InputMode::ViewingHistory => app.scroll_history(if down { 1 } else { -1 }),
+                InputMode::Normal if app.selected_tab == SETTINGS_TAB => match down {
+                    true => app.next_setting(),
+                    false => app.previous_setting(),
+                },
+                InputMode::Normal if app.selected_tab == CHANNELS_TAB => match down {
+                    true => app.next_channel(),
+                    false => app.previous_channel(),
+                },
+                _ => {}
+            }
+        }
+        MouseEventKind::Down(MouseButton::Left) if app.show_help => app.show_help = false,
+        MouseEventKind::Down(MouseButton::Left) => {
+            if screen[2].contains(position) {
+                let key = app
+                    .footer_hints()
+                    .and_then(|hints| clicked_hint(&hints, screen[2], mouse.column));
+                if let Some(key) = key {
+                    return handle_key(app, key);
+                }
+            } else if app.input_mode != InputMode::Normal || app.is_searching {
+                // Prompts keep the focus until they're answered.
+            } else if screen[0].contains(position) {
+                if let Some(tab) = clicked_tab(screen[0], mouse.column) {
+                    app.selected_tab = tab;
+                }
+            } else if app.selected_tab == CHANNELS_TAB {
+                let (list, _, _) = channels_layout(screen[1], app.show_events, &app.pane_layout());
+                let selected = app.channel_list_state.selected();
+                let len = app.filtered_channels().len();
+                if let Some(row) = clicked_row(list, position, selected, len) {
+                    app.channel_list_state.select(Some(row));
+                }
+            } else if app.selected_tab == SETTINGS_TAB {
+                let list = settings_layout(screen[1])[0];
+                let selected = app.settings_state.selected();
+                if let Some(row) = clicked_row(list, position, selected, SETTINGS.len()) {
+                    app.settings_state.select(Some(row));
+                }
+            }
+        }
+        _ => {}
+    }
+    false
+}
+
+/// The row of a bordered list drawn in `area` that `position` is on.
+fn clicked_row(
+    area: Rect,
+    position: Position,
+    selected: Option<usize>,
+    len: usize,
+) -> Option<usize> {
+    let inner = area.inner(Margin::new(1, 1));
+    if !inner.contains(position) {
+        return None;
+    }
+    let window = list_window(selected, len, inner.height as usize);
+    let row = window.start + (position.y - inner.y) as usize;
+    window.contains(&row).then_some(row)
+}
+
+/// The rows of a `len` row list that are drawn in `height` rows. Lists are
+/// drawn from a fresh state each frame, so they're scrolled just far enough
+/// to show `selected`.
+fn list_window(selected: Option<usize>, len: usize, height: usize) -> Range<usize> {
+    let start = selected
+        .map_or(0, |s| (s + 1).saturating_sub(height))
+        .min(len);
+    start..(start + height).min(len)
+}
+
+/// The tab whose title is at `column` of the header drawn in `area`.
+fn clicked_tab(area: Rect, column: u16) -> Option<usize> {
+    // Titles are padded by a space on each side and split by a divider.
+    let mut x = area.x + 1;
+    for (i, title) in tab_titles().iter().enumerate() {
+        let end = x + title.width() as u16 + 2;
+        if (x..end).contains(&column) {
+            return Some(i);
+        }
+        x = end + 1;
+    }
+    None
+}
+
+/// The key of the `[key] action` hint at `column` of the centered footer
+/// drawn in `area`.
+fn clicked_hint(hints: &str, area: Rect, column: u16) -> Option<KeyCode> {
+    let inner = area.inner(Margin::new(1, 1));
+    let mut x = inner.x + inner.width.saturating_sub(hints.width() as u16) / 2;
+    for hint in hints.split(" | ") {
+        let end = x + hint.width() as u16;
+        if (x..end).contains(&column) {
+            return hint_key(hint);
+        }
+        x = end + 3;
+    }
+    None
+}
+
+fn hint_key(hint: &str) -> Option<KeyCode> {
+    let (key, _) = hint.strip_prefix('[')?.split_once(']')?;
+    match key {
+        "Esc" => Some(KeyCode::Esc),
+        "Enter" => Some(KeyCode::Enter),
+        // Hints like [j/k] stand for more than one key and don't parse.
+        _ => parse_key(key),
+    }
+}
+
+/// The header, content and footer rows of the screen.
+fn screen_layout(area: Rect) -> Rc<[Rect]> {
+    Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3), // Header
             Constraint::Min(0),    // Content
             Constraint::Length(3), // Footer
+            Constraint::Length(1), // Status bar
+        ])
+        .split(area)
+}
+
+/// The channel list and, when shown, the details pane and the event feed
+/// of the channels tab.
+fn channels_layout(
+    area: Rect,
+    show_events: bool,
+    layout: &PaneLayout,
+) -> (Rect, Option<Rect>, Option<Rect>) {
+    let (area, feed) = if show_events {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Percentage(35)])
+            .split(area);
+        (rows[0], Some(rows[1]))
+    } else {
+        (area, None)
+    };
+
+    if !layout.details {
+        return (area, None, feed);
+    }
+    let split = layout.split.clamp(
+        *PaneLayout::SPLIT_RANGE.start(),
+        *PaneLayout::SPLIT_RANGE.end(),
+    );
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(split),
+            Constraint::Percentage(100 - split),
         ])
-        .split(f.area());
+        .split(area);
+    (chunks[0], Some(chunks[1]), feed)
+}
+
+/// The settings list and the box below it.
+fn settings_layout(area: Rect) -> Rc<[Rect]> {
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(4)])
+        .split(area)
+}
+
+fn tab_titles() -> [String; 3] {
+    [
+        t!("tui-tab-channels"),
+        t!("tui-tab-dashboard"),
+        t!("tui-tab-settings"),
+    ]
+}
 
-    let header = Tabs::new(vec![Line::from("Channels"), Line::from("Settings")])
-        .block(Block::default().borders(Borders::ALL).title(" Stitch TUI "))
+fn ui(f: &mut Frame, app: &App) {
+    let chunks = screen_layout(f.area());
+
+    let header = Tabs::new(tab_titles().map(Line::from))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(header_title(app)),
+        )
         .select(app.selected_tab)
         .style(Style::default().fg(Color::White))
         .highlight_style(
@@ -337,28 +2009,28 @@ fn ui(f: &mut Frame, app: &App) {
     f.render_widget(header, chunks[0]);
 
     match app.selected_tab {
-        0 => render_channels_tab(f, app, chunks[1]),
-        1 => render_settings_tab(f, app, chunks[1]),
+        CHANNELS_TAB => render_channels_tab(f, app, chunks[1]),
+        DASHBOARD_TAB => render_dashboard_tab(f, app, chunks[1]),
+        SETTINGS_TAB => render_settings_tab(f, app, chunks[1]),
         _ => {}
     }
 
     let footer = match app.input_mode {
-        InputMode::AddingChannel => Paragraph::new(format!("Add channel: {}_", app.input_buffer))
-            .style(Style::default().fg(Color::Yellow))
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title(" Press Enter to add, Esc to cancel "),
-            ),
+        InputMode::AddingChannel => {
+            Paragraph::new(format!("{} {}_", t!("tui-add-channel"), app.input_buffer))
+                .style(Style::default().fg(Color::Yellow))
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(format!(" {} ", t!("tui-add-hint"))),
+                )
+        }
         InputMode::ConfirmingDelete => {
             if let Some(i) = app.channel_list_state.selected() {
                 if let Some(channel) = app.filtered_channels().get(i) {
-                    Paragraph::new(format!(
-                        "Delete '{}'? Press Y to confirm, N to cancel",
-                        channel.name
-                    ))
-                    .style(Style::default().fg(Color::Red))
-                    .block(Block::default().borders(Borders::ALL))
+                    Paragraph::new(t!("tui-delete-confirm", name = channel.name.as_str()))
+                        .style(Style::default().fg(Color::Red))
+                        .block(Block::default().borders(Borders::ALL))
                 } else {
                     render_help_footer(app)
                 }
@@ -366,72 +2038,226 @@ fn ui(f: &mut Frame, app: &App) {
                 render_help_footer(app)
             }
         }
+        InputMode::EditingSetting => {
+            let label = app
+                .selected_setting()
+                .map(setting_label)
+                .unwrap_or_default();
+            Paragraph::new(format!("{} {}_", label, app.input_buffer))
+                .style(Style::default().fg(Color::Yellow))
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(format!(" {} ", t!("tui-settings-hint"))),
+                )
+        }
+        InputMode::ViewingHistory => Paragraph::new(history_footer_text(&app.settings.keys))
+            .style(Style::default().fg(Color::DarkGray))
+            .block(Block::default().borders(Borders::ALL))
+            .alignment(Alignment::Center),
         InputMode::Normal => {
             if app.is_searching {
-                Paragraph::new(format!("Search: {}_", app.search_query))
+                let prompt = match (app.server_search, app.is_pending(&Operation::Search)) {
+                    (false, _) => t!("tui-search"),
+                    (true, false) => t!("tui-search-server"),
+                    (true, true) => format!("{} {}", spinner(), t!("tui-search-server")),
+                };
+                Paragraph::new(format!("{} {}_", prompt, app.search_query))
                     .style(Style::default().fg(Color::Yellow))
-                    .block(Block::default().borders(Borders::ALL))
-            } else if let Some((msg, time)) = &app.status_message {
-                if time.elapsed() < Duration::from_secs(5) {
-                    Paragraph::new(msg.as_str())
-                        .style(Style::default().fg(Color::Green))
-                        .block(Block::default().borders(Borders::ALL))
-                } else {
-                    render_help_footer(app)
-                }
+                    .block(
+                        Block::default()
+                            .borders(Borders::ALL)
+                            .title(format!(" {} ", t!("tui-search-hint"))),
+                    )
             } else {
                 render_help_footer(app)
             }
         }
     };
     f.render_widget(footer, chunks[2]);
+    f.render_widget(status_bar(app), chunks[3]);
+
+    render_toasts(f, app, chunks[1]);
 
     if app.show_help {
-        render_help_overlay(f);
+        render_help_overlay(f, app);
     }
 }
 
-fn render_help_footer(app: &App) -> Paragraph<'static> {
-    let help_text = if app.loading {
-        "Loading..."
-    } else {
-        "[q] Quit | [Tab] Switch tabs | [/] Search | [?] Help | [r] Refresh"
+/// Stacks the toasts in the bottom right corner of `area`, newest at the
+/// bottom, leaving out the oldest if they don't fit.
+fn render_toasts(f: &mut Frame, app: &App, area: Rect) {
+    let width = area.width.min(50);
+    let mut bottom = area.bottom();
+    for toast in app.toasts.iter().rev() {
+        if bottom < area.y + 3 {
+            break;
+        }
+        bottom -= 3;
+        let rect = Rect::new(area.right() - width, bottom, width, 3);
+        let style = Style::default().fg(toast.level.color());
+        let paragraph = Paragraph::new(format!("{} {}", toast.level.symbol(), toast.message))
+            .style(style)
+            .block(Block::default().borders(Borders::ALL).border_style(style));
+        f.render_widget(Clear, rect);
+        f.render_widget(paragraph, rect);
+    }
+}
+
+/// The connection's health, the server, its version and when the channels
+/// were last loaded, which is flagged as stale while the server is away.
+fn status_bar(app: &App) -> Paragraph<'static> {
+    let (health, color) = match &app.connection {
+        Connection::Connecting => (
+            format!("{} {}", spinner(), t!("tui-status-connecting")),
+            Color::Yellow,
+        ),
+        Connection::Connected => (format!("● {}", t!("tui-status-connected")), Color::Green),
+        Connection::Lost { .. } if app.is_pending(&Operation::ServerInfo) => (
+            format!("{} {}", spinner(), t!("tui-status-reconnecting")),
+            Color::Yellow,
+        ),
+        Connection::Lost {
+            attempts,
+            retry_at,
+            error,
+        } => (
+            format!(
+                "● {}",
+                t!(
+                    "tui-status-lost",
+                    error = error.as_str(),
+                    seconds = retry_at.saturating_duration_since(Instant::now()).as_secs() + 1,
+                    attempts = *attempts
+                )
+            ),
+            Color::Red,
+        ),
     };
 
-    Paragraph::new(help_text)
+    let mut server = app.ctx.server.clone();
+    if let Some(version) = &app.server_version {
+        server = format!("{server} (v{version})");
+    }
+    let loaded = match app.last_loaded {
+        Some(time) => {
+            let time = time.format("%H:%M:%S").to_string();
+            match app.connection {
+                Connection::Lost { .. } => t!("tui-status-stale", time = time),
+                _ => t!("tui-status-loaded", time = time),
+            }
+        }
+        None => t!("tui-status-not-loaded"),
+    };
+    let separator = Span::styled(" │ ", Style::default().fg(Color::DarkGray));
+    Paragraph::new(Line::from(vec![
+        Span::styled(format!(" {health}"), Style::default().fg(color)),
+        separator.clone(),
+        Span::raw(server),
+        separator,
+        Span::raw(loaded),
+    ]))
+}
+
+/// The app's name, followed by the channels being tracked or untracked.
+fn header_title(app: &App) -> String {
+    let mut running: Vec<String> = app
+        .pending
+        .iter()
+        .filter_map(|operation| match operation {
+            Operation::Track(name) => Some(t!("tui-tracking", name = name.as_str())),
+            Operation::Untrack(name) => Some(t!("tui-untracking", name = name.as_str())),
+            _ => None,
+        })
+        .collect();
+    if running.is_empty() {
+        return format!(" {} ", t!("tui-title"));
+    }
+    running.sort();
+    format!(
+        " {} · {} {} ",
+        t!("tui-title"),
+        spinner(),
+        running.join(", ")
+    )
+}
+
+fn render_help_footer(app: &App) -> Paragraph<'static> {
+    Paragraph::new(footer_text(&app.settings.keys))
         .style(Style::default().fg(Color::DarkGray))
         .block(Block::default().borders(Borders::ALL))
         .alignment(Alignment::Center)
 }
 
 fn render_channels_tab(f: &mut Frame, app: &App, area: Rect) {
-    let chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
-        .split(area);
+    let layout = &app.pane_layout();
+    let (list_area, details_area, feed_area) = channels_layout(area, app.show_events, layout);
+    if let Some(feed_area) = feed_area {
+        render_event_feed(f, app, feed_area);
+    }
 
     let channels = app.filtered_channels();
-    let items: Vec<ListItem> = channels
+    // Only the rows in view become list items, which keeps drawing cheap for
+    // thousands of channels.
+    let selected = app.channel_list_state.selected();
+    let height = list_area.height.saturating_sub(2) as usize;
+    let window = list_window(selected, channels.len(), height);
+    let items: Vec<ListItem> = channels[window.clone()]
         .iter()
         .map(|c| {
-            let content = Line::from(vec![
-                Span::raw(&c.name),
-                Span::raw(" "),
-                Span::styled(
-                    format!("(ID: {})", c.id),
-                    Style::default().fg(Color::DarkGray),
-                ),
-            ]);
+            let live = if c.is_live {
+                Span::styled("● ", Style::default().fg(Color::Red))
+            } else {
+                Span::raw("  ")
+            };
+            let mut spans = vec![live, Span::raw(&c.name), Span::raw(" ")];
+            if layout.compact {
+                if c.is_live && !c.category.is_empty() {
+                    spans.push(Span::styled(
+                        c.category.as_str(),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                return ListItem::new(Line::from(spans));
+            }
+            if let Some(alias) = app.alias(&c.name) {
+                spans.push(Span::styled(
+                    format!("({}) ", alias),
+                    Style::default().fg(Color::Cyan),
+                ));
+            }
+            spans.push(Span::styled(
+                format!("({} {})", t!("tui-label-id"), c.id),
+                Style::default().fg(Color::DarkGray),
+            ));
+            let content = Line::from(spans);
             ListItem::new(content)
         })
         .collect();
 
-    let total_count = app.channels.len();
+    // A `+` marks that more channels are still to be fetched.
+    let more = if app.next_page_token.is_some() {
+        "+"
+    } else {
+        ""
+    };
+    let total_count = format!("{}{}", app.channels.len(), more);
     let filtered_count = channels.len();
     let title_text = if app.search_query.is_empty() {
-        format!(" Channels ({}) ", total_count)
+        format!(" {} ({}) ", t!("tui-tab-channels"), total_count)
+    } else {
+        format!(
+            " {} ({}/{}) ",
+            t!("tui-tab-channels"),
+            filtered_count,
+            total_count
+        )
+    };
+    let title_text = if app.is_pending(&Operation::Refresh) || app.is_pending(&Operation::LoadMore)
+    {
+        format!("{}{} ", title_text, spinner())
     } else {
-        format!(" Channels ({}/{}) ", filtered_count, total_count)
+        title_text
     };
 
     let channels_list = List::new(items)
@@ -452,126 +2278,775 @@ fn render_channels_tab(f: &mut Frame, app: &App, area: Rect) {
         )
         .highlight_symbol(">> ");
 
-    f.render_stateful_widget(
-        channels_list,
-        chunks[0],
-        &mut app.channel_list_state.clone(),
-    );
+    let mut state = ListState::default().with_selected(selected.map(|s| s - window.start));
+    f.render_stateful_widget(channels_list, list_area, &mut state);
 
-    if let Some(selected) = app.channel_list_state.selected() {
+    let Some(details_area) = details_area else {
+        return;
+    };
+    if let Some(history) = &app.history {
+        let loading = app.is_pending(&Operation::History(history.channel.clone()));
+        render_history(f, history, loading, details_area);
+    } else if let Some(selected) = app.channel_list_state.selected() {
         if let Some(channel) = channels.get(selected) {
-            render_channel_details(f, channel, chunks[1]);
+            // The avatar itself is drawn once ratatui is done; only the
+            // placeholder is part of the frame.
+            let avatar_shown = app.graphics.is_some()
+                && app
+                    .avatars
+                    .get(&channel.profile_image_url)
+                    .is_some_and(Option::is_some);
+            render_channel_details(
+                f,
+                channel,
+                app.alias(&channel.name),
+                app.extras.get(&channel.name),
+                avatar_shown,
+                details_area,
+            );
         }
     }
 }
 
-fn render_channel_details(f: &mut Frame, channel: &Channel, area: Rect) {
-    let details = vec![
+fn render_history(f: &mut Frame, history: &HistoryView, loading: bool, area: Rect) {
+    if history.streams.is_empty() {
+        let text = match loading {
+            true => format!("{} {}", spinner(), t!("tui-loading")),
+            false => t!("tui-history-empty"),
+        };
+        let empty =
+            Paragraph::new(text).block(Block::default().borders(Borders::ALL).title(format!(
+                " {} ",
+                t!("tui-history-title", name = history.channel.as_str())
+            )));
+        f.render_widget(empty, area);
+        return;
+    }
+
+    let now = chrono::Utc::now();
+    let rows: Vec<Row> = history
+        .streams
+        .iter()
+        .map(|stream| {
+            let record = StreamRecord::new(stream, now);
+            let date = record
+                .started_at
+                .map(|dt| {
+                    dt.with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M")
+                        .to_string()
+                })
+                .unwrap_or_default();
+            let duration = record
+                .duration_seconds
+                .map(format_seconds)
+                .unwrap_or_default();
+            let duration = match stream.ended_at {
+                None => Cell::from(t!("duration-live", duration = duration))
+                    .style(Style::default().fg(Color::Red)),
+                Some(_) => Cell::from(duration),
+            };
+            Row::new(vec![
+                Cell::from(date),
+                duration,
+                Cell::from(stream.title.clone()),
+                Cell::from(stream.categories.join(", ")),
+            ])
+        })
+        .collect();
+
+    let title = format!(
+        " {} ({}{}) ",
+        t!("tui-history-title", name = history.channel.as_str()),
+        history.streams.len(),
+        match (loading, history.exhausted) {
+            (true, _) => spinner(),
+            (false, true) => "",
+            (false, false) => "+",
+        }
+    );
+    let header =
+        Row::new(["Date", "Duration", "Title", "Categories"].map(|h| Cell::from(i18n::column(h))))
+            .style(Style::default().add_modifier(Modifier::BOLD));
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(16),
+            Constraint::Length(12),
+            Constraint::Percentage(50),
+            Constraint::Percentage(50),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .title_style(
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+    )
+    .row_highlight_style(
+        Style::default()
+            .bg(Color::DarkGray)
+            .add_modifier(Modifier::BOLD),
+    );
+
+    f.render_stateful_widget(table, area, &mut history.state.clone());
+}
+
+fn render_channel_details(
+    f: &mut Frame,
+    channel: &Channel,
+    alias: Option<&str>,
+    extras: Option<&ChannelExtras>,
+    avatar_shown: bool,
+    area: Rect,
+) {
+    let avatar = avatar_area(area);
+    // Charts only once there are streams to chart and room left for text.
+    let (area, activity_area) = match extras {
+        Some(extras) if !extras.recent.is_empty() && area.height >= 20 => {
+            let rows = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(0), Constraint::Length(8)])
+                .split(area);
+            (rows[0], Some(rows[1]))
+        }
+        _ => (area, None),
+    };
+    let label = |text: String| {
+        Span::styled(
+            format!("{} ", text),
+            Style::default().add_modifier(Modifier::BOLD),
+        )
+    };
+    let date = |ts: Option<&prost_types::Timestamp>| {
+        ts.and_then(to_datetime)
+            .map(|t| {
+                t.with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d")
+                    .to_string()
+            })
+            .unwrap_or_default()
+    };
+    let mut details = vec![
         Line::from(vec![
-            Span::styled("ID: ", Style::default().add_modifier(Modifier::BOLD)),
+            label(t!("tui-label-id")),
             Span::raw(channel.id.to_string()),
         ]),
+        Line::from(vec![label(t!("tui-label-name")), Span::raw(&channel.name)]),
         Line::from(vec![
-            Span::styled("Name: ", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(&channel.name),
+            label(t!("tui-label-display-name")),
+            Span::raw(&channel.display_name),
         ]),
     ];
+    if let Some(alias) = alias {
+        details.push(Line::from(vec![
+            label(t!("tui-label-alias")),
+            Span::styled(alias, Style::default().fg(Color::Cyan)),
+        ]));
+    }
+    details.push(Line::from(vec![
+        label(t!("tui-label-twitch-id")),
+        Span::raw(&channel.twitch_id),
+    ]));
+    details.push(Line::from(vec![
+        label(t!("tui-label-tracked-since")),
+        Span::raw(date(channel.created_at.as_ref())),
+    ]));
+    details.push(Line::from(""));
+
+    let status = match (channel.is_live, channel.paused) {
+        (true, _) => Span::styled(t!("live-badge"), Style::default().fg(Color::Red)),
+        (false, true) => Span::styled(t!("tui-paused"), Style::default().fg(Color::Yellow)),
+        (false, false) => Span::styled(t!("status-offline"), Style::default().fg(Color::DarkGray)),
+    };
+    details.push(Line::from(vec![label(t!("tui-label-status")), status]));
+    if !channel.broken_reason.is_empty() {
+        details.push(Line::from(Span::styled(
+            t!("tui-broken", reason = channel.broken_reason.as_str()),
+            Style::default().fg(Color::Red),
+        )));
+    }
+    if channel.is_live {
+        details.push(Line::from(vec![
+            label(t!("label-category")),
+            Span::raw(&channel.category),
+        ]));
+        if let Some(uptime) = &channel.uptime {
+            details.push(Line::from(vec![
+                label(t!("label-uptime")),
+                Span::raw(format_uptime(uptime)),
+            ]));
+        }
+    }
+
+    match extras {
+        None => details.push(Line::from(Span::styled(
+            format!("{} {}", spinner(), t!("tui-loading")),
+            Style::default().fg(Color::DarkGray),
+        ))),
+        Some(extras) => {
+            if let Some(stream) = &extras.last_stream {
+                let title = match channel.is_live {
+                    true => t!("label-title"),
+                    false => t!("tui-label-last-title"),
+                };
+                details.push(Line::from(vec![
+                    label(title),
+                    Span::raw(stream.title.clone()),
+                ]));
+                if !channel.is_live {
+                    let record = StreamRecord::new(stream, chrono::Utc::now());
+                    details.push(Line::from(vec![
+                        label(t!("tui-label-last-stream")),
+                        Span::raw(t!(
+                            "tui-last-stream",
+                            date = date(stream.started_at.as_ref()),
+                            duration = record
+                                .duration_seconds
+                                .map(format_seconds)
+                                .unwrap_or_default()
+                        )),
+                    ]));
+                }
+            }
+            details.push(Line::from(vec![
+                label(t!("tui-label-total")),
+                Span::raw(t!(
+                    "tui-total",
+                    hours = format!("{:.1}", extras.total_seconds as f64 / 3600.0),
+                    streams = extras.total_streams
+                )),
+            ]));
+        }
+    }
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" {} ", t!("tui-details")))
+        .title_style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        );
+    let mut text_area = block.inner(area);
+    f.render_widget(block, area);
+    if let Some(avatar) = avatar {
+        text_area.width -= avatar.width + 1;
+        if !avatar_shown {
+            render_avatar_placeholder(f, channel, avatar);
+        }
+    }
+    let paragraph = Paragraph::new(details).wrap(Wrap { trim: true });
+    f.render_widget(paragraph, text_area);
+    if let (Some(extras), Some(activity_area)) = (extras, activity_area) {
+        render_activity(f, extras, activity_area);
+    }
+}
 
-    let all_lines = details;
+/// The top right corner of a details pane drawn in `area` that's kept for
+/// the profile image, unless the pane is too small to spare it.
+fn avatar_area(area: Rect) -> Option<Rect> {
+    let inner = area.inner(Margin::new(1, 1));
+    let (cols, rows) = AVATAR_CELLS;
+    (inner.width >= cols * 4 && inner.height >= rows * 2)
+        .then(|| Rect::new(inner.right() - cols, inner.y, cols, rows))
+}
 
-    let paragraph = Paragraph::new(all_lines)
+/// A box with the channel's initial where its profile image would go.
+fn render_avatar_placeholder(f: &mut Frame, channel: &Channel, area: Rect) {
+    let name = match channel.display_name.is_empty() {
+        true => &channel.name,
+        false => &channel.display_name,
+    };
+    let initial = name
+        .chars()
+        .next()
+        .unwrap_or('?')
+        .to_uppercase()
+        .to_string();
+    let placeholder = Paragraph::new(initial)
+        .style(Style::default().fg(Color::DarkGray))
+        .alignment(Alignment::Center)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(" Channel Details ")
-                .title_style(
-                    Style::default()
-                        .fg(Color::Cyan)
-                        .add_modifier(Modifier::BOLD),
+                .padding(Padding::top(area.height.saturating_sub(3) / 2)),
+        );
+    f.render_widget(placeholder, area);
+}
+
+/// Durations of the latest streams as a sparkline, above a heatline of the
+/// time streamed per weekday.
+fn render_activity(f: &mut Frame, extras: &ChannelExtras, area: Rect) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(4), Constraint::Length(4)])
+        .split(area);
+    let style = Style::default().fg(Color::Magenta);
+
+    let sparkline = Sparkline::default()
+        .block(Block::default().borders(Borders::ALL).title(format!(
+            " {} ",
+            t!("tui-recent-streams", count = extras.recent.len())
+        )))
+        .data(&extras.recent)
+        .style(style);
+    f.render_widget(sparkline, rows[0]);
+
+    const SHADES: [&str; 5] = ["·", "░", "▒", "▓", "█"];
+    let max = extras.weekdays.iter().copied().max().unwrap_or(0);
+    let days = t!("tui-weekdays");
+    let (labels, cells): (Vec<_>, Vec<_>) = days
+        .split_whitespace()
+        .zip(&extras.weekdays)
+        .map(|(day, &seconds)| {
+            // Any time at all shows up as at least the lightest shade.
+            let shade = match max {
+                0 => 0,
+                max => (seconds * 4).div_ceil(max) as usize,
+            };
+            (
+                Span::raw(chart::pad(day, 4)),
+                Span::styled(format!("{} ", SHADES[shade].repeat(3)), style),
+            )
+        })
+        .unzip();
+    let heatline = Paragraph::new(vec![Line::from(labels), Line::from(cells)]).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" {} ", t!("tui-weekday-activity"))),
+    );
+    f.render_widget(heatline, rows[1]);
+}
+
+fn render_event_feed(f: &mut Frame, app: &App, area: Rect) {
+    let labels = [t!("event-online"), t!("event-offline"), t!("event-update")];
+    let width = labels.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+    let [online, offline, update] = labels.map(|l| chart::pad(&l, width));
+
+    // Newest at the bottom, like `stitch watch`.
+    let visible = area.height.saturating_sub(2) as usize;
+    let lines: Vec<Line> = app
+        .events
+        .iter()
+        .skip(app.events.len().saturating_sub(visible))
+        .map(|event| {
+            let time = event
+                .timestamp
+                .as_ref()
+                .and_then(to_datetime)
+                .unwrap_or_else(chrono::Utc::now)
+                .with_timezone(&chrono::Local)
+                .format("%H:%M:%S");
+            let (label, style) = match event.kind() {
+                EventKind::Online => (
+                    &online,
+                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
                 ),
-        )
-        .wrap(Wrap { trim: true });
+                EventKind::Offline => (&offline, Style::default().fg(Color::DarkGray)),
+                EventKind::Update | EventKind::Unspecified => {
+                    (&update, Style::default().fg(Color::Yellow))
+                }
+            };
+            let mut spans = vec![
+                Span::styled(format!("{} ", time), Style::default().fg(Color::DarkGray)),
+                Span::styled(label.clone(), style),
+                Span::raw(" "),
+                Span::styled(
+                    event.display_name.clone(),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+            ];
+            if event.kind() != EventKind::Offline {
+                spans.push(Span::raw(format!(
+                    " — {} [{}]",
+                    event.title, event.category
+                )));
+            }
+            Line::from(spans)
+        })
+        .collect();
+
+    let paragraph = if lines.is_empty() {
+        let waiting = match app.event_feed {
+            Some(_) => t!("tui-events-waiting"),
+            None => t!("tui-events-disconnected"),
+        };
+        Paragraph::new(Line::from(Span::styled(
+            waiting,
+            Style::default().fg(Color::DarkGray),
+        )))
+    } else {
+        Paragraph::new(lines)
+    };
+    let paragraph = paragraph.block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" {} ", t!("tui-events")))
+            .title_style(
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+    );
 
     f.render_widget(paragraph, area);
 }
 
-fn render_settings_tab(f: &mut Frame, _app: &App, area: Rect) {
-    let text = vec![
-        Line::from("Settings management coming soon!"),
-        Line::from(""),
-        Line::from("Configuration file: ~/.config/stitch/config.toml"),
+fn render_dashboard_tab(f: &mut Frame, app: &App, area: Rect) {
+    let dashboard = match &app.dashboard {
+        Some(Ok(dashboard)) => dashboard,
+        other => {
+            let text = match other {
+                Some(Err(error)) => t!("tui-dashboard-failed", error = error.as_str()),
+                _ => format!("{} {}", spinner(), t!("tui-loading")),
+            };
+            let paragraph = Paragraph::new(text).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!(" {} ", t!("tui-tab-dashboard"))),
+            );
+            f.render_widget(paragraph, area);
+            return;
+        }
+    };
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .split(area);
+    let cards = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Ratio(1, 3); 3])
+        .split(rows[0]);
+    let numbers = [
+        (t!("tui-dashboard-tracked"), dashboard.tracked.to_string()),
+        (t!("tui-dashboard-live"), dashboard.live.to_string()),
+        (
+            t!("tui-dashboard-week"),
+            format_seconds(dashboard.week_seconds),
+        ),
     ];
+    for ((label, value), area) in numbers.into_iter().zip(cards.iter()) {
+        let card = Paragraph::new(value)
+            .style(Style::default().add_modifier(Modifier::BOLD))
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!(" {label} ")),
+            );
+        f.render_widget(card, *area);
+    }
 
-    let paragraph = Paragraph::new(text)
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(rows[1]);
+    let categories: Vec<(String, i64)> = dashboard
+        .categories
+        .iter()
+        .map(|c| (c.category.clone(), c.duration.map_or(0, |d| d.seconds)))
+        .collect();
+    render_ranking(
+        f,
+        t!("tui-dashboard-categories"),
+        &categories,
+        Color::Magenta,
+        columns[0],
+    );
+    render_ranking(
+        f,
+        t!("tui-dashboard-streamers"),
+        &dashboard.streamers,
+        Color::Cyan,
+        columns[1],
+    );
+}
+
+/// A box of `rows` as names with a bar and time each, scaled to the first.
+fn render_ranking(f: &mut Frame, title: String, rows: &[(String, i64)], color: Color, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" {title} "));
+    if rows.is_empty() {
+        let empty = Paragraph::new(t!("tui-dashboard-empty"))
+            .style(Style::default().fg(Color::DarkGray))
+            .block(block);
+        f.render_widget(empty, area);
+        return;
+    }
+
+    let width = rows.iter().map(|(name, _)| name.width()).max().unwrap_or(0);
+    let times: Vec<String> = rows.iter().map(|&(_, s)| format_seconds(s)).collect();
+    let time_width = times.iter().map(|t| t.width()).max().unwrap_or(0);
+    let max = rows[0].1 as f64;
+    // The bar gets what's left of the row after the name, the time and the
+    // borders.
+    let bar_width = (area.width as usize).saturating_sub(width + time_width + 6);
+    let lines: Vec<Line> = rows
+        .iter()
+        .zip(&times)
+        .map(|((name, seconds), time)| {
+            Line::from(vec![
+                Span::raw(format!("{} ", chart::pad(name, width))),
+                Span::styled(
+                    chart::pad(&chart::bar(*seconds as f64, max, bar_width), bar_width),
+                    Style::default().fg(color),
+                ),
+                Span::raw(format!(" {time}")),
+            ])
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn setting_label(key: &str) -> String {
+    match key {
+        "server" => t!("tui-setting-server"),
+        "output_format" => t!("tui-setting-output-format"),
+        "color" => t!("tui-setting-color"),
+        "refresh_interval" => t!("tui-setting-refresh-interval"),
+        _ => key.to_string(),
+    }
+}
+
+fn render_settings_tab(f: &mut Frame, app: &App, area: Rect) {
+    let chunks = settings_layout(area);
+
+    let width = SETTINGS
+        .iter()
+        .map(|key| setting_label(key).chars().count())
+        .max()
+        .unwrap_or(0);
+    let items: Vec<ListItem> = SETTINGS
+        .iter()
+        .map(|key| {
+            let value = app.settings.get(key).unwrap_or_default();
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{:<width$}  ", setting_label(key)),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(value, Style::default().fg(Color::Cyan)),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(" Settings ")
+                .title(format!(" {} ", t!("tui-tab-settings")))
                 .title_style(
                     Style::default()
                         .fg(Color::Cyan)
                         .add_modifier(Modifier::BOLD),
                 ),
         )
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(">> ");
+
+    f.render_stateful_widget(list, chunks[0], &mut app.settings_state.clone());
+
+    let text = vec![
+        Line::from(t!(
+            "tui-settings-file",
+            path = app.config_path.display().to_string()
+        )),
+        Line::from(Span::styled(
+            t!("tui-settings-help"),
+            Style::default().fg(Color::DarkGray),
+        )),
+    ];
+    let paragraph = Paragraph::new(text)
+        .block(Block::default().borders(Borders::ALL))
         .wrap(Wrap { trim: true });
 
-    f.render_widget(paragraph, area);
+    f.render_widget(paragraph, chunks[1]);
 }
 
-fn render_help_overlay(f: &mut Frame) {
-    let area = centered_rect(60, 60, f.area());
-    f.render_widget(Clear, area);
+/// A key listed in the help overlay, with its section and description as
+/// message ids.
+struct KeyHelp {
+    section: &'static str,
+    keys: String,
+    description: &'static str,
+}
 
-    let help_text = vec![
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "Navigation",
-            Style::default().add_modifier(Modifier::BOLD),
-        )]),
-        Line::from("  ↑/k     - Move up"),
-        Line::from("  ↓/j     - Move down"),
-        Line::from("  Tab     - Switch tabs"),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "Channel Management",
-            Style::default().add_modifier(Modifier::BOLD),
-        )]),
-        Line::from("  a       - Add new channel"),
-        Line::from("  d       - Delete selected channel"),
-        Line::from("  r       - Refresh channel list"),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "Search",
-            Style::default().add_modifier(Modifier::BOLD),
-        )]),
-        Line::from("  /       - Start search"),
-        Line::from("  Esc     - Cancel search"),
-        Line::from("  Enter   - Confirm search"),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "General",
-            Style::default().add_modifier(Modifier::BOLD),
-        )]),
-        Line::from("  ?       - Toggle this help"),
-        Line::from("  q       - Quit application"),
-        Line::from(""),
-    ];
+/// Help sections in the order they're listed.
+const HELP_SECTIONS: &[&str] = &[
+    "tui-help-navigation",
+    "tui-help-channels",
+    "tui-help-layout",
+    "tui-tab-settings",
+    "tui-help-search",
+    "tui-help-general",
+];
 
-    let help = Paragraph::new(help_text)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(" Help ")
-                .title_style(
-                    Style::default()
-                        .fg(Color::Yellow)
-                        .add_modifier(Modifier::BOLD),
-                )
-                .border_style(Style::default().fg(Color::Yellow)),
+/// Keys that can't be remapped, as section, keys and description.
+const FIXED_HELP: &[(&str, &str, &str)] = &[
+    ("tui-help-navigation", "↑/k", "tui-help-up"),
+    ("tui-help-navigation", "↓/j", "tui-help-down"),
+    ("tui-help-navigation", "PgUp/PgDn", "tui-help-page"),
+    ("tui-help-navigation", "Home/End", "tui-help-ends"),
+    ("tui-help-channels", "o", "tui-help-open"),
+    ("tui-help-channels", "y / Y", "tui-help-copy"),
+    ("tui-help-channels", "h", "tui-help-history"),
+    ("tui-help-channels", "e", "tui-help-events"),
+    ("tui-help-channels", "u", "tui-help-undo"),
+    ("tui-help-layout", "v", "tui-help-layout-details"),
+    ("tui-help-layout", "< / >", "tui-help-layout-split"),
+    ("tui-help-layout", "c", "tui-help-layout-compact"),
+    ("tui-tab-settings", "Enter", "tui-help-settings-edit"),
+    ("tui-help-search", "Esc", "tui-help-search-cancel"),
+    ("tui-help-search", "Enter", "tui-help-search-confirm"),
+    ("tui-help-search", "Ctrl-S", "tui-help-search-server"),
+    ("tui-help-general", "?", "tui-help-toggle"),
+];
+
+/// The help section and description of a remappable action.
+fn action_help(action: KeyAction) -> (&'static str, &'static str) {
+    match action {
+        KeyAction::Quit => ("tui-help-general", "tui-help-quit"),
+        KeyAction::Add => ("tui-help-channels", "tui-help-add"),
+        KeyAction::Delete => ("tui-help-channels", "tui-help-delete"),
+        KeyAction::Refresh => ("tui-help-channels", "tui-help-refresh"),
+        KeyAction::Search => ("tui-help-search", "tui-help-search-start"),
+        KeyAction::Tabs => ("tui-help-navigation", "tui-help-tabs"),
+    }
+}
+
+/// Every key of the normal mode, the remappable ones as they're bound,
+/// grouped by section.
+fn keymap(keys: &KeyBindings) -> Vec<KeyHelp> {
+    let bound = keys.bindings().into_iter().map(|(action, _, key)| {
+        let (section, description) = action_help(action);
+        KeyHelp {
+            section,
+            keys: key.to_string(),
+            description,
+        }
+    });
+    let fixed = FIXED_HELP
+        .iter()
+        .map(|&(section, keys, description)| KeyHelp {
+            section,
+            keys: keys.to_string(),
+            description,
+        });
+    let mut keymap: Vec<KeyHelp> = bound.chain(fixed).collect();
+    keymap.sort_by_key(|help| HELP_SECTIONS.iter().position(|&s| s == help.section));
+    keymap
+}
+
+/// The normal footer's hints, naming the keys as they're bound.
+fn footer_text(keys: &KeyBindings) -> String {
+    t!(
+        "tui-footer",
+        quit = keys.quit.as_str(),
+        tabs = keys.tabs.as_str(),
+        search = keys.search.as_str(),
+        refresh = keys.refresh.as_str()
+    )
+}
+
+/// The stream history footer's hints, naming the quit key as it's bound.
+fn history_footer_text(keys: &KeyBindings) -> String {
+    t!("tui-history-footer", quit = keys.quit.as_str())
+}
+
+/// Where the help overlay's filter line and keys go on a screen of `area`.
+fn help_layout(area: Rect) -> Rc<[Rect]> {
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(0)])
+        .split(centered_rect(60, 60, area).inner(Margin::new(1, 1)))
+}
+
+/// The help overlay's lines for the keys matching `filter`, under the
+/// headings of their sections.
+fn help_lines(keys: &KeyBindings, filter: &str) -> Vec<Line<'static>> {
+    let filter = filter.to_lowercase();
+    let mut lines = Vec::new();
+    let mut section = None;
+    for help in keymap(keys) {
+        let description = i18n::message(help.description, None);
+        if !help.keys.to_lowercase().contains(&filter)
+            && !description.to_lowercase().contains(&filter)
+        {
+            continue;
+        }
+        if section != Some(help.section) {
+            if section.is_some() {
+                lines.push(Line::from(""));
+            }
+            section = Some(help.section);
+            lines.push(Line::from(Span::styled(
+                i18n::message(help.section, None),
+                Style::default().add_modifier(Modifier::BOLD),
+            )));
+        }
+        lines.push(Line::from(format!(
+            "  {}- {}",
+            chart::pad(&help.keys, 10),
+            description
+        )));
+    }
+    lines
+}
+
+fn render_help_overlay(f: &mut Frame, app: &App) {
+    let area = centered_rect(60, 60, f.area());
+    f.render_widget(Clear, area);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" {} ", t!("tui-help")))
+        .title_style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
         )
-        .alignment(Alignment::Left);
+        .border_style(Style::default().fg(Color::Yellow));
+    f.render_widget(block, area);
 
-    f.render_widget(help, area);
+    let chunks = help_layout(f.area());
+    let filter = match (app.help_filtering, app.help_filter.is_empty()) {
+        (false, true) => {
+            Paragraph::new(t!("tui-help-filter-hint")).style(Style::default().fg(Color::DarkGray))
+        }
+        (filtering, _) => Paragraph::new(format!(
+            "{} {}{}",
+            t!("tui-help-filter"),
+            app.help_filter,
+            if filtering { "_" } else { "" }
+        ))
+        .style(Style::default().fg(Color::Yellow)),
+    };
+    f.render_widget(filter, chunks[0]);
+
+    let lines = help_lines(&app.settings.keys, &app.help_filter);
+    if lines.is_empty() {
+        let empty =
+            Paragraph::new(t!("tui-help-no-match")).style(Style::default().fg(Color::DarkGray));
+        f.render_widget(empty, chunks[1]);
+        return;
+    }
+    // Keep the last line at the bottom if the screen grew since scrolling.
+    let scroll = app
+        .help_scroll
+        .min(lines.len().saturating_sub(chunks[1].height as usize));
+    let help = Paragraph::new(lines).scroll((scroll as u16, 0));
+    f.render_widget(help, chunks[1]);
 }
 
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
@@ -0,0 +1,42 @@
+use std::fs;
+use std::time::Duration;
+
+pub const CLIENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// How often ordinary commands compare versions with the server in passing.
+const CHECK_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Whether two versions share a major version. Versions that don't parse are
+/// given the benefit of the doubt.
+pub fn same_major(a: &str, b: &str) -> bool {
+    let major = |v: &str| {
+        v.trim_start_matches('v')
+            .split('.')
+            .next()?
+            .parse::<u64>()
+            .ok()
+    };
+    match (major(a), major(b)) {
+        (Some(a), Some(b)) => a == b,
+        _ => true,
+    }
+}
+
+/// Whether a passing version check is due, recording that one ran if so, so
+/// only the first command of the day pays for the extra call.
+pub fn check_due() -> bool {
+    let Some(dir) = dirs::cache_dir().map(|d| d.join("stitch")) else {
+        return false;
+    };
+    let path = dir.join("version-check");
+    let checked_recently = fs::metadata(&path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.elapsed().ok())
+        .is_some_and(|age| age < CHECK_INTERVAL);
+    if checked_recently {
+        return false;
+    }
+    let _ = fs::create_dir_all(&dir).and_then(|()| fs::write(&path, b""));
+    true
+}
@@ -1,4 +1,23 @@
+use std::{env, fs, path::PathBuf};
+
+const PROTO: &str = "proto/stitch/v1/service.proto";
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    tonic_prost_build::compile_protos("proto/stitch/v1/service.proto")?;
+    let out_dir = PathBuf::from(env::var("OUT_DIR")?);
+    tonic_prost_build::configure()
+        .file_descriptor_set_path(out_dir.join("stitch_descriptor.bin"))
+        .compile_protos(&[PROTO], &["proto"])?;
+
+    // FNV-1a rather than std's hasher, whose output may change between Rust
+    // releases: a client and server built from the same proto must agree.
+    let revision = fs::read(PROTO)?
+        .iter()
+        .fold(0xcbf29ce484222325u64, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+        });
+    println!(
+        "cargo:rustc-env=STITCH_PROTO_REVISION={:012x}",
+        revision >> 16
+    );
     Ok(())
 }
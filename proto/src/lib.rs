@@ -1,3 +1,25 @@
 pub mod stitch {
     tonic::include_proto!("stitch.v1");
+
+    /// Encoded descriptor set for `stitch.v1`, used to serve gRPC reflection.
+    pub const FILE_DESCRIPTOR_SET: &[u8] = tonic::include_file_descriptor_set!("stitch_descriptor");
+
+    /// Short hash of `service.proto`, to tell whether a client and server
+    /// speak the same revision of the API.
+    pub const PROTO_REVISION: &str = env!("STITCH_PROTO_REVISION");
+}
+
+/// `google.rpc.ErrorInfo` reasons attached to `stitch.v1` errors.
+pub mod errors {
+    pub const DOMAIN: &str = "stitch.v1";
+
+    pub const CHANNEL_NOT_TRACKED: &str = "CHANNEL_NOT_TRACKED";
+    pub const CHANNEL_ALREADY_TRACKED: &str = "CHANNEL_ALREADY_TRACKED";
+    pub const TWITCH_USER_NOT_FOUND: &str = "TWITCH_USER_NOT_FOUND";
+    pub const TWITCH_RATE_LIMITED: &str = "TWITCH_RATE_LIMITED";
+    pub const EVENTSUB_QUOTA_EXCEEDED: &str = "EVENTSUB_QUOTA_EXCEEDED";
+    pub const DATABASE_UNAVAILABLE: &str = "DATABASE_UNAVAILABLE";
+    pub const DISCORD_PERMISSION_DENIED: &str = "DISCORD_PERMISSION_DENIED";
+    pub const DISCORD_UNAVAILABLE: &str = "DISCORD_UNAVAILABLE";
+    pub const INTERNAL: &str = "INTERNAL";
 }
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_channels_display_name_trgm;
+DROP INDEX IF EXISTS idx_channels_name_trgm;
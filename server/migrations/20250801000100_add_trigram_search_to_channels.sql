@@ -0,0 +1,4 @@
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX IF NOT EXISTS idx_channels_name_trgm ON channels USING GIN (name gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_channels_display_name_trgm ON channels USING GIN (display_name gin_trgm_ops);
@@ -0,0 +1,2 @@
+ALTER TABLE channels
+ADD COLUMN IF NOT EXISTS paused BOOLEAN NOT NULL DEFAULT FALSE;
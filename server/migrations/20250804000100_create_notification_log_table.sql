@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS notification_log (
+    id BIGSERIAL PRIMARY KEY,
+    channel_id TEXT NOT NULL,
+    action TEXT NOT NULL,
+    message_id BIGINT,
+    error TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS notification_log_channel_id_created_at_idx
+    ON notification_log (channel_id, created_at DESC);
@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS channel_tags (
+    channel_id TEXT NOT NULL REFERENCES channels(channel_id) ON DELETE CASCADE,
+    tag TEXT NOT NULL,
+    PRIMARY KEY (channel_id, tag)
+);
+
+CREATE INDEX IF NOT EXISTS channel_tags_tag_idx ON channel_tags (tag);
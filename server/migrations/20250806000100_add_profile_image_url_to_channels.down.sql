@@ -0,0 +1,2 @@
+ALTER TABLE channels
+DROP COLUMN IF EXISTS profile_image_url;
@@ -0,0 +1,2 @@
+ALTER TABLE channels
+ADD COLUMN IF NOT EXISTS profile_image_url TEXT NOT NULL DEFAULT '';
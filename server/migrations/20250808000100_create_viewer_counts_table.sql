@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS viewer_counts (
+    stream_id TEXT NOT NULL REFERENCES streams(stream_id) ON DELETE CASCADE,
+    recorded_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    viewers INTEGER NOT NULL,
+    PRIMARY KEY (stream_id, recorded_at)
+);
@@ -0,0 +1,2 @@
+ALTER TABLE channel_settings
+ADD COLUMN IF NOT EXISTS mass_mention TEXT CHECK (mass_mention IN ('everyone', 'here'));
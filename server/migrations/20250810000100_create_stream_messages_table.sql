@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS stream_messages (
+    stream_id TEXT NOT NULL REFERENCES streams(stream_id) ON DELETE CASCADE,
+    discord_channel_id BIGINT NOT NULL,
+    message_id BIGINT NOT NULL,
+    PRIMARY KEY (stream_id, discord_channel_id)
+);
@@ -0,0 +1,3 @@
+ALTER TABLE channel_settings
+DROP COLUMN IF EXISTS push_priority,
+DROP COLUMN IF EXISTS push;
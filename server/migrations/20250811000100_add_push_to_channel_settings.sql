@@ -0,0 +1,3 @@
+ALTER TABLE channel_settings
+ADD COLUMN IF NOT EXISTS push BOOLEAN NOT NULL DEFAULT FALSE,
+ADD COLUMN IF NOT EXISTS push_priority SMALLINT CHECK (push_priority BETWEEN 1 AND 5);
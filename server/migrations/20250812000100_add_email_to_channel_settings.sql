@@ -0,0 +1,2 @@
+ALTER TABLE channel_settings
+ADD COLUMN IF NOT EXISTS email TEXT CHECK (email IN ('off', 'live', 'digest'));
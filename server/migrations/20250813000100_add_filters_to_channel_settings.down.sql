@@ -0,0 +1,3 @@
+ALTER TABLE channel_settings
+DROP COLUMN IF EXISTS muted_keywords,
+DROP COLUMN IF EXISTS categories;
@@ -0,0 +1,3 @@
+ALTER TABLE channel_settings
+ADD COLUMN IF NOT EXISTS categories TEXT[] NOT NULL DEFAULT '{}',
+ADD COLUMN IF NOT EXISTS muted_keywords TEXT[] NOT NULL DEFAULT '{}';
@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS discord_outbox (
+    id BIGSERIAL PRIMARY KEY,
+    stream_id TEXT NOT NULL REFERENCES streams(stream_id) ON DELETE CASCADE,
+    discord_channel_id BIGINT NOT NULL,
+    payload JSONB NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_discord_outbox_stream_id ON discord_outbox(stream_id);
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS eventsub_messages;
@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS eventsub_messages (
+    message_id TEXT PRIMARY KEY,
+    expires_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_eventsub_messages_expires_at ON eventsub_messages(expires_at);
@@ -0,0 +1,2 @@
+ALTER TABLE channel_settings
+ADD COLUMN IF NOT EXISTS hype_train TEXT CHECK (hype_train IN ('annotate', 'post'));
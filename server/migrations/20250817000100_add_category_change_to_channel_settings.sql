@@ -0,0 +1,2 @@
+ALTER TABLE channel_settings
+ADD COLUMN IF NOT EXISTS category_change TEXT CHECK (category_change IN ('post', 'reply'));
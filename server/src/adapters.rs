@@ -1,4 +1,5 @@
 pub mod db;
 pub mod grpc;
+pub mod notifier;
 pub mod twitch;
 pub mod webhook;
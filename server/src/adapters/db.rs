@@ -2,6 +2,7 @@ use anyhow::{Context, Result};
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use sqlx::{postgres::PgPoolOptions, types::Json, PgPool};
+use std::collections::HashMap;
 
 pub(crate) type Pool = PgPool;
 
@@ -17,19 +18,30 @@ pub(crate) async fn establish_pool(database_url: &str) -> Result<Pool> {
     Ok(pool)
 }
 
+pub(crate) async fn ping(pool: &Pool) -> Result<()> {
+    sqlx::query("SELECT 1")
+        .execute(pool)
+        .await
+        .context("pinging database")?;
+    Ok(())
+}
+
 pub(crate) async fn track_channel(
     pool: &Pool,
     channel: &str,
     display_name: &str,
     channel_id: &str,
+    profile_image_url: &str,
 ) -> Result<Channel> {
     let now = Utc::now().naive_utc();
     let channel = sqlx::query_as::<_, Channel>(
         r#"
-        INSERT INTO channels (name, display_name, channel_id, created_at, updated_at)
-        VALUES ($1, $2, $3, $4, $5)
-        ON CONFLICT (name) DO UPDATE SET updated_at = EXCLUDED.updated_at, active = true
-        RETURNING id, name, display_name, channel_id, active, created_at, updated_at
+        INSERT INTO channels (name, display_name, channel_id, created_at, updated_at, profile_image_url)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        ON CONFLICT (name) DO UPDATE SET updated_at = EXCLUDED.updated_at, active = true, paused = false,
+                                         profile_image_url = EXCLUDED.profile_image_url,
+                                         broken_reason = NULL
+        RETURNING id, name, display_name, channel_id, active, paused, profile_image_url, broken_reason, created_at, updated_at
         "#,
     )
     .bind(channel)
@@ -37,12 +49,53 @@ pub(crate) async fn track_channel(
     .bind(channel_id)
     .bind(now)
     .bind(now)
+    .bind(profile_image_url)
     .fetch_one(pool)
     .await
     .with_context(|| format!("tracking channel `{channel}`"))?;
     Ok(channel)
 }
 
+/// Stores the profile image a channel went live with, should it have changed.
+pub(crate) async fn set_profile_image_url(
+    pool: &Pool,
+    channel_id: &str,
+    profile_image_url: &str,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        UPDATE channels SET profile_image_url = $2
+         WHERE channel_id = $1 AND profile_image_url <> $2
+        "#,
+    )
+    .bind(channel_id)
+    .bind(profile_image_url)
+    .execute(pool)
+    .await
+    .with_context(|| format!("setting profile image of `{channel_id}`"))?;
+    Ok(())
+}
+
+/// Flags a channel whose EventSub subscriptions couldn't be restored, or
+/// clears the flag with `None` once they are.
+pub(crate) async fn set_broken_reason(
+    pool: &Pool,
+    channel_id: &str,
+    reason: Option<&str>,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        UPDATE channels SET broken_reason = $2 WHERE channel_id = $1
+        "#,
+    )
+    .bind(channel_id)
+    .bind(reason)
+    .execute(pool)
+    .await
+    .with_context(|| format!("setting broken reason of `{channel_id}`"))?;
+    Ok(())
+}
+
 pub(crate) async fn untrack_channel(pool: &Pool, channel: &str) -> Result<()> {
     sqlx::query(
         r#"
@@ -56,6 +109,44 @@ pub(crate) async fn untrack_channel(pool: &Pool, channel: &str) -> Result<()> {
     Ok(())
 }
 
+/// Untracks every channel in `names` in a single transaction, returning the
+/// names that were active.
+pub(crate) async fn untrack_channels(pool: &Pool, names: &[String]) -> Result<Vec<String>> {
+    let mut tx = pool.begin().await.context("starting transaction")?;
+    let untracked = sqlx::query_scalar(
+        r#"
+        UPDATE channels SET active = false WHERE name = ANY($1) AND active = true
+        RETURNING name
+        "#,
+    )
+    .bind(names)
+    .fetch_all(&mut *tx)
+    .await
+    .context("untracking channels")?;
+    tx.commit().await.context("committing untrack")?;
+    Ok(untracked)
+}
+
+pub(crate) async fn set_channel_paused(
+    pool: &Pool,
+    channel: &str,
+    paused: bool,
+) -> Result<Channel> {
+    let channel = sqlx::query_as::<_, Channel>(
+        r#"
+        UPDATE channels SET paused = $2, updated_at = $3 WHERE name = $1 AND active = true
+        RETURNING id, name, display_name, channel_id, active, paused, profile_image_url, broken_reason, created_at, updated_at
+        "#,
+    )
+    .bind(channel)
+    .bind(paused)
+    .bind(Utc::now().naive_utc())
+    .fetch_one(pool)
+    .await
+    .with_context(|| format!("setting paused = {paused} on channel `{channel}`"))?;
+    Ok(channel)
+}
+
 #[derive(sqlx::FromRow, Serialize, Deserialize, Debug, Clone)]
 pub(crate) struct Channel {
     pub id: i32,
@@ -63,14 +154,60 @@ pub(crate) struct Channel {
     pub display_name: String,
     pub channel_id: String,
     pub active: bool,
+    pub paused: bool,
+    /// Empty until the channel is tracked or goes live after the column was
+    /// added.
+    pub profile_image_url: String,
+    /// Why the channel no longer gets EventSub notifications, set when a
+    /// revoked subscription couldn't be restored.
+    pub broken_reason: Option<String>,
     pub created_at: chrono::NaiveDateTime,
     pub updated_at: chrono::NaiveDateTime,
+    /// Only populated by queries that select it.
+    #[sqlx(default)]
+    pub tags: Vec<String>,
+}
+
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub(crate) struct InactiveChannel {
+    #[sqlx(flatten)]
+    pub channel: Channel,
+    pub last_stream_at: Option<chrono::DateTime<Utc>>,
+}
+
+/// Active channels, tracked since before `cutoff`, with no stream starting
+/// after it. Channels that never streamed come first, then the longest idle.
+pub(crate) async fn list_inactive_channels(
+    pool: &Pool,
+    cutoff: chrono::DateTime<Utc>,
+) -> Result<Vec<InactiveChannel>> {
+    let channels = sqlx::query_as::<_, InactiveChannel>(
+        r#"
+        SELECT c.id, c.name, c.display_name, c.channel_id, c.active, c.paused,
+               c.profile_image_url, c.broken_reason, c.created_at, c.updated_at,
+               MAX(s.started_at) AS last_stream_at
+          FROM channels c
+          LEFT JOIN streams s ON s.channel_id = c.channel_id
+         WHERE c.active = true AND c.created_at < $1
+         GROUP BY c.id
+        HAVING MAX(s.started_at) IS NULL OR MAX(s.started_at) < $2
+         ORDER BY last_stream_at ASC NULLS FIRST, c.name
+        "#,
+    )
+    .bind(cutoff.naive_utc())
+    .bind(cutoff)
+    .fetch_all(pool)
+    .await
+    .context("listing inactive channels")?;
+    Ok(channels)
 }
 
 pub(crate) async fn list_channels(pool: &Pool) -> Result<Vec<Channel>> {
     let channels = sqlx::query_as::<_, Channel>(
         r#"
-        SELECT id, name, display_name, channel_id, active, created_at, updated_at FROM channels WHERE active = true
+        SELECT id, name, display_name, channel_id, active, paused, profile_image_url, broken_reason, created_at, updated_at,
+               ARRAY(SELECT tag FROM channel_tags t WHERE t.channel_id = channels.channel_id ORDER BY tag) AS tags
+          FROM channels WHERE active = true
         "#,
     )
     .fetch_all(pool)
@@ -79,15 +216,178 @@ pub(crate) async fn list_channels(pool: &Pool) -> Result<Vec<Channel>> {
     Ok(channels)
 }
 
-pub(crate) async fn get_channel_by_name(pool: &Pool, name: &str) -> Result<Channel> {
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) enum ChannelOrder {
+    #[default]
+    Id,
+    Name,
+    CreatedAt,
+}
+
+/// Which active channels [`list_channels_page`] returns. Empty strings and
+/// `None` match everything.
+#[derive(Debug, Default)]
+pub(crate) struct ChannelFilter<'a> {
+    /// Case-insensitive substring of the name or display name.
+    pub(crate) text: &'a str,
+    pub(crate) tag: &'a str,
+    /// Restricts results to these Twitch IDs.
+    pub(crate) channel_ids: Option<&'a [String]>,
+}
+
+pub(crate) async fn list_channels_page(
+    pool: &Pool,
+    filter: &ChannelFilter<'_>,
+    order: ChannelOrder,
+    descending: bool,
+    limit: Option<i64>,
+    offset: i64,
+) -> Result<Vec<Channel>> {
+    let column = match order {
+        ChannelOrder::Id => "id",
+        ChannelOrder::Name => "name",
+        ChannelOrder::CreatedAt => "created_at",
+    };
+    let direction = if descending { "DESC" } else { "ASC" };
+    let channels = sqlx::query_as::<_, Channel>(&format!(
+        r#"
+        SELECT id, name, display_name, channel_id, active, paused, profile_image_url, broken_reason, created_at, updated_at,
+               ARRAY(SELECT tag FROM channel_tags t WHERE t.channel_id = channels.channel_id ORDER BY tag) AS tags
+          FROM channels
+         WHERE active = true
+           AND ($1 = '' OR name ILIKE $2 OR display_name ILIKE $2)
+           AND ($5 = '' OR EXISTS (
+                   SELECT 1 FROM channel_tags t
+                    WHERE t.channel_id = channels.channel_id AND t.tag = $5))
+           AND ($6::text[] IS NULL OR channel_id = ANY($6))
+         ORDER BY {column} {direction}, id {direction}
+         LIMIT $3 OFFSET $4
+        "#
+    ))
+    .bind(filter.text)
+    .bind(like_pattern(filter.text))
+    .bind(limit)
+    .bind(offset)
+    .bind(filter.tag)
+    .bind(filter.channel_ids)
+    .fetch_all(pool)
+    .await
+    .context("listing channels page")?;
+    Ok(channels)
+}
+
+/// Fuzzy-matches active channels against `query` by substring and trigram
+/// similarity on name and display name, best matches first. Channels in
+/// `matched_ids` count as matches whatever their names, after the ones whose
+/// names match. When `channel_ids` is given, results are restricted to those
+/// Twitch IDs.
+pub(crate) async fn search_channels(
+    pool: &Pool,
+    query: &str,
+    matched_ids: &[String],
+    channel_ids: Option<&[String]>,
+    limit: i64,
+) -> Result<Vec<Channel>> {
+    let channels = sqlx::query_as::<_, Channel>(
+        r#"
+        SELECT id, name, display_name, channel_id, active, paused, profile_image_url, broken_reason, created_at, updated_at,
+               ARRAY(SELECT tag FROM channel_tags t WHERE t.channel_id = channels.channel_id ORDER BY tag) AS tags
+          FROM channels
+         WHERE active = true
+           AND (name ILIKE $2 OR display_name ILIKE $2
+                OR similarity(name, $1) > 0.3 OR similarity(display_name, $1) > 0.3
+                OR channel_id = ANY($5))
+           AND ($3::text[] IS NULL OR channel_id = ANY($3))
+         ORDER BY (lower(name) = lower($1)) DESC,
+                  starts_with(lower(name), lower($1)) DESC,
+                  GREATEST(similarity(name, $1), similarity(display_name, $1)) DESC,
+                  name
+         LIMIT $4
+        "#,
+    )
+    .bind(query)
+    .bind(like_pattern(query))
+    .bind(channel_ids)
+    .bind(limit)
+    .bind(matched_ids)
+    .fetch_all(pool)
+    .await
+    .with_context(|| format!("searching channels for `{query}`"))?;
+    Ok(channels)
+}
+
+/// Replaces the tags of a channel, returning the stored tags in order.
+pub(crate) async fn set_channel_tags(
+    pool: &Pool,
+    channel_id: &str,
+    tags: &[String],
+) -> Result<Vec<String>> {
+    let mut tx = pool.begin().await.context("starting transaction")?;
+    sqlx::query("DELETE FROM channel_tags WHERE channel_id = $1")
+        .bind(channel_id)
+        .execute(&mut *tx)
+        .await
+        .with_context(|| format!("clearing tags for `{channel_id}`"))?;
+    let mut tags: Vec<String> = sqlx::query_scalar(
+        r#"
+        INSERT INTO channel_tags (channel_id, tag)
+        SELECT $1, tag FROM UNNEST($2::text[]) AS tag
+        ON CONFLICT DO NOTHING
+        RETURNING tag
+        "#,
+    )
+    .bind(channel_id)
+    .bind(tags)
+    .fetch_all(&mut *tx)
+    .await
+    .with_context(|| format!("setting tags for `{channel_id}`"))?;
+    tx.commit().await.context("committing tags")?;
+    tags.sort();
+    Ok(tags)
+}
+
+/// Tags in use by active channels, with the number of channels carrying each.
+pub(crate) async fn list_tags(pool: &Pool) -> Result<Vec<(String, i64)>> {
+    let tags = sqlx::query_as::<_, (String, i64)>(
+        r#"
+        SELECT t.tag, COUNT(*)
+          FROM channel_tags t
+          JOIN channels c ON c.channel_id = t.channel_id
+         WHERE c.active = true
+         GROUP BY t.tag
+         ORDER BY t.tag
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+    .context("listing tags")?;
+    Ok(tags)
+}
+
+/// Builds an `ILIKE` pattern matching `s` anywhere, escaping wildcards in `s`.
+fn like_pattern(s: &str) -> String {
+    let mut pattern = String::with_capacity(s.len() + 2);
+    pattern.push('%');
+    for c in s.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            pattern.push('\\');
+        }
+        pattern.push(c);
+    }
+    pattern.push('%');
+    pattern
+}
+
+pub(crate) async fn get_channel_by_name(pool: &Pool, name: &str) -> Result<Option<Channel>> {
     let channel = sqlx::query_as::<_, Channel>(
         r#"
-        SELECT id, name, display_name, channel_id, active, created_at, updated_at
+        SELECT id, name, display_name, channel_id, active, paused, profile_image_url, broken_reason, created_at, updated_at,
+               ARRAY(SELECT tag FROM channel_tags t WHERE t.channel_id = channels.channel_id ORDER BY tag) AS tags
           FROM channels WHERE name = $1
         "#,
     )
     .bind(name)
-    .fetch_one(pool)
+    .fetch_optional(pool)
     .await
     .with_context(|| format!("getting channel by name `{name}`"))?;
     Ok(channel)
@@ -113,15 +413,44 @@ pub(crate) async fn update_channel(
     Ok(())
 }
 
+/// A Discord message posted about a stream, one per destination channel.
+#[derive(sqlx::FromRow, Debug, Clone, Copy, PartialEq)]
+pub struct StreamMessage {
+    pub discord_channel_id: i64,
+    pub message_id: i64,
+}
+
+/// A Discord message waiting in the outbox to be posted about a stream.
+#[derive(Debug, Clone)]
+pub(crate) struct OutboxMessage {
+    pub discord_channel_id: i64,
+    /// The body of Discord's create message request.
+    pub payload: serde_json::Value,
+}
+
+/// An outbox message that hasn't been delivered yet.
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub(crate) struct PendingMessage {
+    pub id: i64,
+    pub stream_id: String,
+    pub channel_id: String,
+    pub discord_channel_id: i64,
+    pub payload: Json<serde_json::Value>,
+}
+
+/// Records a stream that went live, queueing the messages to post about it
+/// in the same transaction so they're neither lost nor posted twice should
+/// the server stop in between.
 pub(crate) async fn start_stream(
     pool: &Pool,
     stream_id: &str,
     channel_id: &str,
     title: &str,
     category: &str,
-    message_id: u64,
+    outbox: &[OutboxMessage],
     timestamp: chrono::DateTime<Utc>,
 ) -> Result<()> {
+    let mut tx = pool.begin().await.context("starting transaction")?;
     sqlx::query(
         r#"
         INSERT INTO streams (stream_id, channel_id, title, started_at, last_updated, message_id, events)
@@ -133,18 +462,263 @@ pub(crate) async fn start_stream(
     .bind(title)
     .bind(timestamp)
     .bind(timestamp)
-    .bind(message_id as i64)
+    .bind(0_i64)
     .bind(Json(vec![UpdateEvent {
         title: title.to_string(),
         category: category.to_string(),
         timestamp,
+        raid: None,
     }]))
-    .execute(pool)
+    .execute(&mut *tx)
     .await
     .with_context(|| format!("starting stream `{stream_id}`"))?;
+
+    insert_outbox(&mut tx, stream_id, outbox).await?;
+    tx.commit().await.context("committing stream start")?;
+    Ok(())
+}
+
+/// Queues messages to post about a stream that's already recorded.
+pub(crate) async fn enqueue_messages(
+    pool: &Pool,
+    stream_id: &str,
+    outbox: &[OutboxMessage],
+) -> Result<()> {
+    let mut tx = pool.begin().await.context("starting transaction")?;
+    insert_outbox(&mut tx, stream_id, outbox).await?;
+    tx.commit().await.context("committing outbox messages")?;
+    Ok(())
+}
+
+async fn insert_outbox(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    stream_id: &str,
+    outbox: &[OutboxMessage],
+) -> Result<()> {
+    let (channel_ids, payloads): (Vec<i64>, Vec<Json<&serde_json::Value>>) = outbox
+        .iter()
+        .map(|m| (m.discord_channel_id, Json(&m.payload)))
+        .unzip();
+    sqlx::query(
+        r#"
+        INSERT INTO discord_outbox (stream_id, discord_channel_id, payload)
+        SELECT $1, discord_channel_id, payload
+          FROM UNNEST($2::bigint[], $3::jsonb[]) AS t(discord_channel_id, payload)
+        "#,
+    )
+    .bind(stream_id)
+    .bind(channel_ids)
+    .bind(payloads)
+    .execute(&mut **tx)
+    .await
+    .with_context(|| format!("queueing messages of stream `{stream_id}`"))?;
+    Ok(())
+}
+
+/// Outbox messages of live streams, oldest first, leaving out those that
+/// failed `max_attempts` times.
+pub(crate) async fn pending_messages(
+    pool: &Pool,
+    stream_id: Option<&str>,
+    max_attempts: i32,
+) -> Result<Vec<PendingMessage>> {
+    let messages = sqlx::query_as::<_, PendingMessage>(
+        r#"
+        SELECT o.id, o.stream_id, s.channel_id, o.discord_channel_id, o.payload
+          FROM discord_outbox o
+          JOIN streams s ON s.stream_id = o.stream_id
+         WHERE s.ended_at IS NULL
+           AND o.attempts < $2
+           AND ($1::text IS NULL OR o.stream_id = $1)
+         ORDER BY o.id
+        "#,
+    )
+    .bind(stream_id)
+    .bind(max_attempts)
+    .fetch_all(pool)
+    .await
+    .context("listing pending outbox messages")?;
+    Ok(messages)
+}
+
+/// An outbox message claimed for posting. Its row stays locked until it's
+/// completed or failed, so concurrent deliveries skip it instead of waiting.
+pub(crate) struct ClaimedMessage {
+    tx: sqlx::Transaction<'static, sqlx::Postgres>,
+    pub message: PendingMessage,
+}
+
+/// Claims the oldest outbox message of a live stream with an ID above
+/// `after`, leaving out those that failed `max_attempts` times or that
+/// another delivery has claimed.
+pub(crate) async fn claim_message(
+    pool: &Pool,
+    stream_id: Option<&str>,
+    after: i64,
+    max_attempts: i32,
+) -> Result<Option<ClaimedMessage>> {
+    let mut tx = pool.begin().await.context("starting transaction")?;
+    let message = sqlx::query_as::<_, PendingMessage>(
+        r#"
+        SELECT o.id, o.stream_id, s.channel_id, o.discord_channel_id, o.payload
+          FROM discord_outbox o
+          JOIN streams s ON s.stream_id = o.stream_id
+         WHERE s.ended_at IS NULL
+           AND o.attempts < $2
+           AND ($1::text IS NULL OR o.stream_id = $1)
+           AND o.id > $3
+         ORDER BY o.id
+         LIMIT 1
+           FOR UPDATE OF o SKIP LOCKED
+        "#,
+    )
+    .bind(stream_id)
+    .bind(max_attempts)
+    .bind(after)
+    .fetch_optional(&mut *tx)
+    .await
+    .context("claiming outbox message")?;
+    Ok(message.map(|message| ClaimedMessage { tx, message }))
+}
+
+/// Records a claimed outbox message as posted: it becomes one of the
+/// stream's messages and leaves the outbox.
+pub(crate) async fn complete_message(claim: ClaimedMessage, message: StreamMessage) -> Result<()> {
+    let ClaimedMessage {
+        mut tx,
+        message: pending,
+    } = claim;
+    let (outbox_id, stream_id) = (pending.id, pending.stream_id.as_str());
+    sqlx::query(
+        r#"
+        INSERT INTO stream_messages (stream_id, discord_channel_id, message_id)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (stream_id, discord_channel_id) DO UPDATE SET message_id = EXCLUDED.message_id
+        "#,
+    )
+    .bind(stream_id)
+    .bind(message.discord_channel_id)
+    .bind(message.message_id)
+    .execute(&mut *tx)
+    .await
+    .with_context(|| format!("recording message of stream `{stream_id}`"))?;
+    sqlx::query("UPDATE streams SET message_id = $2 WHERE stream_id = $1 AND message_id = 0")
+        .bind(stream_id)
+        .bind(message.message_id)
+        .execute(&mut *tx)
+        .await
+        .with_context(|| format!("updating message of stream `{stream_id}`"))?;
+    sqlx::query("DELETE FROM discord_outbox WHERE id = $1")
+        .bind(outbox_id)
+        .execute(&mut *tx)
+        .await
+        .with_context(|| format!("removing outbox message {outbox_id}"))?;
+    tx.commit().await.context("committing delivered message")?;
+    Ok(())
+}
+
+/// Records a failed attempt at posting a claimed outbox message, releasing
+/// it to be retried.
+pub(crate) async fn fail_message(claim: ClaimedMessage, error: &str) -> Result<()> {
+    let ClaimedMessage { mut tx, message } = claim;
+    let outbox_id = message.id;
+    sqlx::query(
+        r#"
+        UPDATE discord_outbox SET attempts = attempts + 1, last_error = $2 WHERE id = $1
+        "#,
+    )
+    .bind(outbox_id)
+    .bind(error)
+    .execute(&mut *tx)
+    .await
+    .with_context(|| format!("recording failure of outbox message {outbox_id}"))?;
+    tx.commit().await.context("committing failed message")?;
+    Ok(())
+}
+
+/// Records an EventSub message ID for `ttl`, returning `false` if it was
+/// already seen within its TTL. Unlike the webhook's in-memory set, this
+/// catches Twitch retries that arrive across a restart.
+pub(crate) async fn claim_message_id(
+    pool: &Pool,
+    message_id: &str,
+    ttl: chrono::Duration,
+) -> Result<bool> {
+    let now = Utc::now();
+    let claimed = sqlx::query(
+        r#"
+        INSERT INTO eventsub_messages (message_id, expires_at) VALUES ($1, $2)
+            ON CONFLICT (message_id) DO UPDATE SET expires_at = EXCLUDED.expires_at
+            WHERE eventsub_messages.expires_at <= $3
+        "#,
+    )
+    .bind(message_id)
+    .bind(now + ttl)
+    .bind(now)
+    .execute(pool)
+    .await
+    .with_context(|| format!("recording message ID `{message_id}`"))?
+    .rows_affected();
+    Ok(claimed > 0)
+}
+
+/// Forgets a claimed message ID, so Twitch's retry of a message that failed
+/// to be handled isn't rejected as a duplicate.
+pub(crate) async fn release_message_id(pool: &Pool, message_id: &str) -> Result<()> {
+    sqlx::query("DELETE FROM eventsub_messages WHERE message_id = $1")
+        .bind(message_id)
+        .execute(pool)
+        .await
+        .with_context(|| format!("releasing message ID `{message_id}`"))?;
+    Ok(())
+}
+
+/// Deletes message IDs whose TTL is over, returning how many.
+pub(crate) async fn prune_message_ids(pool: &Pool) -> Result<u64> {
+    let pruned = sqlx::query("DELETE FROM eventsub_messages WHERE expires_at <= $1")
+        .bind(Utc::now())
+        .execute(pool)
+        .await
+        .context("pruning expired message IDs")?
+        .rows_affected();
+    Ok(pruned)
+}
+
+/// Forgets the Discord messages of a stream, posted or still queued, for one
+/// whose announcement was withdrawn.
+pub(crate) async fn clear_stream_messages(pool: &Pool, stream_id: &str) -> Result<()> {
+    let mut tx = pool.begin().await.context("starting transaction")?;
+    for query in [
+        "DELETE FROM stream_messages WHERE stream_id = $1",
+        "DELETE FROM discord_outbox WHERE stream_id = $1",
+        "UPDATE streams SET message_id = 0 WHERE stream_id = $1",
+    ] {
+        sqlx::query(query)
+            .bind(stream_id)
+            .execute(&mut *tx)
+            .await
+            .with_context(|| format!("clearing messages of stream `{stream_id}`"))?;
+    }
+    tx.commit().await.context("committing cleared messages")?;
     Ok(())
 }
 
+pub(crate) async fn get_stream_messages(
+    pool: &Pool,
+    stream_id: &str,
+) -> Result<Vec<StreamMessage>> {
+    let messages = sqlx::query_as::<_, StreamMessage>(
+        r#"
+        SELECT discord_channel_id, message_id FROM stream_messages WHERE stream_id = $1
+        "#,
+    )
+    .bind(stream_id)
+    .fetch_all(pool)
+    .await
+    .with_context(|| format!("getting messages of stream `{stream_id}`"))?;
+    Ok(messages)
+}
+
 pub(crate) async fn update_stream(
     pool: &Pool,
     stream_id: &str,
@@ -186,6 +760,12 @@ pub(crate) async fn end_stream(
     .execute(pool)
     .await
     .with_context(|| format!("ending stream `{stream_id}`"))?;
+    // Live messages still queued would only announce a stream that's over.
+    sqlx::query("DELETE FROM discord_outbox WHERE stream_id = $1")
+        .bind(stream_id)
+        .execute(pool)
+        .await
+        .with_context(|| format!("dropping queued messages of stream `{stream_id}`"))?;
     Ok(())
 }
 
@@ -202,11 +782,145 @@ pub(crate) async fn delete_stream(pool: &Pool, stream_id: &str) -> Result<()> {
     Ok(())
 }
 
-#[derive(sqlx::FromRow, Serialize, Deserialize, Debug, Clone)]
+#[derive(sqlx::FromRow, Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub(crate) struct ChannelSettings {
+    pub mention_role_id: Option<i64>,
+    pub color: Option<i32>,
+    pub muted: bool,
+    /// `everyone` or `here`, mentioned instead of the role.
+    pub mass_mention: Option<String>,
+    /// Sends a push notification when the channel goes live.
+    pub push: bool,
+    /// 1 to 5 on ntfy's scale; `None` uses the server default.
+    pub push_priority: Option<i16>,
+    /// `off`, `live` or `digest`; `None` follows the channel's tags.
+    pub email: Option<String>,
+    /// Only streams in one of these categories are announced; empty allows
+    /// every category.
+    pub categories: Vec<String>,
+    /// Streams whose title contains one of these aren't announced.
+    pub muted_keywords: Vec<String>,
+    /// `annotate` or `post`, how Hype Trains show up in Discord; `None`
+    /// doesn't follow them.
+    pub hype_train: Option<String>,
+    /// `post` or `reply`, how a switch of category is announced besides
+    /// editing the live message; `None` only edits it.
+    pub category_change: Option<String>,
+}
+
+pub(crate) async fn get_channel_settings(pool: &Pool, channel_id: &str) -> Result<ChannelSettings> {
+    let settings = sqlx::query_as::<_, ChannelSettings>(
+        r#"
+        SELECT mention_role_id, color, muted, mass_mention, push, push_priority, email,
+               categories, muted_keywords, hype_train, category_change
+        FROM channel_settings WHERE channel_id = $1
+        "#,
+    )
+    .bind(channel_id)
+    .fetch_optional(pool)
+    .await
+    .with_context(|| format!("getting settings for channel `{channel_id}`"))?;
+    Ok(settings.unwrap_or_default())
+}
+
+/// Twitch IDs of the channels that follow Hype Trains.
+pub(crate) async fn list_hype_train_channels(pool: &Pool) -> Result<Vec<String>> {
+    sqlx::query_scalar(
+        r#"
+        SELECT channel_id FROM channel_settings WHERE hype_train IS NOT NULL
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+    .context("listing Hype Train channels")
+}
+
+/// Channels' own email modes, by Twitch ID, for those that set one.
+pub(crate) async fn list_email_modes(pool: &Pool) -> Result<HashMap<String, String>> {
+    let modes = sqlx::query_as::<_, (String, String)>(
+        r#"
+        SELECT channel_id, email FROM channel_settings WHERE email IS NOT NULL
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+    .context("listing email modes")?;
+    Ok(modes.into_iter().collect())
+}
+
+pub(crate) async fn update_channel_settings(
+    pool: &Pool,
+    channel_id: &str,
+    settings: &ChannelSettings,
+) -> Result<ChannelSettings> {
+    let settings = sqlx::query_as::<_, ChannelSettings>(
+        r#"
+        INSERT INTO channel_settings
+            (channel_id, mention_role_id, color, muted, mass_mention, push, push_priority, email,
+             categories, muted_keywords, hype_train, category_change, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+        ON CONFLICT (channel_id) DO UPDATE
+            SET mention_role_id = EXCLUDED.mention_role_id,
+                color = EXCLUDED.color,
+                muted = EXCLUDED.muted,
+                mass_mention = EXCLUDED.mass_mention,
+                push = EXCLUDED.push,
+                push_priority = EXCLUDED.push_priority,
+                email = EXCLUDED.email,
+                categories = EXCLUDED.categories,
+                muted_keywords = EXCLUDED.muted_keywords,
+                hype_train = EXCLUDED.hype_train,
+                category_change = EXCLUDED.category_change,
+                updated_at = EXCLUDED.updated_at
+        RETURNING mention_role_id, color, muted, mass_mention, push, push_priority, email,
+                  categories, muted_keywords, hype_train, category_change
+        "#,
+    )
+    .bind(channel_id)
+    .bind(settings.mention_role_id)
+    .bind(settings.color)
+    .bind(settings.muted)
+    .bind(settings.mass_mention.as_deref())
+    .bind(settings.push)
+    .bind(settings.push_priority)
+    .bind(settings.email.as_deref())
+    .bind(&settings.categories)
+    .bind(&settings.muted_keywords)
+    .bind(settings.hype_train.as_deref())
+    .bind(settings.category_change.as_deref())
+    .bind(Utc::now())
+    .fetch_one(pool)
+    .await
+    .with_context(|| format!("updating settings for channel `{channel_id}`"))?;
+    Ok(settings)
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct UpdateEvent {
     pub title: String,
     pub category: String,
     pub timestamp: chrono::DateTime<Utc>,
+    /// Set for events recording a raid rather than a change of title or
+    /// category.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raid: Option<Raid>,
+}
+
+/// A raid out of a stream's channel or into it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Raid {
+    pub direction: RaidDirection,
+    /// The other channel of the raid.
+    pub login: String,
+    pub name: String,
+    pub viewers: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RaidDirection {
+    Out,
+    In,
 }
 
 #[derive(sqlx::FromRow, Serialize, Deserialize, Debug, Clone)]
@@ -237,3 +951,418 @@ pub(crate) async fn get_streams(pool: &Pool, channel_id: Option<String>) -> Resu
     .context("getting streams")?;
     Ok(streams)
 }
+
+pub(crate) async fn get_stream_history(
+    pool: &Pool,
+    channel_id: &str,
+    since: Option<chrono::DateTime<Utc>>,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<Stream>> {
+    let streams = sqlx::query_as::<_, Stream>(
+        r#"
+        SELECT id, channel_id, stream_id, title, started_at, ended_at, last_updated, message_id, events
+        FROM streams
+        WHERE channel_id = $1
+          AND ($4::timestamptz IS NULL OR started_at >= $4)
+        ORDER BY started_at DESC
+        LIMIT $2 OFFSET $3
+        "#,
+    )
+    .bind(channel_id)
+    .bind(limit)
+    .bind(offset)
+    .bind(since)
+    .fetch_all(pool)
+    .await
+    .with_context(|| format!("getting stream history for `{channel_id}`"))?;
+    Ok(streams)
+}
+
+pub(crate) async fn list_streams(
+    pool: &Pool,
+    channel_id: Option<&str>,
+    since: Option<chrono::DateTime<Utc>>,
+) -> Result<Vec<Stream>> {
+    let streams = sqlx::query_as::<_, Stream>(
+        r#"
+        SELECT s.id, s.channel_id, s.stream_id, s.title, s.started_at, s.ended_at,
+               s.last_updated, s.message_id, s.events
+          FROM streams s
+          JOIN channels c ON c.channel_id = s.channel_id
+         WHERE c.active = true
+           AND ($1::text IS NULL OR s.channel_id = $1)
+           AND ($2::timestamptz IS NULL OR s.started_at >= $2)
+         ORDER BY s.started_at
+        "#,
+    )
+    .bind(channel_id)
+    .bind(since)
+    .fetch_all(pool)
+    .await
+    .context("listing streams")?;
+    Ok(streams)
+}
+
+/// Viewer counts sampled while live.
+#[derive(sqlx::FromRow, Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct ViewerStats {
+    pub average: f64,
+    pub peak: i32,
+}
+
+/// Stores the viewer counts sampled at `recorded_at`, by stream ID.
+pub(crate) async fn record_viewer_counts(
+    pool: &Pool,
+    counts: &[(String, i32)],
+    recorded_at: chrono::DateTime<Utc>,
+) -> Result<()> {
+    let (stream_ids, viewers): (Vec<&str>, Vec<i32>) = counts
+        .iter()
+        .map(|(stream_id, viewers)| (stream_id.as_str(), *viewers))
+        .unzip();
+    sqlx::query(
+        r#"
+        INSERT INTO viewer_counts (stream_id, recorded_at, viewers)
+        SELECT stream_id, $3, viewers FROM UNNEST($1::text[], $2::int[]) AS t(stream_id, viewers)
+        ON CONFLICT DO NOTHING
+        "#,
+    )
+    .bind(stream_ids)
+    .bind(viewers)
+    .bind(recorded_at)
+    .execute(pool)
+    .await
+    .context("recording viewer counts")?;
+    Ok(())
+}
+
+/// Viewer counts of one stream, or `None` if none were sampled.
+pub(crate) async fn stream_viewer_stats(
+    pool: &Pool,
+    stream_id: &str,
+) -> Result<Option<ViewerStats>> {
+    let stats = sqlx::query_as::<_, ViewerStats>(
+        r#"
+        SELECT AVG(viewers)::float8 AS average, MAX(viewers) AS peak
+          FROM viewer_counts
+         WHERE stream_id = $1
+        HAVING COUNT(*) > 0
+        "#,
+    )
+    .bind(stream_id)
+    .fetch_optional(pool)
+    .await
+    .with_context(|| format!("getting viewer counts of stream `{stream_id}`"))?;
+    Ok(stats)
+}
+
+/// Viewer counts per channel ID over the streams [`list_streams`] would
+/// return. Channels without samples are left out.
+pub(crate) async fn viewer_stats(
+    pool: &Pool,
+    channel_id: Option<&str>,
+    since: Option<chrono::DateTime<Utc>>,
+) -> Result<HashMap<String, ViewerStats>> {
+    let rows = sqlx::query_as::<_, (String, f64, i32)>(
+        r#"
+        SELECT s.channel_id, AVG(v.viewers)::float8, MAX(v.viewers)
+          FROM viewer_counts v
+          JOIN streams s ON s.stream_id = v.stream_id
+         WHERE ($1::text IS NULL OR s.channel_id = $1)
+           AND ($2::timestamptz IS NULL OR s.started_at >= $2)
+         GROUP BY s.channel_id
+        "#,
+    )
+    .bind(channel_id)
+    .bind(since)
+    .fetch_all(pool)
+    .await
+    .context("getting viewer counts")?;
+    Ok(rows
+        .into_iter()
+        .map(|(channel_id, average, peak)| (channel_id, ViewerStats { average, peak }))
+        .collect())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NotificationAction {
+    Send,
+    Edit,
+    Delete,
+}
+
+impl NotificationAction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Send => "send",
+            Self::Edit => "edit",
+            Self::Delete => "delete",
+        }
+    }
+}
+
+#[derive(sqlx::FromRow, Debug, Clone)]
+pub(crate) struct NotificationLogEntry {
+    pub id: i64,
+    pub channel_id: String,
+    /// Channel login, if the channel is still known.
+    pub name: Option<String>,
+    pub action: String,
+    pub message_id: Option<i64>,
+    /// Set when the Discord request failed.
+    pub error: Option<String>,
+    pub created_at: chrono::DateTime<Utc>,
+}
+
+pub(crate) async fn record_notification(
+    pool: &Pool,
+    channel_id: &str,
+    action: NotificationAction,
+    message_id: Option<i64>,
+    error: Option<&str>,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO notification_log (channel_id, action, message_id, error, created_at)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+    )
+    .bind(channel_id)
+    .bind(action.as_str())
+    .bind(message_id)
+    .bind(error)
+    .bind(Utc::now())
+    .execute(pool)
+    .await
+    .with_context(|| format!("recording notification for `{channel_id}`"))?;
+    Ok(())
+}
+
+pub(crate) async fn get_notification_log(
+    pool: &Pool,
+    channel_id: Option<&str>,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<NotificationLogEntry>> {
+    let entries = sqlx::query_as::<_, NotificationLogEntry>(
+        r#"
+        SELECT n.id, n.channel_id, c.name, n.action, n.message_id, n.error, n.created_at
+          FROM notification_log n
+          LEFT JOIN channels c ON c.channel_id = n.channel_id
+         WHERE $1::text IS NULL OR n.channel_id = $1
+         ORDER BY n.created_at DESC, n.id DESC
+         LIMIT $2 OFFSET $3
+        "#,
+    )
+    .bind(channel_id)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(pool)
+    .await
+    .context("getting notification log")?;
+    Ok(entries)
+}
+
+/// Every channel row, including untracked ones, for backups.
+pub(crate) async fn export_channels(pool: &Pool) -> Result<Vec<Channel>> {
+    let channels = sqlx::query_as::<_, Channel>(
+        r#"
+        SELECT id, name, display_name, channel_id, active, paused, profile_image_url, broken_reason, created_at, updated_at,
+               ARRAY(SELECT tag FROM channel_tags t WHERE t.channel_id = channels.channel_id ORDER BY tag) AS tags
+          FROM channels
+         ORDER BY id
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+    .context("exporting channels")?;
+    Ok(channels)
+}
+
+/// Every stream row, for backups.
+pub(crate) async fn export_streams(pool: &Pool) -> Result<Vec<Stream>> {
+    let streams = sqlx::query_as::<_, Stream>(
+        r#"
+        SELECT id, channel_id, stream_id, title, started_at, ended_at, last_updated, message_id, events
+        FROM streams
+        ORDER BY id
+        "#,
+    )
+    .fetch_all(pool)
+    .await
+    .context("exporting streams")?;
+    Ok(streams)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::postgres::PgConnectOptions;
+
+    #[test]
+    fn test_like_pattern_escapes_wildcards() {
+        assert_eq!(like_pattern("shroud"), "%shroud%");
+        assert_eq!(like_pattern("a_b%c"), "%a\\_b\\%c%");
+        assert_eq!(like_pattern("x\\y"), "%x\\\\y%");
+    }
+
+    /// A freshly migrated database of its own, dropped by [`Self::close`].
+    struct TestDatabase {
+        admin: Pool,
+        name: String,
+        pool: Pool,
+    }
+
+    impl TestDatabase {
+        /// `None` when `DATABASE_URL` isn't set, skipping the test.
+        async fn create() -> Option<Self> {
+            let Ok(url) = std::env::var("DATABASE_URL") else {
+                eprintln!("DATABASE_URL isn't set, skipping");
+                return None;
+            };
+            let admin = Pool::connect(&url).await.unwrap();
+            let name = format!("stitch_test_{}", uuid::Uuid::new_v4().simple());
+            sqlx::query(&format!("CREATE DATABASE {name}"))
+                .execute(&admin)
+                .await
+                .unwrap();
+            let options = url.parse::<PgConnectOptions>().unwrap().database(&name);
+            let pool = PgPoolOptions::new().connect_with(options).await.unwrap();
+            sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+            Some(Self { admin, name, pool })
+        }
+
+        async fn close(self) {
+            self.pool.close().await;
+            sqlx::query(&format!("DROP DATABASE {} WITH (FORCE)", self.name))
+                .execute(&self.admin)
+                .await
+                .unwrap();
+        }
+    }
+
+    /// Every query that returns a `Channel` has to select all of its
+    /// columns, which only shows when one runs.
+    #[tokio::test]
+    async fn test_channel_queries() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        let pool = &db.pool;
+        let tracked = track_channel(pool, "cool_user", "Cool_User", "1337", "https://pic")
+            .await
+            .unwrap();
+        assert_eq!(tracked.profile_image_url, "https://pic");
+        set_broken_reason(pool, "1337", Some("revoked"))
+            .await
+            .unwrap();
+
+        let later = Utc::now() + chrono::Duration::days(1);
+        let inactive = list_inactive_channels(pool, later).await.unwrap();
+        assert_eq!(inactive.len(), 1);
+        assert_eq!(
+            inactive[0].channel.broken_reason.as_deref(),
+            Some("revoked")
+        );
+        assert_eq!(list_channels(pool).await.unwrap().len(), 1);
+        let page = list_channels_page(
+            pool,
+            &ChannelFilter::default(),
+            ChannelOrder::Name,
+            false,
+            None,
+            0,
+        )
+        .await
+        .unwrap();
+        assert_eq!(page.len(), 1);
+        let found = search_channels(pool, "cool_user", &[], None, 10)
+            .await
+            .unwrap();
+        assert_eq!(found.len(), 1);
+        assert!(get_channel_by_name(pool, "cool_user")
+            .await
+            .unwrap()
+            .is_some());
+        assert_eq!(export_channels(pool).await.unwrap().len(), 1);
+        let paused = set_channel_paused(pool, "cool_user", true).await.unwrap();
+        assert!(paused.paused);
+
+        db.close().await;
+    }
+
+    #[tokio::test]
+    async fn test_message_ids() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        let pool = &db.pool;
+        let ttl = chrono::Duration::minutes(10);
+        assert!(claim_message_id(pool, "a", ttl).await.unwrap());
+        assert!(!claim_message_id(pool, "a", ttl).await.unwrap());
+        release_message_id(pool, "a").await.unwrap();
+        assert!(claim_message_id(pool, "a", ttl).await.unwrap());
+
+        // Expired IDs can be claimed again, and are pruned.
+        let expired = -chrono::Duration::seconds(1);
+        assert!(claim_message_id(pool, "b", expired).await.unwrap());
+        assert!(claim_message_id(pool, "b", expired).await.unwrap());
+        assert_eq!(prune_message_ids(pool).await.unwrap(), 1);
+        assert!(!claim_message_id(pool, "a", ttl).await.unwrap());
+
+        db.close().await;
+    }
+
+    #[tokio::test]
+    async fn test_outbox_claims() {
+        let Some(db) = TestDatabase::create().await else {
+            return;
+        };
+        let pool = &db.pool;
+        track_channel(pool, "cool_user", "Cool_User", "1337", "")
+            .await
+            .unwrap();
+        let outbox: Vec<_> = [1, 2]
+            .map(|discord_channel_id| OutboxMessage {
+                discord_channel_id,
+                payload: serde_json::json!({}),
+            })
+            .into();
+        start_stream(pool, "s1", "1337", "Title", "Factorio", &outbox, Utc::now())
+            .await
+            .unwrap();
+
+        // A claimed message is skipped rather than waited on.
+        let first = claim_message(pool, None, 0, 3).await.unwrap().unwrap();
+        let second = claim_message(pool, None, 0, 3).await.unwrap().unwrap();
+        assert_ne!(first.message.id, second.message.id);
+        assert!(claim_message(pool, None, 0, 3).await.unwrap().is_none());
+
+        // Failing releases it for a retry, completing removes it.
+        let first_id = first.message.id;
+        fail_message(first, "boom").await.unwrap();
+        let message = StreamMessage {
+            discord_channel_id: second.message.discord_channel_id,
+            message_id: 42,
+        };
+        complete_message(second, message).await.unwrap();
+        let retry = claim_message(pool, Some("s1"), 0, 3)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(retry.message.id, first_id);
+        assert!(claim_message(pool, Some("s1"), first_id, 3)
+            .await
+            .unwrap()
+            .is_none());
+        fail_message(retry, "boom").await.unwrap();
+        assert!(claim_message(pool, None, 0, 2).await.unwrap().is_none());
+        assert_eq!(
+            get_stream_messages(pool, "s1").await.unwrap(),
+            vec![message]
+        );
+
+        db.close().await;
+    }
+}
@@ -1,47 +1,344 @@
 use crate::service::channel::ChannelService;
+use crate::service::export::ExportService;
+use crate::service::from_timestamp;
+use crate::service::info::InfoService;
+use crate::service::stream::StreamService;
+use crate::utils::idempotency::{idempotency_key, Idempotency};
+use futures::Stream;
 use proto::stitch::stitch_service_server::StitchService;
 use proto::stitch::{
-    ListChannelsRequest, ListChannelsResponse, TrackChannelRequest, TrackChannelResponse,
-    UntrackChannelRequest, UntrackChannelResponse,
+    Event, ExportDataChunk, ExportDataRequest, GetChannelSettingsRequest,
+    GetChannelSettingsResponse, GetChannelStatusRequest, GetChannelStatusResponse,
+    GetEventSubSubscriptionsRequest, GetEventSubSubscriptionsResponse, GetNotificationLogRequest,
+    GetNotificationLogResponse, GetServerInfoRequest, GetServerInfoResponse,
+    GetStreamHistoryRequest, GetStreamHistoryResponse, GetStreamStatsRequest,
+    GetStreamStatsResponse, GetTopCategoriesRequest, GetTopCategoriesResponse, ListChannelsRequest,
+    ListChannelsResponse, ListInactiveChannelsRequest, ListInactiveChannelsResponse,
+    ListLiveStreamsRequest, ListLiveStreamsResponse, ListTagsRequest, ListTagsResponse, LogEntry,
+    PauseTrackingRequest, PauseTrackingResponse, ResumeTrackingRequest, ResumeTrackingResponse,
+    ResyncSubscriptionsRequest, ResyncSubscriptionsResponse, SearchChannelsRequest,
+    SearchChannelsResponse, SendTestNotificationRequest, SendTestNotificationResponse,
+    SetChannelTagsRequest, SetChannelTagsResponse, TailLogsRequest, TrackChannelRequest,
+    TrackChannelResponse, UntrackChannelRequest, UntrackChannelResponse, UntrackChannelsRequest,
+    UntrackChannelsResponse, UpdateChannelSettingsRequest, UpdateChannelSettingsResponse,
+    ValidateChannelsRequest, ValidateChannelsResponse, WatchEventsRequest,
 };
+use std::pin::Pin;
+use std::sync::Arc;
 use tonic::{Request, Response, Status};
 
+type ResponseStream<T> = Pin<Box<dyn Stream<Item = Result<T, Status>> + Send>>;
+
 #[derive(Clone)]
 pub struct StitchGRPC {
     service: ChannelService,
+    streams: StreamService,
+    info: InfoService,
+    export: ExportService,
+    track_keys: Arc<Idempotency<TrackChannelResponse>>,
+    untrack_keys: Arc<Idempotency<UntrackChannelResponse>>,
+    untrack_many_keys: Arc<Idempotency<UntrackChannelsResponse>>,
 }
 
 impl StitchGRPC {
-    pub fn new(service: ChannelService) -> Self {
-        Self { service }
+    pub fn new(
+        service: ChannelService,
+        streams: StreamService,
+        info: InfoService,
+        export: ExportService,
+    ) -> Self {
+        Self {
+            service,
+            streams,
+            info,
+            export,
+            track_keys: Arc::new(Idempotency::new()),
+            untrack_keys: Arc::new(Idempotency::new()),
+            untrack_many_keys: Arc::new(Idempotency::new()),
+        }
     }
 }
 
 #[tonic::async_trait]
 impl StitchService for StitchGRPC {
+    type WatchEventsStream = ResponseStream<Event>;
+    type ExportDataStream = ResponseStream<ExportDataChunk>;
+    type TailLogsStream = ResponseStream<LogEntry>;
+
     async fn track_channel(
         &self,
         request: Request<TrackChannelRequest>,
     ) -> Result<Response<TrackChannelResponse>, Status> {
-        let req = request.into_inner();
-        self.service.track_channel(req.name).await?;
-        Ok(Response::new(TrackChannelResponse {}))
+        let key = idempotency_key(&request)?;
+        let name = request.into_inner().name;
+        let response = self
+            .track_keys
+            .run(key.as_deref(), &name, || {
+                self.service.track_channel(name.clone())
+            })
+            .await?;
+        Ok(Response::new(response))
     }
 
     async fn untrack_channel(
         &self,
         request: Request<UntrackChannelRequest>,
     ) -> Result<Response<UntrackChannelResponse>, Status> {
-        let req = request.into_inner();
-        self.service.untrack_channel(req.name).await?;
-        Ok(Response::new(UntrackChannelResponse {}))
+        let key = idempotency_key(&request)?;
+        let name = request.into_inner().name;
+        let response = self
+            .untrack_keys
+            .run(key.as_deref(), &name, || async {
+                self.service.untrack_channel(name.clone()).await?;
+                Ok(UntrackChannelResponse {})
+            })
+            .await?;
+        Ok(Response::new(response))
     }
 
     async fn list_channels(
         &self,
-        _request: Request<ListChannelsRequest>,
+        request: Request<ListChannelsRequest>,
     ) -> Result<Response<ListChannelsResponse>, Status> {
-        let channels = self.service.list_channels().await?;
-        Ok(Response::new(ListChannelsResponse { channels }))
+        let response = self.service.list_channels(request.into_inner()).await?;
+        Ok(Response::new(response))
+    }
+
+    async fn get_stream_history(
+        &self,
+        request: Request<GetStreamHistoryRequest>,
+    ) -> Result<Response<GetStreamHistoryResponse>, Status> {
+        let req = request.into_inner();
+        let since = req.since.map(from_timestamp).transpose()?;
+        let streams = self
+            .streams
+            .get_stream_history(&req.name, since, req.limit, req.offset)
+            .await?;
+        Ok(Response::new(GetStreamHistoryResponse { streams }))
+    }
+
+    async fn get_channel_status(
+        &self,
+        request: Request<GetChannelStatusRequest>,
+    ) -> Result<Response<GetChannelStatusResponse>, Status> {
+        let req = request.into_inner();
+        let status = self.streams.get_channel_status(&req.name).await?;
+        Ok(Response::new(status))
+    }
+
+    async fn list_live_streams(
+        &self,
+        _request: Request<ListLiveStreamsRequest>,
+    ) -> Result<Response<ListLiveStreamsResponse>, Status> {
+        let streams = self.streams.list_live_streams().await?;
+        Ok(Response::new(ListLiveStreamsResponse { streams }))
+    }
+
+    async fn watch_events(
+        &self,
+        request: Request<WatchEventsRequest>,
+    ) -> Result<Response<Self::WatchEventsStream>, Status> {
+        let req = request.into_inner();
+        let events = self.streams.watch_events(req.names);
+        Ok(Response::new(Box::pin(events)))
+    }
+
+    async fn tail_logs(
+        &self,
+        request: Request<TailLogsRequest>,
+    ) -> Result<Response<Self::TailLogsStream>, Status> {
+        let entries = self.info.tail_logs(request.into_inner());
+        Ok(Response::new(Box::pin(entries)))
+    }
+
+    async fn get_server_info(
+        &self,
+        _request: Request<GetServerInfoRequest>,
+    ) -> Result<Response<GetServerInfoResponse>, Status> {
+        let info = self.info.get_server_info().await?;
+        Ok(Response::new(info))
+    }
+
+    async fn send_test_notification(
+        &self,
+        request: Request<SendTestNotificationRequest>,
+    ) -> Result<Response<SendTestNotificationResponse>, Status> {
+        let req = request.into_inner();
+        let sent = self.info.send_test_notification(&req.message).await?;
+        Ok(Response::new(sent))
+    }
+
+    async fn search_channels(
+        &self,
+        request: Request<SearchChannelsRequest>,
+    ) -> Result<Response<SearchChannelsResponse>, Status> {
+        let req = request.into_inner();
+        let channels = self
+            .service
+            .search_channels(&req.query, req.live_only, req.include_streams, req.limit)
+            .await?;
+        Ok(Response::new(SearchChannelsResponse { channels }))
+    }
+
+    async fn get_channel_settings(
+        &self,
+        request: Request<GetChannelSettingsRequest>,
+    ) -> Result<Response<GetChannelSettingsResponse>, Status> {
+        let req = request.into_inner();
+        let settings = self.service.get_channel_settings(&req.name).await?;
+        Ok(Response::new(GetChannelSettingsResponse {
+            settings: Some(settings),
+        }))
+    }
+
+    async fn update_channel_settings(
+        &self,
+        request: Request<UpdateChannelSettingsRequest>,
+    ) -> Result<Response<UpdateChannelSettingsResponse>, Status> {
+        let req = request.into_inner();
+        let settings = self
+            .service
+            .update_channel_settings(&req.name, req.settings.unwrap_or_default())
+            .await?;
+        Ok(Response::new(UpdateChannelSettingsResponse {
+            settings: Some(settings),
+        }))
+    }
+
+    async fn resync_subscriptions(
+        &self,
+        _request: Request<ResyncSubscriptionsRequest>,
+    ) -> Result<Response<ResyncSubscriptionsResponse>, Status> {
+        let report = self.service.resync_subscriptions().await?;
+        Ok(Response::new(report))
+    }
+
+    async fn get_stream_stats(
+        &self,
+        request: Request<GetStreamStatsRequest>,
+    ) -> Result<Response<GetStreamStatsResponse>, Status> {
+        let req = request.into_inner();
+        let since = req.since.map(from_timestamp).transpose()?;
+        let channels = self.streams.get_stream_stats(&req.name, since).await?;
+        Ok(Response::new(GetStreamStatsResponse { channels }))
+    }
+
+    async fn get_top_categories(
+        &self,
+        request: Request<GetTopCategoriesRequest>,
+    ) -> Result<Response<GetTopCategoriesResponse>, Status> {
+        let req = request.into_inner();
+        let since = req.since.map(from_timestamp).transpose()?;
+        let categories = self.streams.get_top_categories(since, req.limit).await?;
+        Ok(Response::new(GetTopCategoriesResponse { categories }))
+    }
+
+    async fn pause_tracking(
+        &self,
+        request: Request<PauseTrackingRequest>,
+    ) -> Result<Response<PauseTrackingResponse>, Status> {
+        let req = request.into_inner();
+        let channel = self.service.pause_tracking(&req.name).await?;
+        Ok(Response::new(PauseTrackingResponse {
+            channel: Some(channel),
+        }))
+    }
+
+    async fn resume_tracking(
+        &self,
+        request: Request<ResumeTrackingRequest>,
+    ) -> Result<Response<ResumeTrackingResponse>, Status> {
+        let req = request.into_inner();
+        let channel = self.service.resume_tracking(&req.name).await?;
+        Ok(Response::new(ResumeTrackingResponse {
+            channel: Some(channel),
+        }))
+    }
+
+    async fn get_event_sub_subscriptions(
+        &self,
+        request: Request<GetEventSubSubscriptionsRequest>,
+    ) -> Result<Response<GetEventSubSubscriptionsResponse>, Status> {
+        let req = request.into_inner();
+        let subscriptions = self.info.get_eventsub_subscriptions(&req.name).await?;
+        Ok(Response::new(GetEventSubSubscriptionsResponse {
+            subscriptions,
+        }))
+    }
+
+    async fn export_data(
+        &self,
+        _request: Request<ExportDataRequest>,
+    ) -> Result<Response<Self::ExportDataStream>, Status> {
+        let chunks = self.export.export_data().await?;
+        Ok(Response::new(Box::pin(chunks)))
+    }
+
+    async fn get_notification_log(
+        &self,
+        request: Request<GetNotificationLogRequest>,
+    ) -> Result<Response<GetNotificationLogResponse>, Status> {
+        let req = request.into_inner();
+        let notifications = self
+            .streams
+            .get_notification_log(&req.name, req.limit, req.offset)
+            .await?;
+        Ok(Response::new(GetNotificationLogResponse { notifications }))
+    }
+
+    async fn set_channel_tags(
+        &self,
+        request: Request<SetChannelTagsRequest>,
+    ) -> Result<Response<SetChannelTagsResponse>, Status> {
+        let req = request.into_inner();
+        let channel = self.service.set_channel_tags(&req.name, req.tags).await?;
+        Ok(Response::new(SetChannelTagsResponse {
+            channel: Some(channel),
+        }))
+    }
+
+    async fn list_tags(
+        &self,
+        _request: Request<ListTagsRequest>,
+    ) -> Result<Response<ListTagsResponse>, Status> {
+        let tags = self.service.list_tags().await?;
+        Ok(Response::new(ListTagsResponse { tags }))
+    }
+
+    async fn untrack_channels(
+        &self,
+        request: Request<UntrackChannelsRequest>,
+    ) -> Result<Response<UntrackChannelsResponse>, Status> {
+        let key = idempotency_key(&request)?;
+        let names = request.into_inner().names;
+        let response = self
+            .untrack_many_keys
+            .run(key.as_deref(), &names.join("\n"), || async {
+                let results = self.service.untrack_channels(names.clone()).await?;
+                Ok(UntrackChannelsResponse { results })
+            })
+            .await?;
+        Ok(Response::new(response))
+    }
+
+    async fn validate_channels(
+        &self,
+        request: Request<ValidateChannelsRequest>,
+    ) -> Result<Response<ValidateChannelsResponse>, Status> {
+        let req = request.into_inner();
+        let results = self
+            .service
+            .validate_channels(req.names, req.untrack)
+            .await?;
+        Ok(Response::new(ValidateChannelsResponse { results }))
+    }
+
+    async fn list_inactive_channels(
+        &self,
+        request: Request<ListInactiveChannelsRequest>,
+    ) -> Result<Response<ListInactiveChannelsResponse>, Status> {
+        let req = request.into_inner();
+        let channels = self.service.list_inactive_channels(req.days).await?;
+        Ok(Response::new(ListInactiveChannelsResponse { channels }))
     }
 }
@@ -6,13 +6,32 @@ use futures::future::try_join_all;
 use reqwest::Client;
 use serde::Deserialize;
 use serde_json::Value;
-use tracing::{info, instrument};
+use tracing::{info, instrument, warn};
 
 const TWITCH_OAUTH_URL: &str = "https://id.twitch.tv/oauth2/token";
+const TWITCH_OAUTH_VALIDATE_URL: &str = "https://id.twitch.tv/oauth2/validate";
 const TWITCH_HELIX_USERS_URL: &str = "https://api.twitch.tv/helix/users";
 const TWITCH_HELIX_STREAMS_URL: &str = "https://api.twitch.tv/helix/streams";
+const TWITCH_HELIX_VIDEOS_URL: &str = "https://api.twitch.tv/helix/videos";
 const TWITCH_EVENTSUB_URL: &str = "https://api.twitch.tv/helix/eventsub/subscriptions";
 
+/// EventSub events every tracked channel is subscribed to. Raids take two
+/// `channel.raid` subscriptions, for raids out of the channel and into it,
+/// told apart by which side of the raid the condition names.
+pub const CHANNEL_EVENTS: [&str; 5] = [
+    "stream.online",
+    "channel.update",
+    "stream.offline",
+    RAID_OUT_EVENT,
+    RAID_IN_EVENT,
+];
+pub const RAID_OUT_EVENT: &str = "channel.raid.from";
+pub const RAID_IN_EVENT: &str = "channel.raid.to";
+const RAID_TYPE: &str = "channel.raid";
+/// EventSub events of channels that follow Hype Trains, which Twitch only
+/// allows once the broadcaster granted `channel:read:hype_train`.
+pub const HYPE_TRAIN_EVENTS: [&str; 2] = ["channel.hype_train.begin", "channel.hype_train.end"];
+
 const STREAM_FETCH_RETRY_DELAY_SECS: &[u64; 5] = &[15, 30, 60, 120, 300];
 
 fn truncate(s: &str, max: usize) -> String {
@@ -23,6 +42,18 @@ fn truncate(s: &str, max: usize) -> String {
     }
 }
 
+/// Twitch failures callers react to specifically; anything else stays an
+/// opaque `anyhow::Error`.
+#[derive(thiserror::Error, Debug)]
+pub enum TwitchError {
+    #[error("No Twitch user found for `{0}`")]
+    UserNotFound(String),
+    #[error("Twitch API rate limit exceeded")]
+    RateLimited,
+    #[error("EventSub subscription quota exceeded")]
+    QuotaExceeded,
+}
+
 #[derive(Deserialize)]
 pub struct StreamsResponse {
     pub data: Vec<TwitchStream>,
@@ -38,6 +69,11 @@ pub struct TwitchStream {
     pub game_name: String,
     pub title: String,
     pub started_at: DateTime<Utc>,
+    #[serde(default)]
+    pub viewer_count: u32,
+    /// Preview image URL with `{width}` and `{height}` placeholders.
+    #[serde(default)]
+    pub thumbnail_url: String,
 }
 
 #[derive(Deserialize)]
@@ -54,14 +90,56 @@ pub struct TwitchChannel {
     pub profile_image_url: String,
 }
 
+#[derive(Deserialize)]
+pub struct VideosResponse {
+    data: Vec<TwitchVideo>,
+}
+
+#[derive(Deserialize)]
+pub struct TwitchVideo {
+    pub id: String,
+    /// The stream an archive was recorded from; absent for other videos.
+    pub stream_id: Option<String>,
+    pub url: String,
+}
+
 #[derive(Deserialize)]
 pub struct TokenResponse {
     pub access_token: String,
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Default)]
 pub struct SubscriptionCondition {
+    #[serde(default)]
     pub broadcaster_user_id: String,
+    #[serde(default)]
+    pub from_broadcaster_user_id: String,
+    #[serde(default)]
+    pub to_broadcaster_user_id: String,
+}
+
+impl SubscriptionCondition {
+    /// The channel the subscription is for, whichever side of a raid it's on.
+    pub fn user_id(&self) -> &str {
+        [
+            &self.broadcaster_user_id,
+            &self.from_broadcaster_user_id,
+            &self.to_broadcaster_user_id,
+        ]
+        .into_iter()
+        .find(|id| !id.is_empty())
+        .map_or("", |id| id.as_str())
+    }
+
+    /// A subscription of type `kind` with this condition as one of
+    /// [`CHANNEL_EVENTS`].
+    pub fn event<'a>(&self, kind: &'a str) -> &'a str {
+        match kind {
+            RAID_TYPE if !self.from_broadcaster_user_id.is_empty() => RAID_OUT_EVENT,
+            RAID_TYPE if !self.to_broadcaster_user_id.is_empty() => RAID_IN_EVENT,
+            kind => kind,
+        }
+    }
 }
 
 #[derive(Deserialize, Debug)]
@@ -69,11 +147,19 @@ pub struct Subscription {
     pub id: String,
     pub status: String,
     pub condition: SubscriptionCondition,
+    pub created_at: DateTime<Utc>,
 
     #[serde(rename = "type")]
     pub kind: String,
 }
 
+impl Subscription {
+    /// The subscription as one of [`CHANNEL_EVENTS`].
+    pub fn event(&self) -> &str {
+        self.condition.event(&self.kind)
+    }
+}
+
 #[derive(Deserialize, Debug)]
 pub struct Pagination {
     pub cursor: Option<String>,
@@ -85,6 +171,39 @@ pub struct SubscriptionResponse {
     pub pagination: Pagination,
 }
 
+#[derive(Debug, Clone)]
+pub struct SubscriptionChange {
+    pub broadcaster_user_id: String,
+    pub kind: String,
+}
+
+impl SubscriptionChange {
+    fn new(broadcaster_user_id: &str, kind: &str) -> Self {
+        Self {
+            broadcaster_user_id: broadcaster_user_id.to_string(),
+            kind: kind.to_string(),
+        }
+    }
+}
+
+/// Outcome of [`TwitchAPI::sync`].
+#[derive(Debug, Default)]
+pub struct SyncReport {
+    pub added: Vec<SubscriptionChange>,
+    pub removed: Vec<SubscriptionChange>,
+    /// Subscriptions that were not enabled (e.g. failed verification) and got deleted.
+    pub stale: Vec<SubscriptionChange>,
+    pub failed: Vec<(SubscriptionChange, String)>,
+    pub kept: usize,
+}
+
+impl SyncReport {
+    fn fail(&mut self, change: SubscriptionChange, error: anyhow::Error) {
+        warn!(?change, error = ?error, "Failed to reconcile EventSub subscription");
+        self.failed.push((change, format!("{error:#}")));
+    }
+}
+
 #[instrument(skip_all)]
 async fn get_access_token(client_id: &str, client_secret: &str) -> anyhow::Result<String> {
     let resp = Client::new()
@@ -151,65 +270,111 @@ impl TwitchAPI {
             .text()
             .await
             .unwrap_or_else(|e| format!("(failed to read body: {e})"));
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(anyhow::Error::new(TwitchError::RateLimited).context(ctx));
+        }
         if !status.is_success() {
             anyhow::bail!("{ctx}: Twitch {status}: {}", truncate(&body, 256));
         }
         serde_json::from_str::<T>(&body).context(ctx)
     }
 
-    pub async fn sync(&self, channels: &[String]) -> anyhow::Result<()> {
+    #[instrument(skip(self))]
+    pub async fn validate_token(&self) -> anyhow::Result<()> {
+        self.http_client
+            .get(TWITCH_OAUTH_VALIDATE_URL)
+            .header("Authorization", format!("OAuth {}", self.access_token))
+            .send()
+            .await
+            .context("Failed to validate OAuth token")?
+            .error_for_status()
+            .context("Twitch rejected the OAuth token")?;
+        Ok(())
+    }
+
+    /// Reconciles EventSub subscriptions with `channels`: drops subscriptions
+    /// that are not enabled, subscribes missing event types and unsubscribes
+    /// channels that are no longer tracked. Those of `hype_trains` are also
+    /// subscribed to Hype Train events.
+    pub async fn sync(
+        &self,
+        channels: &[String],
+        hype_trains: &[String],
+    ) -> anyhow::Result<SyncReport> {
         let (subs, stale): (Vec<Subscription>, Vec<Subscription>) = self
             .get_subscriptions(None)
             .await?
             .into_iter()
             .partition(|sub| sub.status == "enabled");
-        futures::future::join_all(stale.iter().map(|sub| self.unsubscribe(&sub.id))).await;
+        let mut report = SyncReport::default();
+
+        let results =
+            futures::future::join_all(stale.iter().map(|sub| self.unsubscribe(&sub.id))).await;
+        for (sub, result) in stale.iter().zip(results) {
+            let change = SubscriptionChange::new(sub.condition.user_id(), sub.event());
+            match result {
+                Ok(()) => report.stale.push(change),
+                Err(e) => report.fail(change, e),
+            }
+        }
 
         let have: HashMap<(&str, &str), &str> = subs
             .iter()
-            .map(|e| {
-                (
-                    (e.condition.broadcaster_user_id.as_str(), e.kind.as_str()),
-                    e.id.as_str(),
-                )
-            })
+            .map(|e| ((e.condition.user_id(), e.event()), e.id.as_str()))
             .collect();
         let want: HashSet<(&str, &str)> = channels
             .iter()
-            .flat_map(|c| {
-                [
-                    (c.as_str(), "stream.online"),
-                    (c.as_str(), "channel.update"),
-                    (c.as_str(), "stream.offline"),
-                ]
-            })
+            .flat_map(|c| CHANNEL_EVENTS.map(|kind| (c.as_str(), kind)))
+            .chain(
+                hype_trains
+                    .iter()
+                    .filter(|c| channels.contains(c))
+                    .flat_map(|c| HYPE_TRAIN_EVENTS.map(|kind| (c.as_str(), kind))),
+            )
             .collect();
 
         let add = want
             .iter()
             .filter(|e| !have.contains_key(e))
             .collect::<Vec<_>>();
-        futures::future::join_all(
+        let results = futures::future::join_all(
             add.iter()
                 .map(|(channel, event)| self.subscribe(event, channel)),
         )
         .await;
+        for ((channel, event), result) in add.iter().zip(results) {
+            let change = SubscriptionChange::new(channel, event);
+            match result {
+                Ok(_) => report.added.push(change),
+                Err(e) => report.fail(change, e),
+            }
+        }
 
         let remove = have
             .iter()
             .filter(|e| !want.contains(e.0))
-            .map(|e| e.1)
             .collect::<Vec<_>>();
-        futures::future::join_all(remove.iter().map(|id| self.unsubscribe(id))).await;
+        let results =
+            futures::future::join_all(remove.iter().map(|(_, id)| self.unsubscribe(id))).await;
+        for (((channel, event), _), result) in remove.iter().zip(results) {
+            let change = SubscriptionChange::new(channel, event);
+            match result {
+                Ok(()) => report.removed.push(change),
+                Err(e) => report.fail(change, e),
+            }
+        }
+        report.kept = have.len() - remove.len();
 
         info!(
-            "Twitch webhooks synchronized for {} channels: {} added, {} removed, {} kept",
+            "Twitch webhooks synchronized for {} channels: {} added, {} removed, {} stale, {} kept, {} failed",
             channels.len(),
-            add.len(),
-            remove.len(),
-            have.len() - remove.len()
+            report.added.len(),
+            report.removed.len(),
+            report.stale.len(),
+            report.kept,
+            report.failed.len(),
         );
-        Ok(())
+        Ok(report)
     }
 
     #[instrument(skip(self))]
@@ -225,7 +390,7 @@ impl TwitchAPI {
         resp.data
             .into_iter()
             .next()
-            .ok_or_else(|| anyhow::anyhow!("No user found for id: {}", user_id))
+            .ok_or_else(|| TwitchError::UserNotFound(user_id.to_string()).into())
     }
 
     #[instrument(skip(self))]
@@ -237,6 +402,7 @@ impl TwitchAPI {
         };
 
         let mut last_error: Option<anyhow::Error> = None;
+        #[allow(clippy::needless_range_loop)]
         for attempt in 0..=attempts {
             match self
                 .send_json::<StreamsResponse>(
@@ -292,6 +458,27 @@ impl TwitchAPI {
         Ok(streams)
     }
 
+    /// The archived VOD of `stream_id`, if Twitch has published it yet.
+    #[instrument(skip(self))]
+    pub async fn get_archive_vod(
+        &self,
+        user_id: &str,
+        stream_id: &str,
+    ) -> anyhow::Result<Option<TwitchVideo>> {
+        let resp: VideosResponse = self
+            .send_json(
+                self.authenticated_request(reqwest::Method::GET, TWITCH_HELIX_VIDEOS_URL)
+                    .query(&[("user_id", user_id), ("type", "archive"), ("first", "5")]),
+                "fetch archive videos by user_id",
+            )
+            .await?;
+
+        Ok(resp
+            .data
+            .into_iter()
+            .find(|video| video.stream_id.as_deref() == Some(stream_id)))
+    }
+
     #[instrument(skip(self))]
     pub async fn get_channel_by_name(&self, username: &str) -> anyhow::Result<TwitchChannel> {
         let resp: ChannelsResponse = self
@@ -305,15 +492,32 @@ impl TwitchAPI {
         resp.data
             .into_iter()
             .next()
-            .ok_or_else(|| anyhow::anyhow!("No user found for username: {}", username))
+            .ok_or_else(|| TwitchError::UserNotFound(username.to_string()).into())
     }
 
     #[instrument(skip(self))]
     pub async fn subscribe(&self, event: &str, user_id: &str) -> anyhow::Result<Value> {
+        let (kind, condition) = match event {
+            RAID_OUT_EVENT => (
+                RAID_TYPE,
+                serde_json::json!({ "from_broadcaster_user_id": user_id }),
+            ),
+            RAID_IN_EVENT => (
+                RAID_TYPE,
+                serde_json::json!({ "to_broadcaster_user_id": user_id }),
+            ),
+            _ => (event, serde_json::json!({ "broadcaster_user_id": user_id })),
+        };
+        // Version 1 of the Hype Train events is deprecated.
+        let version = if HYPE_TRAIN_EVENTS.contains(&kind) {
+            "2"
+        } else {
+            "1"
+        };
         let payload = serde_json::json!({
-            "type": event,
-            "version": "1",
-            "condition": { "broadcaster_user_id": user_id },
+            "type": kind,
+            "version": version,
+            "condition": condition,
             "transport": {
                 "method":   "webhook",
                 "callback": format!("https://{}/webhook/twitch", &self.webhook_url),
@@ -328,22 +532,53 @@ impl TwitchAPI {
                     .json(&payload),
                 "create subscription",
             )
-            .await?;
+            .await
+            .map_err(|e| {
+                // On subscription creation a 429 means the EventSub cost limit
+                // was hit rather than the request rate limit.
+                if matches!(e.downcast_ref(), Some(TwitchError::RateLimited)) {
+                    anyhow::Error::new(TwitchError::QuotaExceeded).context("create subscription")
+                } else {
+                    e
+                }
+            })?;
         Ok(resp)
     }
 
     #[instrument(skip(self))]
     pub async fn subscribe_channel(&self, user_id: &str) -> anyhow::Result<()> {
-        futures::try_join!(
-            self.subscribe("stream.online", user_id),
-            self.subscribe("channel.update", user_id),
-            self.subscribe("stream.offline", user_id),
-        )?;
+        try_join_all(
+            CHANNEL_EVENTS
+                .iter()
+                .map(|kind| self.subscribe(kind, user_id)),
+        )
+        .await?;
 
         info!("Subscription created for user_id: {}", user_id);
         Ok(())
     }
 
+    /// Subscribes `user_id` to Hype Train events, or drops those
+    /// subscriptions when `enabled` is false.
+    #[instrument(skip(self))]
+    pub async fn subscribe_hype_train(&self, user_id: &str, enabled: bool) -> anyhow::Result<()> {
+        let have: Vec<Subscription> = self
+            .get_subscriptions(Some(user_id))
+            .await?
+            .into_iter()
+            .filter(|sub| HYPE_TRAIN_EVENTS.contains(&sub.kind.as_str()))
+            .collect();
+        if enabled {
+            let missing = HYPE_TRAIN_EVENTS
+                .iter()
+                .filter(|kind| !have.iter().any(|sub| sub.kind == **kind));
+            try_join_all(missing.map(|kind| self.subscribe(kind, user_id))).await?;
+        } else {
+            try_join_all(have.iter().map(|sub| self.unsubscribe(&sub.id))).await?;
+        }
+        Ok(())
+    }
+
     #[instrument(skip(self))]
     pub async fn unsubscribe(&self, subscription_id: &str) -> anyhow::Result<()> {
         self.authenticated_request(reqwest::Method::DELETE, TWITCH_EVENTSUB_URL)
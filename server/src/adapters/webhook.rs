@@ -1,5 +1,8 @@
-use crate::adapters::db;
-use crate::adapters::twitch::TwitchStream;
+use crate::adapters::db::{self, NotificationAction};
+use crate::adapters::twitch::{
+    SubscriptionCondition, TwitchStream, HYPE_TRAIN_EVENTS, RAID_OUT_EVENT,
+};
+use crate::utils::quiet_hours::QuietHours;
 use crate::utils::ttl_set;
 use axum::{
     body::Bytes,
@@ -10,14 +13,17 @@ use axum::{
     routing, BoxError, Router,
 };
 use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
 use dashmap::{DashMap, Entry};
 use futures::stream::{self, StreamExt};
 use hex;
 use hmac::{digest::Key, Hmac, Mac};
 use serde::Deserialize;
-use serenity::all::{EditMessage, MessageId};
+use serenity::all::{
+    CreateActionRow, CreateAllowedMentions, CreateButton, EditMessage, MessageId, Nonce, RoleId,
+};
 use serenity::{
-    all::{CreateEmbed, CreateMessage, Message},
+    all::{CreateEmbed, CreateMessage},
     http::Http as DiscordHttp,
     model::{colour, id::ChannelId},
 };
@@ -27,7 +33,9 @@ use std::{
     time::Duration,
 };
 use std::{collections::HashMap, sync::Arc};
-use tokio::sync::Mutex;
+use tokio::sync::{broadcast, Mutex};
+use tokio::time::MissedTickBehavior;
+use tokio_util::sync::CancellationToken;
 use tower::ServiceBuilder;
 use tower_governor::{
     governor::GovernorConfigBuilder, key_extractor::SmartIpKeyExtractor, GovernorLayer,
@@ -37,8 +45,11 @@ use tracing::{error, info, instrument, warn};
 const SIGNATURE_PREFIX: &str = "sha256=";
 const WEBHOOK_VERIFICATION_TYPE: &str = "webhook_callback_verification";
 const NOTIFICATION_TYPE: &str = "notification";
+const REVOCATION_TYPE: &str = "revocation";
 const MAX_TIMESTAMP_AGE_SECONDS: u64 = 600;
 const MAX_FUTURE_TIMESTAMP_SECONDS: u64 = 180;
+/// How long a message ID is remembered to reject Twitch's retries of it.
+const MESSAGE_ID_TTL: Duration = Duration::from_secs(10 * 60);
 
 const HEADER_SIGNATURE: &str = "Twitch-Eventsub-Message-Signature";
 const HEADER_TIMESTAMP: &str = "Twitch-Eventsub-Message-Timestamp";
@@ -47,6 +58,34 @@ const HEADER_MESSAGE_TYPE: &str = "Twitch-Eventsub-Message-Type";
 
 const CONCURRENCY_LIMIT: usize = 40;
 const MAX_BODY_BYTES: usize = 64 * 1024;
+const EVENT_CHANNEL_CAPACITY: usize = 256;
+/// Seconds to wait before each look for a finished stream's VOD, which
+/// Twitch publishes with some delay.
+const VOD_RETRY_DELAY_SECS: [u64; 4] = [60, 120, 300, 600];
+/// How often the viewer counts of live streams are sampled.
+const VIEWER_POLL_INTERVAL: Duration = Duration::from_secs(5 * 60);
+/// Raids listed in a stream's embeds, the latest ones.
+const MAX_RAID_FIELDS: usize = 5;
+/// How often messages left in the outbox are retried.
+const OUTBOX_RETRY_INTERVAL: Duration = Duration::from_secs(30);
+/// Attempts at posting an outbox message before it's given up on.
+const MAX_OUTBOX_ATTEMPTS: i32 = 5;
+/// Size of the stream preview in live embeds.
+const PREVIEW_SIZE: (u32, u32) = (1280, 720);
+/// How long Discord may keep showing a cached stream preview. Twitch serves
+/// every preview of a stream from the same URL, so the URL changes this often.
+const PREVIEW_REFRESH_SECS: i64 = 5 * 60;
+
+/// Stored in `streams.message_id` for streams without a Discord message,
+/// like those of muted channels.
+const NO_MESSAGE: i64 = 0;
+const LIVE_COLOR: (u8, u8, u8) = (145, 70, 255);
+const ENDED_COLOR: (u8, u8, u8) = (128, 128, 128);
+const ALERT_COLOR: (u8, u8, u8) = (231, 76, 60);
+
+/// Revocation statuses after which subscribing again can't work: the user
+/// is gone or Twitch dropped the subscription version.
+const FINAL_REVOCATIONS: [&str; 2] = ["user_removed", "version_removed"];
 
 #[derive(thiserror::Error, Debug)]
 pub enum WebhookError {
@@ -141,8 +180,46 @@ pub struct ChannelUpdateEvent {
 pub struct Subscription {
     #[serde(rename = "type")]
     pub kind: String,
+    #[serde(default)]
+    pub condition: SubscriptionCondition,
+}
+
+/// `channel.hype_train.begin` or `.end`, with the fields both share.
+#[derive(Deserialize, Debug)]
+pub struct HypeTrainEvent {
+    pub broadcaster_user_id: String,
+    pub broadcaster_user_name: String,
+    pub level: i64,
 }
 
+#[derive(Deserialize, Debug)]
+pub struct RaidEvent {
+    pub from_broadcaster_user_id: String,
+    pub from_broadcaster_user_login: String,
+    pub from_broadcaster_user_name: String,
+    pub to_broadcaster_user_id: String,
+    pub to_broadcaster_user_login: String,
+    pub to_broadcaster_user_name: String,
+    pub viewers: i64,
+}
+
+/// The subscription a `revocation` message is about.
+#[derive(Deserialize, Debug)]
+pub struct RevokedSubscription {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Why Twitch revoked it, e.g. `notification_failures_exceeded`.
+    pub status: String,
+    pub condition: SubscriptionCondition,
+}
+
+#[derive(Deserialize, Debug)]
+struct Revocation {
+    subscription: RevokedSubscription,
+}
+
+#[derive(Clone)]
 pub struct Stream {
     pub id: String,
     pub channel_id: String,
@@ -157,25 +234,96 @@ pub struct Stream {
     pub started_at: chrono::DateTime<Utc>,
     pub last_updated: chrono::DateTime<Utc>,
 
-    pub message_id: i64,
+    /// One per Discord channel posted to; none for muted channels.
+    pub messages: Vec<db::StreamMessage>,
     pub profile_image_url: String,
+    /// Helix preview image template, see [`preview_url`].
+    pub thumbnail_url: String,
+    /// The latest Hype Train, for channels that follow them.
+    pub hype_train: Option<HypeTrain>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HypeTrain {
+    pub level: i64,
+    pub ended: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StreamEventKind {
+    Online,
+    Offline,
+    Update,
+}
+
+/// A stream lifecycle change, published to subscribers of [`TwitchWebhook::subscribe`].
+#[derive(Clone, Debug)]
+pub struct StreamEvent {
+    pub kind: StreamEventKind,
+    pub channel_id: String,
+    pub user_login: String,
+    pub user_name: String,
+    pub stream_id: String,
+    pub title: String,
+    pub category: String,
+    pub started_at: DateTime<Utc>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl StreamEvent {
+    fn new(kind: StreamEventKind, stream: &Stream, timestamp: DateTime<Utc>) -> Self {
+        Self {
+            kind,
+            channel_id: stream.channel_id.clone(),
+            user_login: stream.user_login.clone(),
+            user_name: stream.user_name.clone(),
+            stream_id: stream.id.clone(),
+            title: stream.title.clone(),
+            category: stream.category.clone(),
+            started_at: stream.started_at,
+            timestamp,
+        }
+    }
+}
+
+/// A stream that went offline and is only finished once the grace period
+/// passes without the channel coming back.
+struct Ending {
+    stream: Arc<Mutex<Stream>>,
+    offline_at: DateTime<Utc>,
+    timer: tokio::task::AbortHandle,
+}
+
+/// An outbox message that made it to Discord.
+struct Delivered {
+    channel_id: String,
+    stream_id: String,
+    message: db::StreamMessage,
 }
 
 pub struct TwitchWebhook {
     key: Key<Hmac<Sha256>>,
     port: u16,
+    offline_grace: Duration,
+    flap_window: Duration,
+    quiet_hours: Vec<QuietHours>,
+    timezone: Tz,
 
     api: Arc<super::twitch::TwitchAPI>,
     pool: sqlx::PgPool,
     recent_messages: ttl_set::TtlSet,
     streams: DashMap<String, Arc<Mutex<Stream>>>,
+    ending: DashMap<String, Ending>,
 
     tasks: Mutex<tokio::task::JoinSet<()>>,
+    shutdown: CancellationToken,
 
     channels: DashMap<String, db::Channel>,
 
     discord_http: Arc<DiscordHttp>,
-    discord_channel: ChannelId,
+    discord_channels: Vec<ChannelId>,
+
+    events: broadcast::Sender<StreamEvent>,
 }
 
 impl TwitchWebhook {
@@ -186,24 +334,72 @@ impl TwitchWebhook {
         pool: sqlx::PgPool,
         channels: Vec<db::Channel>,
         discord_http: Arc<DiscordHttp>,
-        discord_channel: ChannelId,
+        discord_channels: Vec<ChannelId>,
     ) -> Result<Self> {
         let webhook = Self {
             key: Key::<Hmac<Sha256>>::clone_from_slice(secret.as_bytes()),
             port,
+            offline_grace: Duration::ZERO,
+            flap_window: Duration::ZERO,
+            quiet_hours: Vec::new(),
+            timezone: Tz::UTC,
             api,
             pool,
             recent_messages: ttl_set::TtlSet::new(),
             streams: DashMap::new(),
+            ending: DashMap::new(),
             tasks: Mutex::new(tokio::task::JoinSet::new()),
+            shutdown: CancellationToken::new(),
             channels: DashMap::from_iter(channels.into_iter().map(|c| (c.channel_id.clone(), c))),
             discord_http,
-            discord_channel,
+            discord_channels,
+            events: broadcast::channel(EVENT_CHANNEL_CAPACITY).0,
         };
         webhook.load_streams().await?;
         Ok(webhook)
     }
 
+    /// Waits `grace` before finishing a stream that went offline, continuing
+    /// it should the channel come back in the meantime.
+    pub(crate) fn with_offline_grace(mut self, grace: Duration) -> Self {
+        self.offline_grace = grace;
+        self
+    }
+
+    /// Treats streams that go offline within `window` of going live as
+    /// flaps, like an encoder restart: they wait at least `window` for the
+    /// channel to come back, and are dropped rather than recorded if it
+    /// doesn't.
+    pub(crate) fn with_flap_window(mut self, window: Duration) -> Self {
+        self.flap_window = window;
+        self
+    }
+
+    fn is_flap(&self, started_at: DateTime<Utc>, offline_at: DateTime<Utc>) -> bool {
+        is_flap(self.flap_window, started_at, offline_at)
+    }
+
+    /// Posts live messages without mentions during `quiet_hours`, read in
+    /// `timezone` local time.
+    pub(crate) fn with_quiet_hours(mut self, quiet_hours: Vec<QuietHours>, timezone: Tz) -> Self {
+        self.quiet_hours = quiet_hours;
+        self.timezone = timezone;
+        self
+    }
+
+    /// Stops background work that can wait on Twitch for long, like looking
+    /// for VODs, once `shutdown` fires.
+    pub(crate) fn with_shutdown(mut self, shutdown: CancellationToken) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
+    fn is_quiet(&self, channel: ChannelId, at: DateTime<Utc>) -> bool {
+        self.quiet_hours
+            .iter()
+            .any(|quiet| quiet.contains(channel.get(), at, self.timezone))
+    }
+
     pub(crate) async fn track_channel(&self, user_id: &str, channel: db::Channel) -> Result<()> {
         self.channels.insert(channel.channel_id.clone(), channel);
         if let Ok(stream) = self.api.get_stream(user_id, false).await {
@@ -220,17 +416,148 @@ impl TwitchWebhook {
 
     pub(crate) async fn untrack_channel(&self, channel_id: &str) -> Result<()> {
         self.channels.remove(channel_id);
-        if let Some((_, stream)) = self.streams.remove(channel_id) {
+        let stream = match self.streams.remove(channel_id) {
+            Some((_, stream)) => Some(stream),
+            None => self.take_ending(channel_id).map(|ending| ending.stream),
+        };
+        if let Some(stream) = stream {
             let stream = stream.lock().await;
-            self.delete_discord(stream.message_id).await?;
+            self.delete_discord(channel_id, &stream.messages).await?;
             db::delete_stream(&self.pool, &stream.id).await?;
         }
         Ok(())
     }
 
+    /// Stops notifying for `channel` while keeping it tracked. A live stream is
+    /// closed out as if it had gone offline.
+    pub(crate) async fn pause_channel(&self, channel: db::Channel) -> Result<()> {
+        let channel_id = channel.channel_id.clone();
+        self.channels.insert(channel_id.clone(), channel);
+        if let Some(ending) = self.take_ending(&channel_id) {
+            return self.finish_stream(ending.stream, ending.offline_at).await;
+        }
+        self.end_stream(&channel_id, Utc::now()).await
+    }
+
+    /// Stops waiting for the channel's stream to come back, handing it over
+    /// to be finished or dropped.
+    fn take_ending(&self, channel_id: &str) -> Option<Ending> {
+        let (_, ending) = self.ending.remove(channel_id)?;
+        ending.timer.abort();
+        Some(ending)
+    }
+
+    /// Finishes every stream still in its grace period, as happens on
+    /// shutdown when nothing would be left to finish them later.
+    async fn finish_ending(&self) {
+        let channel_ids: Vec<String> = self.ending.iter().map(|e| e.key().clone()).collect();
+        for channel_id in channel_ids {
+            if let Some(ending) = self.take_ending(&channel_id) {
+                if let Err(e) = self.finish_stream(ending.stream, ending.offline_at).await {
+                    error!("Error finishing stream of {channel_id}: {e:?}");
+                }
+            }
+        }
+    }
+
+    pub(crate) fn subscribe(&self) -> broadcast::Receiver<StreamEvent> {
+        self.events.subscribe()
+    }
+
+    fn publish(&self, event: StreamEvent) {
+        // Sending only fails when nobody is subscribed.
+        let _ = self.events.send(event);
+    }
+
+    pub(crate) async fn live_stream(&self, channel_id: &str) -> Option<Stream> {
+        let stream = self
+            .streams
+            .get(channel_id)
+            .map(|s| Arc::clone(s.value()))?;
+        let stream = stream.lock().await;
+        Some(stream.clone())
+    }
+
+    pub(crate) fn live_channel_ids(&self) -> Vec<String> {
+        self.streams.iter().map(|s| s.key().clone()).collect()
+    }
+
+    pub(crate) fn live_stream_count(&self) -> usize {
+        self.streams.len()
+    }
+
+    pub(crate) async fn live_streams(&self) -> Vec<Stream> {
+        let streams: Vec<_> = self.streams.iter().map(|s| Arc::clone(s.value())).collect();
+        let mut live = Vec::with_capacity(streams.len());
+        for stream in streams {
+            live.push(stream.lock().await.clone());
+        }
+        live
+    }
+
+    /// Samples the viewer counts of every live stream until `cancel` fires,
+    /// for the averages and peaks of stream summaries and stats.
+    pub(crate) async fn poll_viewers(self: Arc<Self>, cancel: CancellationToken) {
+        let mut ticker = tokio::time::interval(VIEWER_POLL_INTERVAL);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                _ = cancel.cancelled() => break,
+                _ = ticker.tick() => {}
+            }
+            if let Err(e) = self.sample_viewers().await {
+                warn!("Failed to sample viewer counts: {e:#}");
+            }
+        }
+    }
+
+    /// Retries messages left in the outbox until `cancel` fires, starting
+    /// with those a previous run didn't get to post.
+    pub(crate) async fn poll_outbox(self: Arc<Self>, cancel: CancellationToken) {
+        let mut ticker = tokio::time::interval(OUTBOX_RETRY_INTERVAL);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                _ = cancel.cancelled() => break,
+                _ = ticker.tick() => {}
+            }
+            match self.deliver_outbox(None).await {
+                Ok(delivered) => self.attach_messages(delivered).await,
+                Err(e) => warn!("Failed to deliver outbox messages: {e}"),
+            }
+        }
+    }
+
+    async fn sample_viewers(&self) -> anyhow::Result<()> {
+        let channel_ids = self.live_channel_ids();
+        if channel_ids.is_empty() {
+            return Ok(());
+        }
+        let live = self.api.get_streams(&channel_ids).await?;
+        let mut counts = Vec::with_capacity(live.len());
+        for twitch in live {
+            let Some(stream) = self
+                .streams
+                .get(&twitch.user_id)
+                .map(|s| Arc::clone(s.value()))
+            else {
+                continue;
+            };
+            // Counts go to the stream as recorded, which a resumed stream
+            // keeps even though Twitch started a new one.
+            let stream_id = stream.lock().await.id.clone();
+            counts.push((stream_id, twitch.viewer_count as i32));
+        }
+        db::record_viewer_counts(&self.pool, &counts, Utc::now()).await
+    }
+
     #[instrument(skip(self))]
     async fn load_streams(&self) -> Result<()> {
-        let channels = db::list_channels(&self.pool).await?;
+        let channels: Vec<_> = db::list_channels(&self.pool)
+            .await?
+            .into_iter()
+            .filter(|c| !c.paused)
+            .collect();
         if channels.is_empty() {
             return Ok(());
         }
@@ -290,10 +617,7 @@ impl TwitchWebhook {
     fn verify(&self, headers: &HeaderMap, body: &[u8]) -> Result<DateTime<Utc>> {
         let (raw_signature, timestamp_str, message_id) = self.signature_headers(headers)?;
 
-        if !self
-            .recent_messages
-            .insert(message_id, tokio::time::Duration::from_secs(10 * 60))
-        {
+        if !self.recent_messages.insert(message_id, MESSAGE_ID_TTL) {
             return Err(WebhookError::DuplicateMessageId(message_id.to_string()));
         }
 
@@ -346,14 +670,51 @@ impl TwitchWebhook {
         Ok(timestamp)
     }
 
+    /// Rejects messages already handled before a restart, which the
+    /// in-memory check in [`Self::verify`] can't know about.
+    async fn claim_message_id(&self, message_id: &str) -> Result<()> {
+        let ttl = chrono::Duration::from_std(MESSAGE_ID_TTL).expect("TTL is in range");
+        if !db::claim_message_id(&self.pool, message_id, ttl).await? {
+            return Err(WebhookError::DuplicateMessageId(message_id.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Lets Twitch's retry of a message that failed to be handled through.
+    async fn release_message_id(&self, message_id: &str) {
+        self.recent_messages.remove(message_id);
+        if let Err(e) = db::release_message_id(&self.pool, message_id).await {
+            warn!("Failed to release message ID {message_id}: {e:#}");
+        }
+    }
+
+    /// Deletes expired message IDs from the database until `cancel` fires.
+    pub(crate) async fn prune_message_ids(self: Arc<Self>, cancel: CancellationToken) {
+        let mut ticker = tokio::time::interval(MESSAGE_ID_TTL);
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                _ = cancel.cancelled() => break,
+                _ = ticker.tick() => {}
+            }
+            if let Err(e) = db::prune_message_ids(&self.pool).await {
+                warn!("Failed to prune message IDs: {e:#}");
+            }
+        }
+    }
+
     fn handle_challenge(&self, body: &Bytes) -> Result<String> {
         let payload = json::<ChallengePayload>(body)?;
         Ok(payload.challenge)
     }
 
+    /// Handles a notification. A stream going online is handled in the
+    /// background, releasing `message_id` should that fail so Twitch's retry
+    /// gets through.
     async fn handle_notification(
         self: &Arc<Self>,
         body: &Bytes,
+        message_id: &str,
         timestamp: DateTime<Utc>,
     ) -> Result<()> {
         #[derive(Deserialize, Debug)]
@@ -372,6 +733,7 @@ impl TwitchWebhook {
                 let Notification { event } = json::<Notification<OnlineEvent>>(body)?;
                 let webhook = Arc::clone(self);
                 let user_id = event.broadcaster_user_id.clone();
+                let message_id = message_id.to_string();
                 {
                     let mut tasks = self.tasks.lock().await;
                     tasks.spawn(async move {
@@ -380,6 +742,7 @@ impl TwitchWebhook {
                             .await
                         {
                             error!("Error handling stream online: {e:?}");
+                            webhook.release_message_id(&message_id).await;
                         }
                     });
                 }
@@ -392,6 +755,16 @@ impl TwitchWebhook {
                 let Notification { event } = json::<Notification<ChannelUpdateEvent>>(body)?;
                 self.handle_channel_update(&event, timestamp).await?;
             }
+            "channel.raid" => {
+                let Notification { event } = json::<Notification<RaidEvent>>(body)?;
+                let outgoing = subscription.condition.event(&subscription.kind) == RAID_OUT_EVENT;
+                self.handle_raid(&event, outgoing, timestamp).await?;
+            }
+            kind @ ("channel.hype_train.begin" | "channel.hype_train.end") => {
+                let Notification { event } = json::<Notification<HypeTrainEvent>>(body)?;
+                let ended = kind == "channel.hype_train.end";
+                self.handle_hype_train(&event, ended, timestamp).await?;
+            }
             _ => {
                 warn!("Unknown notification type: {}", subscription.kind);
             }
@@ -399,6 +772,84 @@ impl TwitchWebhook {
         Ok(())
     }
 
+    /// Answers a revocation right away and restores the subscription in the
+    /// background, since Twitch stops sending its events for good.
+    async fn handle_revocation(self: &Arc<Self>, body: &Bytes) -> Result<()> {
+        let Revocation { subscription } = json::<Revocation>(body)?;
+        warn!(
+            id = subscription.id,
+            status = subscription.status,
+            "EventSub {} subscription revoked for user: {}",
+            subscription.kind,
+            subscription.condition.broadcaster_user_id
+        );
+        let webhook = Arc::clone(self);
+        let mut tasks = self.tasks.lock().await;
+        tasks.spawn(async move {
+            if let Err(e) = webhook.resubscribe(&subscription).await {
+                error!("Error handling revocation: {e:?}");
+            }
+        });
+        Ok(())
+    }
+
+    /// Subscribes to a revoked event type again. Channels for which that
+    /// fails are flagged as broken and reported to Discord, so tracking
+    /// never stops unnoticed.
+    pub(crate) async fn resubscribe(&self, subscription: &RevokedSubscription) -> Result<()> {
+        let channel_id = subscription.condition.user_id();
+        let event = subscription.condition.event(&subscription.kind);
+        let Some(channel) = self.channels.get(channel_id).map(|c| c.value().clone()) else {
+            // Untracked since, so the subscription isn't wanted anymore.
+            return Ok(());
+        };
+        if channel.paused {
+            return Ok(());
+        }
+
+        let error = if FINAL_REVOCATIONS.contains(&subscription.status.as_str()) {
+            subscription.status.clone()
+        } else {
+            match self.api.subscribe(event, channel_id).await {
+                Ok(_) => {
+                    info!("Resubscribed to {} for user: {}", event, channel.name);
+                    if channel.broken_reason.is_some() {
+                        db::set_broken_reason(&self.pool, channel_id, None).await?;
+                        self.set_broken_reason(channel_id, None);
+                    }
+                    return Ok(());
+                }
+                Err(e) => format!("{}, resubscribing failed: {e:#}", subscription.status),
+            }
+        };
+        // Hype Trains need the broadcaster's consent, which they may take
+        // back without anything else about the channel breaking.
+        if HYPE_TRAIN_EVENTS.contains(&event) {
+            warn!("Stopped following Hype Trains of {}: {error}", channel.name);
+            return Ok(());
+        }
+
+        let reason = format!("{event} subscription revoked ({error})");
+        error!("Tracking of {} is broken: {reason}", channel.name);
+        db::set_broken_reason(&self.pool, channel_id, Some(&reason)).await?;
+        self.set_broken_reason(channel_id, Some(reason.clone()));
+        self.alert_discord(
+            &format!(
+                "Tracking of **{}** stopped",
+                display_name(&channel.display_name, &channel.name)
+            ),
+            &reason,
+        )
+        .await;
+        Ok(())
+    }
+
+    fn set_broken_reason(&self, channel_id: &str, reason: Option<String>) {
+        if let Some(mut channel) = self.channels.get_mut(channel_id) {
+            channel.broken_reason = reason;
+        }
+    }
+
     pub(crate) async fn handle_stream_online(
         &self,
         user_id: String,
@@ -447,6 +898,9 @@ impl TwitchWebhook {
             match entry {
                 Entry::Occupied(mut occ) => {
                     let stored = occ.get_mut();
+                    if stored.paused {
+                        return Ok(());
+                    }
                     if channel.login != stored.name || channel.display_name != stored.display_name {
                         stored.name = channel.login.clone();
                         stored.display_name = channel.display_name.clone();
@@ -463,54 +917,55 @@ impl TwitchWebhook {
             }
         }
 
+        if self.resume_stream(&channel.id, &stream, timestamp).await? {
+            info!(
+                "Stream resumed within the grace period for user: {}",
+                channel.display_name
+            );
+            return Ok(());
+        }
+
         info!("Stream online received for user: {}", channel.display_name);
 
-        let message_id = match preload.as_ref() {
-            Some(stream) => stream.message_id,
-            None => self
-                .message_discord(
-                    CreateMessage::new().embed(
-                        CreateEmbed::new()
-                            .title(format!(
-                                "**{}** is live!",
-                                display_name(&channel.display_name, &channel.login)
-                            ))
-                            .description(&stream.title)
-                            .thumbnail(&channel.profile_image_url)
-                            .color(colour::Color::from_rgb(145, 70, 255))
-                            .url(format!("https://twitch.tv/{}", &channel.login))
-                            .field(format!("**»** {}", &stream.game_name), "", true),
-                    ),
-                )
-                .await?
-                .id
-                .get() as i64,
+        let settings = db::get_channel_settings(&self.pool, &channel.id).await?;
+        if let Err(e) =
+            db::set_profile_image_url(&self.pool, &channel.id, &channel.profile_image_url).await
+        {
+            warn!("Failed to store profile image of {}: {e:#}", channel.login);
+        }
+        let mut live = Stream {
+            id: stream.id.clone(),
+            channel_id: channel.id.clone(),
+            user_login: channel.login.clone(),
+            user_name: channel.display_name.clone(),
+            title: stream.title.clone(),
+            category: stream.game_name.clone(),
+            started_at: stream.started_at,
+            last_updated: stream.started_at,
+            events: if let Some(stream) = preload.as_ref() {
+                stream.events.0.clone()
+            } else {
+                vec![db::UpdateEvent {
+                    title: stream.title.clone(),
+                    category: stream.game_name.clone(),
+                    timestamp,
+                    raid: None,
+                }]
+            },
+            messages: Vec::new(),
+            profile_image_url: channel.profile_image_url.clone(),
+            thumbnail_url: stream.thumbnail_url.clone(),
+            hype_train: None,
         };
-
-        self.streams.insert(
-            channel.id.clone(),
-            Arc::new(Mutex::new(Stream {
-                id: stream.id.clone(),
-                channel_id: channel.id.clone(),
-                user_login: channel.login.clone(),
-                user_name: channel.display_name.clone(),
-                title: stream.title.clone(),
-                category: stream.game_name.clone(),
-                started_at: stream.started_at,
-                last_updated: stream.started_at,
-                events: if let Some(stream) = preload.as_ref() {
-                    stream.events.0.clone()
-                } else {
-                    vec![db::UpdateEvent {
-                        title: stream.title.clone(),
-                        category: stream.game_name.clone(),
-                        timestamp,
-                    }]
-                },
-                message_id,
-                profile_image_url: channel.profile_image_url.clone(),
-            })),
-        );
+        let mut outbox = Vec::new();
+        match preload.as_ref() {
+            Some(stream) => live.messages = self.preloaded_messages(stream).await?,
+            None if settings.muted || !passes_filters(&settings, &live.title, &live.category) => {}
+            None => outbox = self.outbox_messages(&live, &settings, timestamp)?,
+        }
+        let event = StreamEvent::new(StreamEventKind::Online, &live, timestamp);
+        self.streams
+            .insert(channel.id.clone(), Arc::new(Mutex::new(live)));
 
         if preload.is_none() {
             db::start_stream(
@@ -519,17 +974,202 @@ impl TwitchWebhook {
                 &channel.id,
                 &stream.title,
                 &stream.game_name,
-                message_id as u64,
+                &outbox,
                 stream.started_at,
             )
             .await?;
+            let delivered = self.deliver_outbox(Some(&stream.id)).await?;
+            self.attach_messages(delivered).await;
+            self.publish(event);
         }
 
         Ok(())
     }
 
-    pub(crate) async fn handle_stream_offline(
+    /// Queues the live message of `stream` and posts it to Discord.
+    async fn announce(
         &self,
+        stream: &Stream,
+        settings: &db::ChannelSettings,
+        at: DateTime<Utc>,
+    ) -> Result<Vec<db::StreamMessage>> {
+        let outbox = self.outbox_messages(stream, settings, at)?;
+        db::enqueue_messages(&self.pool, &stream.id, &outbox).await?;
+        let delivered = self.deliver_outbox(Some(&stream.id)).await?;
+        Ok(delivered.into_iter().map(|d| d.message).collect())
+    }
+
+    /// The live message of `stream` for every notification channel, with
+    /// the mention unless the channel is in its quiet hours. Nobody is
+    /// pinged once quiet hours end for streams that went live during them,
+    /// so a morning doesn't start with a burst of stale pings. Each carries
+    /// a nonce Discord enforces, so a message posted right before the
+    /// server stopped isn't posted again when the outbox is retried.
+    fn outbox_messages(
+        &self,
+        stream: &Stream,
+        settings: &db::ChannelSettings,
+        at: DateTime<Utc>,
+    ) -> Result<Vec<db::OutboxMessage>> {
+        let mention = mention(settings);
+        let now = Utc::now();
+        self.discord_channels
+            .iter()
+            .map(|channel| {
+                let nonce = format!("{:016x}", uuid::Uuid::new_v4().as_u64_pair().0);
+                let message = CreateMessage::new()
+                    .embed(live_embed(stream, settings, at))
+                    .nonce(Nonce::String(nonce))
+                    .enforce_nonce(true);
+                let message = match &mention {
+                    Some(_) if self.is_quiet(*channel, now) => {
+                        info!(
+                            "Posting to Discord channel {channel} without mentions during quiet hours"
+                        );
+                        message
+                    }
+                    Some((content, allowed)) => {
+                        message.content(content).allowed_mentions(allowed.clone())
+                    }
+                    None => message,
+                };
+                let payload = serde_json::to_value(&message).map_err(|e| {
+                    WebhookError::InternalServerError(format!("Failed to serialize message: {e}"))
+                })?;
+                Ok(db::OutboxMessage {
+                    discord_channel_id: channel.get() as i64,
+                    payload,
+                })
+            })
+            .collect()
+    }
+
+    /// Posts the messages waiting in the outbox, only those of `stream_id`
+    /// if given, and records the ones that made it. Each is claimed in the
+    /// database while it's posted, so other deliveries skip it rather than
+    /// wait on it. Failures stay queued for [`Self::poll_outbox`] to retry.
+    async fn deliver_outbox(&self, stream_id: Option<&str>) -> Result<Vec<Delivered>> {
+        let mut delivered = Vec::new();
+        let mut after = 0;
+        while let Some(claim) =
+            db::claim_message(&self.pool, stream_id, after, MAX_OUTBOX_ATTEMPTS).await?
+        {
+            let pending = claim.message.clone();
+            after = pending.id;
+            let channel = ChannelId::new(pending.discord_channel_id as u64);
+            let result = self
+                .discord_http
+                .send_message(channel, Vec::new(), &pending.payload.0)
+                .await;
+            let message_id = result.as_ref().ok().map(|m| m.id.get() as i64);
+            self.log_notification(
+                &pending.channel_id,
+                NotificationAction::Send,
+                message_id,
+                &result,
+            )
+            .await;
+            match result {
+                Ok(sent) => {
+                    let message = db::StreamMessage {
+                        discord_channel_id: pending.discord_channel_id,
+                        message_id: sent.id.get() as i64,
+                    };
+                    db::complete_message(claim, message).await?;
+                    delivered.push(Delivered {
+                        channel_id: pending.channel_id,
+                        stream_id: pending.stream_id,
+                        message,
+                    });
+                }
+                Err(e) => {
+                    warn!("Failed to send message to Discord channel {channel}: {e}");
+                    db::fail_message(claim, &e.to_string()).await?;
+                }
+            }
+        }
+        Ok(delivered)
+    }
+
+    /// Adds delivered messages to the streams they're about, so later edits
+    /// reach them too.
+    async fn attach_messages(&self, delivered: Vec<Delivered>) {
+        for delivered in delivered {
+            let stream = self
+                .streams
+                .get(&delivered.channel_id)
+                .map(|s| Arc::clone(s.value()))
+                .or_else(|| {
+                    self.ending
+                        .get(&delivered.channel_id)
+                        .map(|e| Arc::clone(&e.stream))
+                });
+            let Some(stream) = stream else { continue };
+            let mut stream = stream.lock().await;
+            if stream.id == delivered.stream_id && !stream.messages.contains(&delivered.message) {
+                stream.messages.push(delivered.message);
+            }
+        }
+    }
+
+    /// The messages of a stream that was live before a restart. Streams from
+    /// before there could be several only have `message_id`, which went to
+    /// the first channel.
+    async fn preloaded_messages(&self, stream: &db::Stream) -> Result<Vec<db::StreamMessage>> {
+        let messages = db::get_stream_messages(&self.pool, &stream.stream_id).await?;
+        if !messages.is_empty() || stream.message_id == NO_MESSAGE {
+            return Ok(messages);
+        }
+        Ok(self
+            .discord_channels
+            .first()
+            .map(|channel| db::StreamMessage {
+                discord_channel_id: channel.get() as i64,
+                message_id: stream.message_id,
+            })
+            .into_iter()
+            .collect())
+    }
+
+    /// Continues the stream the channel went offline from during the grace
+    /// period, keeping its record and Discord message. Returns whether there
+    /// was one.
+    async fn resume_stream(
+        &self,
+        channel_id: &str,
+        twitch: &TwitchStream,
+        timestamp: DateTime<Utc>,
+    ) -> Result<bool> {
+        let Some(ending) = self.take_ending(channel_id) else {
+            return Ok(false);
+        };
+        {
+            let mut stream = ending.stream.lock().await;
+            stream.title = twitch.title.clone();
+            stream.category = twitch.game_name.clone();
+            stream.last_updated = timestamp;
+            stream.events.push(db::UpdateEvent {
+                title: twitch.title.clone(),
+                category: twitch.game_name.clone(),
+                timestamp,
+                raid: None,
+            });
+            db::update_stream(
+                &self.pool,
+                &stream.id,
+                &stream.title,
+                stream.events.last().unwrap(),
+            )
+            .await?;
+        }
+        self.streams.insert(channel_id.to_string(), ending.stream);
+        Ok(true)
+    }
+
+    /// Ends the channel's stream, after the offline grace period if there is
+    /// one so a quick return continues it.
+    pub(crate) async fn handle_stream_offline(
+        self: &Arc<Self>,
         event: &OfflineEvent,
         timestamp: DateTime<Utc>,
     ) -> Result<()> {
@@ -537,22 +1177,82 @@ impl TwitchWebhook {
             "Stream offline received for user: {}",
             event.broadcaster_user_name
         );
-
-        let guard = match self.streams.remove(&event.broadcaster_user_id) {
-            Some(guard) => guard,
-            None => return Ok(()),
+        let channel_id = &event.broadcaster_user_id;
+        let Some((_, stream)) = self.streams.remove(channel_id) else {
+            return Ok(());
         };
+        let started_at = stream.lock().await.started_at;
+        let grace = offline_wait(self.offline_grace, self.flap_window, started_at, timestamp);
+        if grace.is_zero() {
+            return self.finish_stream(stream, timestamp).await;
+        }
 
-        let stream = guard.1.lock().await;
+        let webhook = Arc::clone(self);
+        let key = channel_id.clone();
+        let timer = tokio::spawn(async move {
+            tokio::time::sleep(grace).await;
+            let Some((_, ending)) = webhook.ending.remove(&key) else {
+                return;
+            };
+            if let Err(e) = webhook
+                .finish_stream(ending.stream, ending.offline_at)
+                .await
+            {
+                error!("Error handling stream offline: {e:?}");
+            }
+        });
+        self.ending.insert(
+            channel_id.clone(),
+            Ending {
+                stream,
+                offline_at: timestamp,
+                timer: timer.abort_handle(),
+            },
+        );
+        Ok(())
+    }
+
+    async fn end_stream(&self, channel_id: &str, timestamp: DateTime<Utc>) -> Result<()> {
+        match self.streams.remove(channel_id) {
+            Some((_, stream)) => self.finish_stream(stream, timestamp).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Writes the summary of a stream that's over to Discord and the
+    /// database, or drops it entirely if it was only a flap.
+    async fn finish_stream(
+        &self,
+        stream: Arc<Mutex<Stream>>,
+        timestamp: DateTime<Utc>,
+    ) -> Result<()> {
+        let stream = stream.lock().await;
         if stream.events.is_empty() {
             warn!("{}'s stream has no events", stream.user_name);
             return Ok(());
         }
+        if self.is_flap(stream.started_at, timestamp) {
+            info!(
+                "Dropping {}'s stream, which was only live for {}s",
+                stream.user_login,
+                (timestamp - stream.started_at).num_seconds()
+            );
+            self.delete_discord(&stream.channel_id, &stream.messages)
+                .await?;
+            db::delete_stream(&self.pool, &stream.id).await?;
+            self.publish(StreamEvent::new(
+                StreamEventKind::Offline,
+                &stream,
+                timestamp,
+            ));
+            return Ok(());
+        }
         let mut events = stream.events.clone();
         events.push(db::UpdateEvent {
             title: stream.title.clone(),
             category: stream.category.clone(),
             timestamp,
+            raid: None,
         });
         events.sort_by_key(|e| e.timestamp);
 
@@ -571,25 +1271,258 @@ impl TwitchWebhook {
 
         let elapsed = human_duration(stream.started_at, timestamp);
 
-        let builder = EditMessage::new().embed(
-            CreateEmbed::new()
-                .title(format!(
-                    "**{}** streamed for {}",
-                    display_name(&stream.user_name, &stream.user_login),
-                    elapsed
-                ))
-                .description(title.to_string())
-                .thumbnail(stream.profile_image_url.clone())
-                .color(colour::Color::from_rgb(128, 128, 128))
-                .url(format!("https://twitch.tv/{}", stream.user_login))
-                .field(category, "", true),
-        );
-        self.edit_discord(stream.message_id, builder).await?;
+        let mut embed = CreateEmbed::new()
+            .title(format!(
+                "**{}** streamed for {}",
+                display_name(&stream.user_name, &stream.user_login),
+                elapsed
+            ))
+            .description(title.to_string())
+            .thumbnail(stream.profile_image_url.clone())
+            .color(ENDED_COLOR)
+            .url(format!("https://twitch.tv/{}", stream.user_login))
+            .field(category, "", true);
+        embed = raid_fields(embed, &stream.events);
+        // A summary without viewers beats none at all.
+        match db::stream_viewer_stats(&self.pool, &stream.id).await {
+            Ok(Some(viewers)) => {
+                embed = embed.field(
+                    format!(
+                        "**»** {:.0} average ⬩ {} peak viewers",
+                        viewers.average, viewers.peak
+                    ),
+                    "",
+                    true,
+                );
+            }
+            Ok(None) => {}
+            Err(e) => warn!("Failed to get viewer counts of {}: {e:#}", stream.id),
+        }
+        let builder = EditMessage::new().embed(embed);
+        self.edit_discord(&stream.channel_id, &stream.messages, builder)
+            .await?;
 
         db::end_stream(&self.pool, &stream.id, title, timestamp).await?;
+        self.link_vod(&stream).await;
+        self.publish(StreamEvent::new(
+            StreamEventKind::Offline,
+            &stream,
+            timestamp,
+        ));
+        Ok(())
+    }
+
+    /// Looks for the stream's VOD in the background, giving Twitch a few
+    /// tries to publish it, and adds a link to the summary once it's there.
+    async fn link_vod(&self, stream: &Stream) {
+        if stream.messages.is_empty() {
+            return;
+        }
+        let api = Arc::clone(&self.api);
+        let http = Arc::clone(&self.discord_http);
+        let pool = self.pool.clone();
+        let channel_id = stream.channel_id.clone();
+        let stream_id = stream.id.clone();
+        let messages = stream.messages.clone();
+        let shutdown = self.shutdown.clone();
+        let mut tasks = self.tasks.lock().await;
+        tasks.spawn(async move {
+            for delay in VOD_RETRY_DELAY_SECS {
+                tokio::select! {
+                    _ = shutdown.cancelled() => return,
+                    _ = tokio::time::sleep(Duration::from_secs(delay)) => {}
+                }
+                let vod = match api.get_archive_vod(&channel_id, &stream_id).await {
+                    Ok(Some(vod)) => vod,
+                    Ok(None) => continue,
+                    Err(e) => {
+                        warn!("Failed to look up VOD of stream {stream_id}: {e:#}");
+                        continue;
+                    }
+                };
+                let button = CreateButton::new_link(vod.url).label("Watch VOD");
+                let edit =
+                    EditMessage::new().components(vec![CreateActionRow::Buttons(vec![button])]);
+                for message in &messages {
+                    let result = ChannelId::new(message.discord_channel_id as u64)
+                        .edit_message(
+                            &http,
+                            MessageId::from(message.message_id as u64),
+                            edit.clone(),
+                        )
+                        .await;
+                    let error = result.as_ref().err().map(|e| e.to_string());
+                    if let Err(e) = db::record_notification(
+                        &pool,
+                        &channel_id,
+                        NotificationAction::Edit,
+                        Some(message.message_id),
+                        error.as_deref(),
+                    )
+                    .await
+                    {
+                        warn!(error = ?e, "Failed to record notification");
+                    }
+                    if let Some(error) = error {
+                        warn!(
+                            "Failed to link VOD {} of stream {stream_id}: {error}",
+                            vod.id
+                        );
+                    }
+                }
+                return;
+            }
+            info!("No VOD published for stream {stream_id}");
+        });
+    }
+
+    /// Adds a raid out of a live channel or into it to the stream's
+    /// timeline and its live message. Raids of channels that aren't live,
+    /// like hosts of a finished stream, aren't recorded.
+    pub(crate) async fn handle_raid(
+        &self,
+        event: &RaidEvent,
+        outgoing: bool,
+        timestamp: DateTime<Utc>,
+    ) -> Result<()> {
+        let (channel_id, raid) = if outgoing {
+            let raid = db::Raid {
+                direction: db::RaidDirection::Out,
+                login: event.to_broadcaster_user_login.clone(),
+                name: event.to_broadcaster_user_name.clone(),
+                viewers: event.viewers,
+            };
+            (&event.from_broadcaster_user_id, raid)
+        } else {
+            let raid = db::Raid {
+                direction: db::RaidDirection::In,
+                login: event.from_broadcaster_user_login.clone(),
+                name: event.from_broadcaster_user_name.clone(),
+                viewers: event.viewers,
+            };
+            (&event.to_broadcaster_user_id, raid)
+        };
+        info!(
+            "Raid from {} to {} with {} viewers",
+            event.from_broadcaster_user_name, event.to_broadcaster_user_name, event.viewers
+        );
+
+        // Raiding out usually ends the stream, so it may be ending already.
+        let live = self.streams.get(channel_id).map(|s| Arc::clone(s.value()));
+        let Some(stream) = live
+            .clone()
+            .or_else(|| self.ending.get(channel_id).map(|e| Arc::clone(&e.stream)))
+        else {
+            return Ok(());
+        };
+        let mut stream = stream.lock().await;
+        let event = db::UpdateEvent {
+            title: stream.title.clone(),
+            category: stream.category.clone(),
+            timestamp,
+            raid: Some(raid),
+        };
+        db::update_stream(&self.pool, &stream.id, &stream.title, &event).await?;
+        stream.events.push(event);
+
+        // Ending streams get the raid in their summary instead.
+        if live.is_some() && !stream.messages.is_empty() {
+            let settings = db::get_channel_settings(&self.pool, &stream.channel_id).await?;
+            let builder = EditMessage::new().embed(live_embed(&stream, &settings, timestamp));
+            self.edit_discord(&stream.channel_id, &stream.messages, builder)
+                .await?;
+        }
         Ok(())
     }
 
+    /// Shows a Hype Train of a live channel in Discord the way its settings
+    /// ask for: in the live message, or as a reply to it when it starts.
+    pub(crate) async fn handle_hype_train(
+        &self,
+        event: &HypeTrainEvent,
+        ended: bool,
+        timestamp: DateTime<Utc>,
+    ) -> Result<()> {
+        info!(
+            "Hype Train {} at level {} for user: {}",
+            if ended { "ended" } else { "started" },
+            event.level,
+            event.broadcaster_user_name
+        );
+        let Some(stream) = self
+            .streams
+            .get(&event.broadcaster_user_id)
+            .map(|s| Arc::clone(s.value()))
+        else {
+            return Ok(());
+        };
+        let mut stream = stream.lock().await;
+        stream.hype_train = Some(HypeTrain {
+            level: event.level,
+            ended,
+        });
+
+        let settings = db::get_channel_settings(&self.pool, &stream.channel_id).await?;
+        if settings.muted || stream.messages.is_empty() {
+            return Ok(());
+        }
+        match settings.hype_train.as_deref() {
+            Some("annotate") => {
+                let builder = EditMessage::new().embed(live_embed(&stream, &settings, timestamp));
+                self.edit_discord(&stream.channel_id, &stream.messages, builder)
+                    .await?;
+            }
+            Some("post") if !ended => {
+                let content = format!(
+                    "🚂 **{}** started a Hype Train!",
+                    display_name(&stream.user_name, &stream.user_login)
+                );
+                self.follow_up_discord(&stream.channel_id, &stream.messages, &content, None, true)
+                    .await;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Posts `content` to the Discord channels of `messages`, as a reply to
+    /// each when `reply` is set. Only `mention` pings anyone, and not during
+    /// quiet hours.
+    async fn follow_up_discord(
+        &self,
+        channel_id: &str,
+        messages: &[db::StreamMessage],
+        content: &str,
+        mention: Option<&(String, CreateAllowedMentions)>,
+        reply: bool,
+    ) {
+        let now = Utc::now();
+        for message in messages {
+            let channel = ChannelId::new(message.discord_channel_id as u64);
+            let mut follow_up = match mention {
+                Some((mention, allowed)) if !self.is_quiet(channel, now) => CreateMessage::new()
+                    .content(format!("{mention} {content}"))
+                    .allowed_mentions(allowed.clone()),
+                _ => CreateMessage::new()
+                    .content(content)
+                    .allowed_mentions(CreateAllowedMentions::new()),
+            };
+            if reply {
+                follow_up = follow_up
+                    .reference_message((channel, MessageId::new(message.message_id as u64)));
+            }
+            let result = channel
+                .send_message(self.discord_http.clone(), follow_up)
+                .await;
+            let message_id = result.as_ref().ok().map(|m| m.id.get() as i64);
+            self.log_notification(channel_id, NotificationAction::Send, message_id, &result)
+                .await;
+            if let Err(e) = result {
+                warn!("Failed to follow up in Discord channel {channel}: {e}");
+            }
+        }
+    }
+
     pub(crate) async fn handle_channel_update(
         &self,
         event: &ChannelUpdateEvent,
@@ -605,6 +1538,7 @@ impl TwitchWebhook {
             None => return Ok(()),
         };
         let mut stream = guard.lock().await;
+        let category_changed = stream.category != event.category_name;
         stream.title = event.title.clone();
         stream.category = event.category_name.clone();
         stream.last_updated = timestamp;
@@ -613,6 +1547,7 @@ impl TwitchWebhook {
             title: event.title.clone(),
             category: event.category_name.clone(),
             timestamp,
+            raid: None,
         });
         db::update_stream(
             &self.pool,
@@ -622,60 +1557,191 @@ impl TwitchWebhook {
         )
         .await?;
 
-        let builder = EditMessage::new().embed(
-            CreateEmbed::new()
-                .title(format!(
-                    "**{}** is live!",
-                    display_name(&stream.user_name, &stream.user_login)
-                ))
-                .description(&event.title)
-                .thumbnail(&stream.profile_image_url)
-                .color(colour::Color::from_rgb(145, 70, 255))
-                .url(format!("https://twitch.tv/{}", stream.user_login))
-                .field(format!("**»** {}", &event.category_name), "", true),
-        );
-        self.edit_discord(stream.message_id, builder).await?;
+        let settings = db::get_channel_settings(&self.pool, &stream.channel_id).await?;
+        let wanted = passes_filters(&settings, &stream.title, &stream.category);
+        if !wanted {
+            let messages = std::mem::take(&mut stream.messages);
+            if !messages.is_empty() {
+                info!(
+                    "Withdrawing the announcement of {}, which no longer passes its filters",
+                    stream.user_login
+                );
+            }
+            // Also drops messages still waiting in the outbox.
+            db::clear_stream_messages(&self.pool, &stream.id).await?;
+            self.delete_discord(&stream.channel_id, &messages).await?;
+        } else if stream.messages.is_empty() {
+            // Filters kept the stream from being announced until now, unless
+            // its messages are still waiting in the outbox.
+            let pending =
+                db::pending_messages(&self.pool, Some(&stream.id), MAX_OUTBOX_ATTEMPTS).await?;
+            if !settings.muted && pending.is_empty() {
+                stream.messages = self.announce(&stream, &settings, timestamp).await?;
+            }
+        } else {
+            let builder = EditMessage::new().embed(live_embed(&stream, &settings, timestamp));
+            self.edit_discord(&stream.channel_id, &stream.messages, builder)
+                .await?;
+            if category_changed && !settings.muted {
+                if let Some(mode) = settings.category_change.as_deref() {
+                    let content = format!(
+                        "🎮 **{}** is now playing **{}**",
+                        display_name(&stream.user_name, &stream.user_login),
+                        stream.category
+                    );
+                    self.follow_up_discord(
+                        &stream.channel_id,
+                        &stream.messages,
+                        &content,
+                        mention(&settings).as_ref(),
+                        mode == "reply",
+                    )
+                    .await;
+                }
+            }
+        }
+        self.publish(StreamEvent::new(
+            StreamEventKind::Update,
+            &stream,
+            timestamp,
+        ));
 
         Ok(())
     }
 
-    pub(crate) async fn message_discord(
+    /// Posts a one-off embed to every notification channel so operators can
+    /// check the bot token and channel permissions, stopping at the first
+    /// that fails. It isn't tied to a Twitch channel, so it stays out of the
+    /// notification log.
+    pub(crate) async fn send_test_message(
         &self,
-        message: CreateMessage,
-    ) -> Result<serenity::all::Message> {
-        self.discord_channel
-            .send_message(self.discord_http.clone(), message)
-            .await
-            .map_err(|e| {
-                WebhookError::InternalServerError(format!(
-                    "Failed to send message to Discord channel: {e}"
-                ))
-            })
+        text: &str,
+    ) -> serenity::Result<Vec<serenity::all::Message>> {
+        let message = CreateMessage::new().embed(
+            CreateEmbed::new()
+                .title("Stitch test notification")
+                .description(text)
+                .color(LIVE_COLOR)
+                .timestamp(serenity::model::Timestamp::now()),
+        );
+        let mut sent = Vec::with_capacity(self.discord_channels.len());
+        for channel in &self.discord_channels {
+            sent.push(
+                channel
+                    .send_message(self.discord_http.clone(), message.clone())
+                    .await?,
+            );
+        }
+        Ok(sent)
     }
 
+    /// Posts an operator alert to every notification channel. Like test
+    /// messages it stays out of the notification log, and failing to send it
+    /// is only logged.
+    async fn alert_discord(&self, title: &str, text: &str) {
+        let message = CreateMessage::new().embed(
+            CreateEmbed::new()
+                .title(title)
+                .description(text)
+                .color(ALERT_COLOR)
+                .timestamp(serenity::model::Timestamp::now()),
+        );
+        for channel in &self.discord_channels {
+            if let Err(e) = channel
+                .send_message(self.discord_http.clone(), message.clone())
+                .await
+            {
+                warn!("Failed to send alert to Discord channel {channel}: {e}");
+            }
+        }
+    }
+
+    /// Applies `message` to each of `messages`, trying all of them before
+    /// reporting the first failure.
     pub(crate) async fn edit_discord(
         &self,
-        message_id: i64,
+        channel_id: &str,
+        messages: &[db::StreamMessage],
         message: EditMessage,
-    ) -> Result<Message> {
-        self.discord_channel
-            .edit_message(
-                &self.discord_http,
-                MessageId::from(message_id as u64),
-                message,
+    ) -> Result<()> {
+        let mut failure = None;
+        for posted in messages {
+            let result = ChannelId::new(posted.discord_channel_id as u64)
+                .edit_message(
+                    &self.discord_http,
+                    MessageId::from(posted.message_id as u64),
+                    message.clone(),
+                )
+                .await;
+            self.log_notification(
+                channel_id,
+                NotificationAction::Edit,
+                Some(posted.message_id),
+                &result,
             )
-            .await
-            .map_err(|e| WebhookError::InternalServerError(format!("Failed to edit message: {e}")))
+            .await;
+            if let Err(e) = result {
+                failure.get_or_insert(e);
+            }
+        }
+        match failure {
+            Some(e) => Err(WebhookError::InternalServerError(format!(
+                "Failed to edit message: {e}"
+            ))),
+            None => Ok(()),
+        }
     }
 
-    pub(crate) async fn delete_discord(&self, message_id: i64) -> Result<()> {
-        self.discord_channel
-            .delete_message(&self.discord_http, MessageId::from(message_id as u64))
-            .await
-            .map_err(|e| {
-                WebhookError::InternalServerError(format!("Failed to delete message: {e}"))
-            })?;
-        Ok(())
+    /// Deletes each of `messages`, trying all of them before reporting the
+    /// first failure.
+    pub(crate) async fn delete_discord(
+        &self,
+        channel_id: &str,
+        messages: &[db::StreamMessage],
+    ) -> Result<()> {
+        let mut failure = None;
+        for posted in messages {
+            let result = ChannelId::new(posted.discord_channel_id as u64)
+                .delete_message(
+                    &self.discord_http,
+                    MessageId::from(posted.message_id as u64),
+                )
+                .await;
+            self.log_notification(
+                channel_id,
+                NotificationAction::Delete,
+                Some(posted.message_id),
+                &result,
+            )
+            .await;
+            if let Err(e) = result {
+                failure.get_or_insert(e);
+            }
+        }
+        match failure {
+            Some(e) => Err(WebhookError::InternalServerError(format!(
+                "Failed to delete message: {e}"
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Appends a Discord request to the notification log. Failures to record
+    /// are only logged so they never block the notification itself.
+    async fn log_notification<T>(
+        &self,
+        channel_id: &str,
+        action: NotificationAction,
+        message_id: Option<i64>,
+        result: &std::result::Result<T, serenity::Error>,
+    ) {
+        let error = result.as_ref().err().map(|e| e.to_string());
+        if let Err(e) =
+            db::record_notification(&self.pool, channel_id, action, message_id, error.as_deref())
+                .await
+        {
+            warn!(error = ?e, "Failed to record notification");
+        }
     }
 
     pub(crate) async fn serve<F>(
@@ -728,12 +1794,15 @@ impl TwitchWebhook {
         let listener =
             tokio::net::TcpListener::bind((std::net::Ipv4Addr::UNSPECIFIED, port)).await?;
 
+        let hype_trains = db::list_hype_train_channels(&self.pool).await?;
         self.api
             .sync(
                 &channels
                     .iter()
+                    .filter(|c| !c.paused)
                     .map(|c| c.channel_id.clone())
                     .collect::<Vec<String>>(),
+                &hype_trains,
             )
             .await?;
 
@@ -744,7 +1813,9 @@ impl TwitchWebhook {
         )
         .with_graceful_shutdown(shutdown)
         .await?;
-        let mut tasks = self.tasks.lock().await;
+        self.finish_ending().await;
+        // Taken out of the lock, so tasks still running can spawn more.
+        let mut tasks = std::mem::take(&mut *self.tasks.lock().await);
         while let Some(result) = tasks.join_next().await {
             result.unwrap_or_else(|e| error!("Task failed: {e:?}"));
         }
@@ -758,21 +1829,180 @@ async fn handle_message(
     body: Bytes,
 ) -> Result<impl IntoResponse> {
     let timestamp = server.verify(&headers, &body)?;
+    let message_id = TwitchWebhook::header_val(&headers, HEADER_MESSAGE_ID)?;
+    server.claim_message_id(message_id).await?;
 
-    let msg_type_header = TwitchWebhook::header_val(&headers, HEADER_MESSAGE_TYPE)?;
+    let result = dispatch_message(&server, &headers, &body, message_id, timestamp).await;
+    if result.is_err() {
+        server.release_message_id(message_id).await;
+    }
+    result
+}
+
+async fn dispatch_message(
+    server: &Arc<TwitchWebhook>,
+    headers: &HeaderMap,
+    body: &Bytes,
+    message_id: &str,
+    timestamp: DateTime<Utc>,
+) -> Result<Response> {
+    let msg_type_header = TwitchWebhook::header_val(headers, HEADER_MESSAGE_TYPE)?;
     match msg_type_header {
         WEBHOOK_VERIFICATION_TYPE => {
-            let challenge = server.handle_challenge(&body)?;
+            let challenge = server.handle_challenge(body)?;
             Ok((StatusCode::OK, challenge).into_response())
         }
         NOTIFICATION_TYPE => {
-            server.handle_notification(&body, timestamp).await?;
+            server
+                .handle_notification(body, message_id, timestamp)
+                .await?;
+            Ok(StatusCode::NO_CONTENT.into_response())
+        }
+        REVOCATION_TYPE => {
+            server.handle_revocation(body).await?;
             Ok(StatusCode::NO_CONTENT.into_response())
         }
         unknown_type => Err(WebhookError::UnknownMessageType(unknown_type.to_string())),
     }
 }
 
+fn live_color(settings: &db::ChannelSettings) -> colour::Color {
+    match settings.color {
+        Some(color) => colour::Color::new(color as u32),
+        None => LIVE_COLOR.into(),
+    }
+}
+
+/// Whether a stream that started at `started_at` and went offline at
+/// `offline_at` was too short to be more than a flap. Offline times before
+/// the start, from clocks disagreeing, count as flaps.
+fn is_flap(window: Duration, started_at: DateTime<Utc>, offline_at: DateTime<Utc>) -> bool {
+    !window.is_zero()
+        && (offline_at - started_at)
+            .to_std()
+            .map_or(true, |live| live < window)
+}
+
+/// How long a stream that went offline at `offline_at` waits for the channel
+/// to come back before it's finished. A flap waits out the flap window too,
+/// so the channel coming right back continues the stream instead of posting
+/// another live message.
+fn offline_wait(
+    grace: Duration,
+    window: Duration,
+    started_at: DateTime<Utc>,
+    offline_at: DateTime<Utc>,
+) -> Duration {
+    if is_flap(window, started_at, offline_at) {
+        grace.max(window)
+    } else {
+        grace
+    }
+}
+
+fn live_embed(stream: &Stream, settings: &db::ChannelSettings, at: DateTime<Utc>) -> CreateEmbed {
+    let embed = CreateEmbed::new()
+        .title(format!(
+            "**{}** is live!",
+            display_name(&stream.user_name, &stream.user_login)
+        ))
+        .description(&stream.title)
+        .thumbnail(&stream.profile_image_url)
+        .color(live_color(settings))
+        .url(format!("https://twitch.tv/{}", stream.user_login))
+        .field(format!("**»** {}", &stream.category), "", true);
+    let embed = raid_fields(embed, &stream.events);
+    let embed = match stream.hype_train {
+        Some(train) => embed.field(hype_train_line(train), "", false),
+        None => embed,
+    };
+    match preview_url(&stream.thumbnail_url, at) {
+        Some(url) => embed.image(url),
+        None => embed,
+    }
+}
+
+/// Adds a line for each of the latest raids in `events` to `embed`.
+fn raid_fields(embed: CreateEmbed, events: &[db::UpdateEvent]) -> CreateEmbed {
+    let raids: Vec<_> = events.iter().filter_map(|e| e.raid.as_ref()).collect();
+    let skip = raids.len().saturating_sub(MAX_RAID_FIELDS);
+    raids
+        .into_iter()
+        .skip(skip)
+        .fold(embed, |embed, raid| embed.field(raid_line(raid), "", false))
+}
+
+fn hype_train_line(train: HypeTrain) -> String {
+    if train.ended {
+        format!("🚂 Hype Train reached level {}", train.level)
+    } else {
+        format!("🚂 Hype Train at level {}", train.level)
+    }
+}
+
+fn raid_line(raid: &db::Raid) -> String {
+    let name = display_name(&raid.name, &raid.login);
+    match raid.direction {
+        db::RaidDirection::Out => format!("**»** Raided out to **{name}**"),
+        db::RaidDirection::In => {
+            format!("**»** Was raided by **{name}** ({} viewers)", raid.viewers)
+        }
+    }
+}
+
+/// Whether a stream with `title` in `category` should be announced under the
+/// channel's category and keyword filters, both compared case-insensitively.
+fn passes_filters(settings: &db::ChannelSettings, title: &str, category: &str) -> bool {
+    let title = title.to_lowercase();
+    let allowed = settings.categories.is_empty()
+        || settings
+            .categories
+            .iter()
+            .any(|c| c.eq_ignore_ascii_case(category));
+    allowed
+        && !settings
+            .muted_keywords
+            .iter()
+            .any(|keyword| title.contains(&keyword.to_lowercase()))
+}
+
+/// What to ping when a channel goes live, allowing just that mention so
+/// nothing else in the message pings anyone.
+fn mention(settings: &db::ChannelSettings) -> Option<(String, CreateAllowedMentions)> {
+    match (settings.mass_mention.as_deref(), settings.mention_role_id) {
+        // Discord governs @here with the same flag as @everyone.
+        (Some(target @ ("everyone" | "here")), _) => Some((
+            format!("@{target}"),
+            CreateAllowedMentions::new().everyone(true),
+        )),
+        (_, Some(role)) => {
+            let role = RoleId::new(role as u64);
+            Some((
+                format!("<@&{role}>"),
+                CreateAllowedMentions::new().roles([role]),
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// The stream preview from a Helix `thumbnail_url` template, with a query
+/// that changes every [`PREVIEW_REFRESH_SECS`] so Discord fetches a fresh
+/// frame instead of its cached one.
+fn preview_url(template: &str, at: DateTime<Utc>) -> Option<String> {
+    if template.is_empty() {
+        return None;
+    }
+    let (width, height) = PREVIEW_SIZE;
+    Some(format!(
+        "{}?t={}",
+        template
+            .replace("{width}", &width.to_string())
+            .replace("{height}", &height.to_string()),
+        at.timestamp() / PREVIEW_REFRESH_SECS
+    ))
+}
+
 fn display_name(user_name: &str, user_login: &str) -> String {
     if user_name.to_lowercase() == user_login {
         user_name.to_string()
@@ -781,7 +2011,7 @@ fn display_name(user_name: &str, user_login: &str) -> String {
     }
 }
 
-fn human_duration(start: DateTime<Utc>, end: DateTime<Utc>) -> String {
+pub(crate) fn human_duration(start: DateTime<Utc>, end: DateTime<Utc>) -> String {
     let minutes = end.signed_duration_since(start).num_minutes();
     if minutes < 0 {
         return "<in the future>".into();
@@ -790,7 +2020,7 @@ fn human_duration(start: DateTime<Utc>, end: DateTime<Utc>) -> String {
     format!("{hours}h{mins:02}m")
 }
 
-fn tally_categories(events: &[db::UpdateEvent]) -> (&str, HashMap<&str, u64>) {
+pub(crate) fn tally_categories(events: &[db::UpdateEvent]) -> (&str, HashMap<&str, u64>) {
     let mut titles: HashMap<&str, u64> = HashMap::new();
     let mut categories: HashMap<&str, u64> = HashMap::new();
 
@@ -823,11 +2053,13 @@ mod tests {
                 title: "Stream Title".to_string(),
                 category: "Gaming".to_string(),
                 timestamp: base_time,
+                raid: None,
             },
             db::UpdateEvent {
                 title: "Stream Title".to_string(),
                 category: "Gaming".to_string(),
                 timestamp: base_time + chrono::Duration::hours(1),
+                raid: None,
             },
         ];
         let (title, categories) = tally_categories(&events);
@@ -840,21 +2072,25 @@ mod tests {
                 title: "Initial Title".to_string(),
                 category: "Gaming".to_string(),
                 timestamp: base_time,
+                raid: None,
             },
             db::UpdateEvent {
                 title: "Initial Title".to_string(),
                 category: "Gaming".to_string(),
                 timestamp: base_time + chrono::Duration::hours(1),
+                raid: None,
             },
             db::UpdateEvent {
                 title: "Changed Title".to_string(),
                 category: "Gaming".to_string(),
                 timestamp: base_time + chrono::Duration::hours(4),
+                raid: None,
             },
             db::UpdateEvent {
                 title: "Final Title".to_string(),
                 category: "Gaming".to_string(),
                 timestamp: base_time + chrono::Duration::hours(4) + chrono::Duration::minutes(30),
+                raid: None,
             },
         ];
         let (title, categories) = tally_categories(&events);
@@ -867,21 +2103,25 @@ mod tests {
                 title: "Playing Game A".to_string(),
                 category: "Game A".to_string(),
                 timestamp: base_time,
+                raid: None,
             },
             db::UpdateEvent {
                 title: "Still Playing".to_string(),
                 category: "Game A".to_string(),
                 timestamp: base_time + chrono::Duration::hours(1) + chrono::Duration::minutes(30),
+                raid: None,
             },
             db::UpdateEvent {
                 title: "Just Chatting".to_string(),
                 category: "Game B".to_string(),
                 timestamp: base_time + chrono::Duration::hours(4),
+                raid: None,
             },
             db::UpdateEvent {
                 title: "Playing Game C".to_string(),
                 category: "Game C".to_string(),
                 timestamp: base_time + chrono::Duration::hours(4) + chrono::Duration::minutes(15),
+                raid: None,
             },
         ];
         let (title, categories) = tally_categories(&events);
@@ -896,16 +2136,19 @@ mod tests {
                 title: "Title A".to_string(),
                 category: "Category A".to_string(),
                 timestamp: base_time,
+                raid: None,
             },
             db::UpdateEvent {
                 title: "Title B".to_string(),
                 category: "Category B".to_string(),
                 timestamp: base_time + chrono::Duration::hours(1),
+                raid: None,
             },
             db::UpdateEvent {
                 title: "Title C".to_string(),
                 category: "Category C".to_string(),
                 timestamp: base_time + chrono::Duration::hours(2),
+                raid: None,
             },
         ];
         let (title, categories) = tally_categories(&events);
@@ -915,6 +2158,226 @@ mod tests {
         assert_eq!(categories.get("Category C"), None);
     }
 
+    #[test]
+    fn test_preview_url() {
+        let template =
+            "https://static-cdn.jtvnw.net/previews-ttv/live_user_kzh-{width}x{height}.jpg";
+        let at = Utc.with_ymd_and_hms(2025, 8, 10, 12, 0, 0).unwrap();
+        let url = preview_url(template, at).unwrap();
+        assert_eq!(
+            url,
+            format!(
+                "https://static-cdn.jtvnw.net/previews-ttv/live_user_kzh-1280x720.jpg?t={}",
+                at.timestamp() / PREVIEW_REFRESH_SECS
+            )
+        );
+        assert_eq!(
+            preview_url(template, at + chrono::Duration::seconds(30)),
+            Some(url.clone())
+        );
+        assert_ne!(
+            preview_url(
+                template,
+                at + chrono::Duration::seconds(PREVIEW_REFRESH_SECS)
+            ),
+            Some(url)
+        );
+        assert_eq!(preview_url("", at), None);
+    }
+
+    #[test]
+    fn test_is_flap() {
+        let start = Utc.with_ymd_and_hms(2025, 8, 10, 12, 0, 0).unwrap();
+        let window = Duration::from_secs(60);
+        let after = |secs| start + chrono::Duration::seconds(secs);
+        assert!(is_flap(window, start, after(10)));
+        assert!(is_flap(window, start, after(-5)));
+        assert!(!is_flap(window, start, after(60)));
+        assert!(!is_flap(window, start, after(3600)));
+        assert!(!is_flap(Duration::ZERO, start, after(10)));
+    }
+
+    #[test]
+    fn test_offline_wait() {
+        let start = Utc.with_ymd_and_hms(2025, 8, 10, 12, 0, 0).unwrap();
+        let after = |secs| start + chrono::Duration::seconds(secs);
+        let grace = Duration::from_secs(5 * 60);
+        let window = Duration::from_secs(60);
+
+        // Offline again within the window waits it out, or the grace period
+        // if that's longer.
+        assert_eq!(
+            offline_wait(Duration::ZERO, window, start, after(10)),
+            window
+        );
+        assert_eq!(offline_wait(grace, window, start, after(10)), grace);
+        assert_eq!(
+            offline_wait(Duration::from_secs(10), window, start, after(-5)),
+            window
+        );
+        // Past the window only the grace period counts.
+        assert_eq!(
+            offline_wait(Duration::ZERO, window, start, after(60)),
+            Duration::ZERO
+        );
+        assert_eq!(offline_wait(grace, window, start, after(3600)), grace);
+        // With both disabled, as by default, streams end right away.
+        assert_eq!(
+            offline_wait(Duration::ZERO, Duration::ZERO, start, after(10)),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn test_passes_filters() {
+        let settings = db::ChannelSettings {
+            categories: vec!["Factorio".to_string(), "Satisfactory".to_string()],
+            muted_keywords: vec!["rerun".to_string()],
+            ..Default::default()
+        };
+        assert!(passes_filters(&settings, "Megabase day 3", "factorio"));
+        assert!(!passes_filters(
+            &settings,
+            "Megabase day 3",
+            "Just Chatting"
+        ));
+        assert!(!passes_filters(
+            &settings,
+            "[RERUN] Megabase day 2",
+            "Factorio"
+        ));
+        assert!(passes_filters(
+            &db::ChannelSettings::default(),
+            "Anything",
+            "Anything"
+        ));
+    }
+
+    #[test]
+    fn test_mention() {
+        let content = |mass_mention: Option<&str>, mention_role_id: Option<i64>| {
+            let settings = db::ChannelSettings {
+                mention_role_id,
+                mass_mention: mass_mention.map(str::to_string),
+                ..Default::default()
+            };
+            mention(&settings).map(|(content, _)| content)
+        };
+        assert_eq!(content(None, None), None);
+        assert_eq!(content(None, Some(42)), Some("<@&42>".to_string()));
+        assert_eq!(content(Some("here"), None), Some("@here".to_string()));
+        assert_eq!(
+            content(Some("everyone"), Some(42)),
+            Some("@everyone".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_raid() {
+        let body = br#"{
+            "subscription": {
+                "id": "f1c2a387-161a-49f9-a165-0f21d7a4e1c4",
+                "type": "channel.raid",
+                "version": "1",
+                "condition": {
+                    "from_broadcaster_user_id": "1234",
+                    "to_broadcaster_user_id": ""
+                }
+            },
+            "event": {
+                "from_broadcaster_user_id": "1234",
+                "from_broadcaster_user_login": "cool_user",
+                "from_broadcaster_user_name": "Cool_User",
+                "to_broadcaster_user_id": "1337",
+                "to_broadcaster_user_login": "cooler_user",
+                "to_broadcaster_user_name": "Cooler_User",
+                "viewers": 9001
+            }
+        }"#;
+        #[derive(Deserialize)]
+        struct Notification {
+            subscription: Subscription,
+            event: RaidEvent,
+        }
+        let Notification {
+            subscription,
+            event,
+        } = json::<Notification>(body).unwrap();
+        assert_eq!(subscription.condition.user_id(), "1234");
+        assert_eq!(
+            subscription.condition.event(&subscription.kind),
+            RAID_OUT_EVENT
+        );
+        assert_eq!(event.viewers, 9001);
+
+        let raid = |direction| db::Raid {
+            direction,
+            login: event.to_broadcaster_user_login.clone(),
+            name: event.to_broadcaster_user_name.clone(),
+            viewers: event.viewers,
+        };
+        assert_eq!(
+            raid_line(&raid(db::RaidDirection::Out)),
+            "**»** Raided out to **Cooler_User**"
+        );
+        assert_eq!(
+            raid_line(&raid(db::RaidDirection::In)),
+            "**»** Was raided by **Cooler_User** (9001 viewers)"
+        );
+    }
+
+    #[test]
+    fn test_parse_hype_train() {
+        let body = br#"{
+            "id": "1b0AsbInCHZW2SQFQkCzqN07Ib2",
+            "broadcaster_user_id": "1337",
+            "broadcaster_user_login": "cool_user",
+            "broadcaster_user_name": "Cool_User",
+            "total": 137,
+            "progress": 137,
+            "goal": 500,
+            "level": 2,
+            "type": "regular",
+            "started_at": "2020-07-15T17:16:03.17106713Z",
+            "expires_at": "2020-07-15T17:16:11.17106713Z"
+        }"#;
+        let event = json::<HypeTrainEvent>(body).unwrap();
+        assert_eq!(event.level, 2);
+        let train = |ended| HypeTrain {
+            level: event.level,
+            ended,
+        };
+        assert_eq!(hype_train_line(train(false)), "🚂 Hype Train at level 2");
+        assert_eq!(
+            hype_train_line(train(true)),
+            "🚂 Hype Train reached level 2"
+        );
+    }
+
+    #[test]
+    fn test_parse_revocation() {
+        let body = br#"{
+            "subscription": {
+                "id": "f1c2a387-161a-49f9-a165-0f21d7a4e1c4",
+                "status": "authorization_revoked",
+                "type": "channel.update",
+                "version": "1",
+                "cost": 1,
+                "condition": { "broadcaster_user_id": "12826" },
+                "transport": {
+                    "method": "webhook",
+                    "callback": "https://example.com/webhook/twitch"
+                },
+                "created_at": "2019-11-16T10:11:12.634234626Z"
+            }
+        }"#;
+        let Revocation { subscription } = json::<Revocation>(body).unwrap();
+        assert_eq!(subscription.kind, "channel.update");
+        assert_eq!(subscription.status, "authorization_revoked");
+        assert_eq!(subscription.condition.broadcaster_user_id, "12826");
+        assert!(!FINAL_REVOCATIONS.contains(&subscription.status.as_str()));
+    }
+
     #[test]
     #[should_panic]
     fn test_tally_categories_insufficient_events() {
@@ -923,6 +2386,7 @@ mod tests {
             title: "Only Title".to_string(),
             category: "Only Category".to_string(),
             timestamp: base_time,
+            raid: None,
         }];
         let _ = tally_categories(&events);
     }
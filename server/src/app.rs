@@ -1,19 +1,36 @@
 use anyhow::Context;
 use serenity::all::ChannelId;
 use serenity::http::Http as DiscordHttp;
+use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
+use subtle::ConstantTimeEq;
 use tokio_util::sync::CancellationToken;
-use tonic::transport::Server;
-use tracing::{error, info};
+use tonic::codec::CompressionEncoding;
+use tonic::service::interceptor::InterceptedService;
+use tonic::service::Interceptor;
+use tonic::transport::{Certificate, Identity, Server, ServerTlsConfig};
+use tonic::{Request, Status};
+use tonic_health::server::HealthReporter;
+use tracing::{error, info, warn};
 
-use crate::adapters::db::{establish_pool, list_channels};
+use crate::adapters::db::{establish_pool, list_channels, ping, Pool};
 use crate::adapters::grpc::StitchGRPC;
+use crate::adapters::notifier::{
+    EmailNotifier, EmailTags, PushNotifier, PushSink, WebhookNotifier,
+};
 use crate::adapters::twitch::TwitchAPI;
 use crate::adapters::webhook::TwitchWebhook;
 use crate::config::ServerConfig;
+use crate::utils::log_tail::LogTail;
+use crate::utils::rate_limit::RateLimitLayer;
+use crate::utils::request_id::RequestIdLayer;
 use proto::stitch::stitch_service_server::StitchServiceServer;
 
-pub async fn run(config: ServerConfig) -> anyhow::Result<()> {
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+pub async fn run(config: ServerConfig, log_tail: LogTail) -> anyhow::Result<()> {
+    let started_at = chrono::Utc::now();
     let ServerConfig {
         database_url,
         discord_token,
@@ -23,10 +40,47 @@ pub async fn run(config: ServerConfig) -> anyhow::Result<()> {
         webhook_url,
         webhook_secret,
         webhook_port,
-        tokio_console_port,
+        offline_grace_minutes,
+        flap_window_seconds,
+        notify_webhook_urls,
+        notify_webhook_secret,
+        ntfy_url,
+        ntfy_token,
+        pushover_token,
+        pushover_user,
+        push_priority,
+        smtp_url,
+        email_from,
+        email_to,
+        email_live_tags,
+        email_digest_tags,
+        email_digest_hour,
+        quiet_hours,
+        quiet_hours_tz,
+        tokio_console_port: _,
         port,
+        api_keys,
+        track_rate_limit,
+        grpc_compression,
+        tls_cert,
+        tls_key,
+        tls_client_ca,
     } = config;
 
+    let tls = match (tls_cert, tls_key) {
+        (Some(cert), Some(key)) => Some(
+            load_tls_config(&cert, &key, tls_client_ca.as_deref())
+                .await
+                .context("Failed to load gRPC TLS configuration")?,
+        ),
+        _ => None,
+    };
+
+    let auth = ApiKeyAuth::new(api_keys);
+    if auth.keys.is_empty() {
+        warn!("No API keys configured; the gRPC API is unauthenticated");
+    }
+
     let pool = establish_pool(&database_url)
         .await
         .context("Failed to establish database pool")?;
@@ -53,6 +107,8 @@ pub async fn run(config: ServerConfig) -> anyhow::Result<()> {
         .context("Failed to initialize Twitch API client")?,
     );
 
+    let cancel = shutdown_token();
+
     let discord_http = Arc::new(DiscordHttp::new(&discord_token));
     let webhook = Arc::new(
         TwitchWebhook::new(
@@ -62,10 +118,14 @@ pub async fn run(config: ServerConfig) -> anyhow::Result<()> {
             pool.clone(),
             channels.clone(),
             discord_http,
-            ChannelId::new(discord_channel),
+            discord_channel.into_iter().map(ChannelId::new).collect(),
         )
         .await
-        .context("Failed to initialize Twitch webhook")?,
+        .context("Failed to initialize Twitch webhook")?
+        .with_offline_grace(Duration::from_secs(offline_grace_minutes * 60))
+        .with_flap_window(Duration::from_secs(flap_window_seconds))
+        .with_quiet_hours(quiet_hours, quiet_hours_tz)
+        .with_shutdown(cancel.clone()),
     );
 
     let addr_string: String = format!("0.0.0.0:{port}");
@@ -73,17 +133,107 @@ pub async fn run(config: ServerConfig) -> anyhow::Result<()> {
         .parse()
         .with_context(|| format!("Invalid server address: {addr_string}"))?;
 
-    let grpc = Server::builder().add_service(StitchServiceServer::new(StitchGRPC::new(
+    tokio::spawn(Arc::clone(&webhook).poll_viewers(cancel.clone()));
+    tokio::spawn(Arc::clone(&webhook).poll_outbox(cancel.clone()));
+    tokio::spawn(Arc::clone(&webhook).prune_message_ids(cancel.clone()));
+
+    if !notify_webhook_urls.is_empty() {
+        let notifier = WebhookNotifier::new(notify_webhook_urls, notify_webhook_secret)
+            .context("Failed to initialize outbound webhooks")?;
+        tokio::spawn(Arc::new(notifier).run(webhook.subscribe(), cancel.clone()));
+    }
+
+    let push_sink = match (ntfy_url, pushover_token.zip(pushover_user)) {
+        (Some(url), _) => Some(PushSink::Ntfy {
+            url,
+            token: ntfy_token,
+        }),
+        (None, Some((token, user))) => Some(PushSink::Pushover { token, user }),
+        (None, None) => None,
+    };
+    if let Some(sink) = push_sink {
+        let notifier = PushNotifier::new(pool.clone(), sink, push_priority)
+            .context("Failed to initialize push notifications")?;
+        tokio::spawn(Arc::new(notifier).run(webhook.subscribe(), cancel.clone()));
+    }
+
+    if let Some(smtp_url) = smtp_url {
+        let tags = EmailTags {
+            live: email_live_tags,
+            digest: email_digest_tags,
+        };
+        let notifier = EmailNotifier::new(
+            pool.clone(),
+            &smtp_url,
+            email_from.as_deref().unwrap_or_default(),
+            &email_to,
+            tags,
+            email_digest_hour,
+        )
+        .context("Failed to initialize emails")?;
+        tokio::spawn(Arc::new(notifier).run(webhook.subscribe(), cancel.clone()));
+    }
+
+    let (health_reporter, health_service) = tonic_health::server::health_reporter();
+    tokio::spawn(report_health(
+        health_reporter,
+        pool.clone(),
+        Arc::clone(&api),
+        cancel.clone(),
+    ));
+
+    let reflection_service = tonic_reflection::server::Builder::configure()
+        .register_encoded_file_descriptor_set(proto::stitch::FILE_DESCRIPTOR_SET)
+        .register_encoded_file_descriptor_set(tonic_health::pb::FILE_DESCRIPTOR_SET)
+        .build_v1()
+        .context("Failed to build gRPC reflection service")?;
+
+    let mut stitch = StitchServiceServer::new(StitchGRPC::new(
         crate::service::channel::ChannelService::new(
             pool.clone(),
+            Arc::clone(&service_channels_map),
+            Arc::clone(&webhook),
+            Arc::clone(&api),
+        ),
+        crate::service::stream::StreamService::new(
+            pool.clone(),
+            Arc::clone(&webhook),
+            Arc::clone(&api),
+            cancel.clone(),
+        ),
+        crate::service::info::InfoService::new(
+            started_at,
             service_channels_map,
             Arc::clone(&webhook),
             api,
+            log_tail,
+            cancel.clone(),
         ),
-    )));
+        crate::service::export::ExportService::new(pool.clone()),
+    ));
+    if grpc_compression {
+        for encoding in [CompressionEncoding::Gzip, CompressionEncoding::Zstd] {
+            stitch = stitch.accept_compressed(encoding).send_compressed(encoding);
+        }
+    }
+
+    let mut builder = Server::builder();
+    if let Some(tls) = tls {
+        builder = builder
+            .tls_config(tls)
+            .context("Failed to apply gRPC TLS configuration")?;
+    }
+    let grpc = builder
+        .layer(RequestIdLayer)
+        .layer(RateLimitLayer::per_minute(
+            track_rate_limit,
+            !auth.keys.is_empty(),
+        ))
+        .add_service(health_service)
+        .add_service(reflection_service)
+        .add_service(InterceptedService::new(stitch, auth));
     info!("Stitch gRPC server listening: {}", addr);
 
-    let cancel = shutdown_token();
     tokio::select! {
         result = {
             let tok = cancel.clone();
@@ -109,6 +259,119 @@ pub async fn run(config: ServerConfig) -> anyhow::Result<()> {
     Ok(())
 }
 
+async fn load_tls_config(
+    cert: &Path,
+    key: &Path,
+    client_ca: Option<&Path>,
+) -> anyhow::Result<ServerTlsConfig> {
+    let identity = Identity::from_pem(read_pem(cert).await?, read_pem(key).await?);
+    let mut tls = ServerTlsConfig::new().identity(identity);
+    match client_ca {
+        Some(ca) => {
+            tls = tls.client_ca_root(Certificate::from_pem(read_pem(ca).await?));
+            info!("gRPC TLS enabled with client certificate verification");
+        }
+        None => info!("gRPC TLS enabled"),
+    }
+    Ok(tls)
+}
+
+async fn read_pem(path: &Path) -> anyhow::Result<Vec<u8>> {
+    tokio::fs::read(path)
+        .await
+        .with_context(|| format!("reading {}", path.display()))
+}
+
+/// Rejects calls whose `authorization` metadata doesn't carry one of the
+/// configured API keys as a bearer token. Lets everything through when no keys
+/// are configured.
+#[derive(Clone)]
+struct ApiKeyAuth {
+    keys: Arc<[String]>,
+}
+
+impl ApiKeyAuth {
+    fn new(keys: Vec<String>) -> Self {
+        let keys: Vec<String> = keys
+            .into_iter()
+            .map(|k| k.trim().to_string())
+            .filter(|k| !k.is_empty())
+            .collect();
+        Self { keys: keys.into() }
+    }
+}
+
+impl Interceptor for ApiKeyAuth {
+    fn call(&mut self, request: Request<()>) -> Result<Request<()>, Status> {
+        if self.keys.is_empty() {
+            return Ok(request);
+        }
+        let token = request
+            .metadata()
+            .get("authorization")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .ok_or_else(|| Status::unauthenticated("Missing API key"))?;
+        let valid = self
+            .keys
+            .iter()
+            .any(|key| bool::from(key.as_bytes().ct_eq(token.trim().as_bytes())));
+        if !valid {
+            return Err(Status::unauthenticated("Invalid API key"));
+        }
+        Ok(request)
+    }
+}
+
+/// Periodically probes the database and the Twitch app token, flipping the
+/// standard gRPC health status of `StitchService` between SERVING and NOT_SERVING.
+async fn report_health(
+    reporter: HealthReporter,
+    pool: Pool,
+    api: Arc<TwitchAPI>,
+    cancel: CancellationToken,
+) {
+    let mut ticker = tokio::time::interval(HEALTH_CHECK_INTERVAL);
+    let mut serving = None;
+    loop {
+        tokio::select! {
+            _ = cancel.cancelled() => break,
+            _ = ticker.tick() => {}
+        }
+
+        let (db, twitch) = tokio::join!(ping(&pool), api.validate_token());
+        let healthy = match (db, twitch) {
+            (Ok(()), Ok(())) => true,
+            (db, twitch) => {
+                if let Err(e) = db {
+                    warn!(error = ?e, "Health check: database unavailable");
+                }
+                if let Err(e) = twitch {
+                    warn!(error = ?e, "Health check: Twitch token unavailable");
+                }
+                false
+            }
+        };
+
+        if serving != Some(healthy) {
+            serving = Some(healthy);
+            if healthy {
+                reporter
+                    .set_serving::<StitchServiceServer<StitchGRPC>>()
+                    .await;
+            } else {
+                reporter
+                    .set_not_serving::<StitchServiceServer<StitchGRPC>>()
+                    .await;
+            }
+            info!(healthy, "gRPC health status changed");
+        }
+    }
+    reporter
+        .set_not_serving::<StitchServiceServer<StitchGRPC>>()
+        .await;
+}
+
 fn shutdown_token() -> CancellationToken {
     let token = CancellationToken::new();
     let cancel = token.clone();
@@ -126,3 +389,36 @@ fn shutdown_token() -> CancellationToken {
     });
     token
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(authorization: Option<&str>) -> Request<()> {
+        let mut request = Request::new(());
+        if let Some(value) = authorization {
+            request
+                .metadata_mut()
+                .insert("authorization", value.parse().unwrap());
+        }
+        request
+    }
+
+    #[test]
+    fn test_api_key_auth() {
+        let mut auth = ApiKeyAuth::new(vec!["secret".to_string(), " other ".to_string()]);
+        assert!(auth.call(request(Some("Bearer secret"))).is_ok());
+        assert!(auth.call(request(Some("Bearer other"))).is_ok());
+
+        let err = auth.call(request(None)).unwrap_err();
+        assert_eq!(err.code(), tonic::Code::Unauthenticated);
+        assert!(auth.call(request(Some("Bearer wrong"))).is_err());
+        assert!(auth.call(request(Some("secret"))).is_err());
+    }
+
+    #[test]
+    fn test_api_key_auth_disabled() {
+        let mut auth = ApiKeyAuth::new(vec![String::new()]);
+        assert!(auth.call(request(None)).is_ok());
+    }
+}
@@ -13,6 +13,7 @@ use tracing::level_filters::LevelFilter;
 use tracing_subscriber::{filter, fmt, layer::SubscriberExt, util::SubscriberInitExt, Layer};
 
 use crate::config::ServerConfig;
+use crate::utils::log_tail::{LogTail, LOG_TAIL_CAPACITY};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -22,20 +23,21 @@ async fn main() -> Result<()> {
     let console_layer = ConsoleLayer::builder()
         .server_addr(([0, 0, 0, 0], cfg.tokio_console_port))
         .spawn();
-    let fmt_layer = fmt::layer().with_filter(
-        filter::Targets::new()
-            .with_target("stitch", LevelFilter::INFO)
-            .with_target("tokio", LevelFilter::OFF)
-            .with_target("runtime", LevelFilter::OFF)
-            .with_target("console_subscriber", LevelFilter::OFF)
-            .with_default(LevelFilter::INFO),
-    );
+    let targets = filter::Targets::new()
+        .with_target("stitch", LevelFilter::INFO)
+        .with_target("tokio", LevelFilter::OFF)
+        .with_target("runtime", LevelFilter::OFF)
+        .with_target("console_subscriber", LevelFilter::OFF)
+        .with_default(LevelFilter::INFO);
+    let fmt_layer = fmt::layer().with_filter(targets.clone());
+    let log_tail = LogTail::new(LOG_TAIL_CAPACITY);
 
     tracing_subscriber::registry()
         .with(console_layer)
         .with(fmt_layer)
+        .with(log_tail.clone().with_filter(targets))
         .init();
 
-    app::run(cfg).await?;
+    app::run(cfg, log_tail).await?;
     Ok(())
 }
@@ -1 +1,146 @@
 pub mod channel;
+pub mod export;
+pub mod info;
+pub mod stats;
+pub mod stream;
+
+use crate::adapters::twitch::TwitchError;
+use crate::utils::request_id;
+use chrono::{DateTime, TimeDelta, Utc};
+use proto::errors;
+use std::collections::HashMap;
+use tonic::{Code, Status};
+use tonic_types::{ErrorDetails, StatusExt};
+
+pub(crate) fn to_timestamp(dt: DateTime<Utc>) -> prost_types::Timestamp {
+    prost_types::Timestamp {
+        seconds: dt.timestamp(),
+        nanos: dt.timestamp_subsec_nanos() as i32,
+    }
+}
+
+pub(crate) fn to_duration(delta: TimeDelta) -> prost_types::Duration {
+    prost_types::Duration {
+        seconds: delta.num_seconds(),
+        nanos: delta.subsec_nanos(),
+    }
+}
+
+pub(crate) fn from_timestamp(ts: prost_types::Timestamp) -> Result<DateTime<Utc>, Status> {
+    DateTime::from_timestamp(ts.seconds, ts.nanos.max(0) as u32)
+        .ok_or_else(|| Status::invalid_argument("Timestamp out of range"))
+}
+
+/// A status carrying a `google.rpc.ErrorInfo` with `reason` from
+/// [`proto::errors`].
+pub(crate) fn status_with_reason(code: Code, message: impl Into<String>, reason: &str) -> Status {
+    Status::with_error_details(
+        code,
+        message,
+        ErrorDetails::with_error_info(reason, errors::DOMAIN, HashMap::new()),
+    )
+}
+
+/// An `INVALID_ARGUMENT` status with a `google.rpc.BadRequest` pointing at
+/// `field`.
+pub(crate) fn bad_request(field: &str, description: impl Into<String>) -> Status {
+    let description = description.into();
+    Status::with_error_details(
+        Code::InvalidArgument,
+        description.clone(),
+        ErrorDetails::with_bad_request_violation(field, description),
+    )
+}
+
+pub(crate) fn not_tracked() -> Status {
+    status_with_reason(
+        Code::NotFound,
+        "Channel not tracked",
+        errors::CHANNEL_NOT_TRACKED,
+    )
+}
+
+/// Maps a failed adapter call to a status, recognizing Twitch and database
+/// failures clients can act on. Anything else becomes `INTERNAL`, with the
+/// error only logged and the client given the request ID to look it up by.
+pub(crate) fn error_status(op: &str, e: &anyhow::Error) -> Status {
+    for cause in e.chain() {
+        if let Some(e) = cause.downcast_ref::<TwitchError>() {
+            let (code, reason) = match e {
+                TwitchError::UserNotFound(_) => (Code::NotFound, errors::TWITCH_USER_NOT_FOUND),
+                TwitchError::RateLimited => (Code::Unavailable, errors::TWITCH_RATE_LIMITED),
+                TwitchError::QuotaExceeded => {
+                    (Code::ResourceExhausted, errors::EVENTSUB_QUOTA_EXCEEDED)
+                }
+            };
+            return status_with_reason(code, e.to_string(), reason);
+        }
+        if let Some(sqlx::Error::PoolTimedOut | sqlx::Error::PoolClosed | sqlx::Error::Io(_)) =
+            cause.downcast_ref::<sqlx::Error>()
+        {
+            return status_with_reason(
+                Code::Unavailable,
+                format!("{op} failed: database unavailable"),
+                errors::DATABASE_UNAVAILABLE,
+            );
+        }
+    }
+    tracing::error!(error = ?e, "{op} failed");
+    let message = match request_id::current() {
+        Some(id) => format!("{op} failed: internal error (request ID {id})"),
+        None => format!("{op} failed: internal error"),
+    };
+    status_with_reason(Code::Internal, message, errors::INTERNAL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    fn reason(status: &Status) -> Option<String> {
+        status
+            .get_error_details()
+            .error_info()
+            .map(|i| i.reason.clone())
+    }
+
+    #[test]
+    fn test_error_status() {
+        let e = Err::<(), _>(TwitchError::UserNotFound("nobody".into()))
+            .context("Failed to get channel")
+            .unwrap_err();
+        let status = error_status("Track", &e);
+        assert_eq!(status.code(), Code::NotFound);
+        assert_eq!(
+            reason(&status).as_deref(),
+            Some(errors::TWITCH_USER_NOT_FOUND)
+        );
+
+        let e = anyhow::Error::new(sqlx::Error::PoolTimedOut).context("Failed to list channels");
+        let status = error_status("List", &e);
+        assert_eq!(status.code(), Code::Unavailable);
+        assert_eq!(
+            reason(&status).as_deref(),
+            Some(errors::DATABASE_UNAVAILABLE)
+        );
+
+        let status = error_status("List", &anyhow::anyhow!("boom"));
+        assert_eq!(status.code(), Code::Internal);
+        assert_eq!(reason(&status).as_deref(), Some(errors::INTERNAL));
+        assert_eq!(status.message(), "List failed: internal error");
+    }
+
+    #[tokio::test]
+    async fn test_internal_error_hides_details() {
+        let e = anyhow::anyhow!("password authentication failed for user \"stitch\"")
+            .context("Failed to list channels");
+        let status = request_id::CURRENT
+            .scope("abc-123".to_string(), async { error_status("List", &e) })
+            .await;
+        assert_eq!(
+            status.message(),
+            "List failed: internal error (request ID abc-123)"
+        );
+    }
+}
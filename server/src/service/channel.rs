@@ -1,13 +1,39 @@
 use crate::adapters::db::{
-    list_channels as db_list, track_channel as db_track, untrack_channel as db_untrack, Pool,
+    self, list_channels_page as db_list_page, search_channels as db_search,
+    track_channel as db_track, untrack_channel as db_untrack, ChannelFilter, ChannelOrder, Pool,
 };
-use crate::adapters::twitch::TwitchAPI;
-use crate::adapters::webhook::TwitchWebhook;
+use crate::adapters::twitch::{SubscriptionChange, TwitchAPI, TwitchChannel, CHANNEL_EVENTS};
+use crate::adapters::webhook::{Stream, TwitchWebhook};
+use crate::service::stream::to_live_stream;
+use crate::service::{
+    bad_request, error_status, not_tracked, status_with_reason, to_duration, to_timestamp,
+};
+use chrono::{TimeDelta, Utc};
 use dashmap::DashMap;
-use proto::stitch::Channel as ProtoChannel;
+use proto::errors;
+use proto::stitch::{
+    CategoryChangeMode, Channel as ProtoChannel, ChannelSettings as ProtoChannelSettings,
+    ChannelSort, ChannelValidation, EmailMode, HypeTrainMode, InactiveChannel, ListChannelsRequest,
+    ListChannelsResponse, MassMention, ResyncSubscriptionsResponse,
+    SubscriptionChange as ProtoSubscriptionChange, TagCount, TrackChannelResponse, UntrackResult,
+    ValidationOutcome,
+};
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use tonic::Status;
-use tracing::instrument;
+use tonic::{Code, Status};
+use tracing::{instrument, warn};
+
+const MAX_PAGE_SIZE: u32 = 1000;
+const MAX_COLOR: u32 = 0xFF_FF_FF;
+const PUSH_PRIORITIES: std::ops::RangeInclusive<u32> = 1..=5;
+const DEFAULT_SEARCH_LIMIT: u32 = 20;
+const MAX_SEARCH_LIMIT: u32 = 100;
+const MAX_LOGIN_LEN: usize = 25;
+const MAX_TAGS: usize = 20;
+const MAX_TAG_LEN: usize = 32;
+const MAX_VALIDATE_NAMES: usize = 100;
+const DEFAULT_INACTIVE_DAYS: u32 = 30;
+const MAX_INACTIVE_DAYS: u32 = 3650;
 
 #[derive(Clone)]
 pub struct ChannelService {
@@ -32,78 +58,902 @@ impl ChannelService {
         }
     }
 
+    /// Tracks a channel given as a login name, a `twitch.tv/<name>` URL or a
+    /// numeric Helix user ID.
     #[instrument(skip(self, name))]
-    pub async fn track_channel(&self, name: String) -> Result<ProtoChannel, Status> {
+    pub async fn track_channel(&self, name: String) -> Result<TrackChannelResponse, Status> {
+        let channel = match parse_channel_ref(&name)? {
+            ChannelRef::Login(login) => {
+                if self.channels.contains_key(&login) {
+                    return Err(status_with_reason(
+                        Code::AlreadyExists,
+                        "Channel already tracked",
+                        errors::CHANNEL_ALREADY_TRACKED,
+                    ));
+                }
+                self.lookup(ChannelRef::Login(login)).await
+            }
+            channel => self.lookup(channel).await,
+        }
+        .map_err(|e| error_status("get_channel", &e))?;
+        let name = channel.login.clone();
         if self.channels.contains_key(&name) {
-            return Err(Status::already_exists("Channel already tracked"));
+            return Err(status_with_reason(
+                Code::AlreadyExists,
+                "Channel already tracked",
+                errors::CHANNEL_ALREADY_TRACKED,
+            ));
         }
-        let channel = self
-            .twitch_api
-            .get_channel_by_name(&name)
-            .await
-            .map_err(|e| Status::internal(format!("get_channel_id failed: {e}")))?;
-        let db_channel = db_track(&self.pool, &name, &channel.display_name, &channel.id)
-            .await
-            .map_err(|e| {
-                tracing::error!(error = %e, "db_track failed");
-                Status::internal(format!("db_track failed: {e:#}"))
-            })?;
+        let db_channel = db_track(
+            &self.pool,
+            &name,
+            &channel.display_name,
+            &channel.id,
+            &channel.profile_image_url,
+        )
+        .await
+        .map_err(|e| error_status("db_track", &e))?;
         self.webhook
             .track_channel(&channel.id, db_channel.clone())
             .await
-            .map_err(|e| Status::internal(format!("track_channel failed: {e:#}")))?;
+            .map_err(|e| error_status("track_channel", &e.into()))?;
         self.twitch_api
             .subscribe_channel(&channel.id)
             .await
-            .map_err(|e| Status::internal(format!("subscribe failed: {e}")))?;
+            .map_err(|e| error_status("subscribe", &e))?;
+        // Tracking picks up an ongoing stream, so this reflects it right away.
+        let stream = self.webhook.live_stream(&channel.id).await;
         self.channels.insert(name.clone(), channel.id);
-        Ok(ProtoChannel {
-            id: db_channel.id,
-            name: db_channel.name,
+        Ok(TrackChannelResponse {
+            channel: Some(to_proto_channel(db_channel)),
+            live: stream.is_some(),
+            stream: stream.as_ref().map(to_live_stream),
         })
     }
 
+    /// Reports what tracking, or with `untrack` untracking, each of `names`
+    /// would do, including the EventSub subscriptions involved, without
+    /// changing anything.
+    #[instrument(skip(self))]
+    pub async fn validate_channels(
+        &self,
+        names: Vec<String>,
+        untrack: bool,
+    ) -> Result<Vec<ChannelValidation>, Status> {
+        let mut seen = HashSet::new();
+        let names: Vec<String> = names
+            .into_iter()
+            .map(|n| n.trim().to_string())
+            .filter(|n| !n.is_empty() && seen.insert(n.clone()))
+            .collect();
+        if names.is_empty() {
+            return Err(bad_request("names", "No channel names given"));
+        }
+        if names.len() > MAX_VALIDATE_NAMES {
+            return Err(bad_request(
+                "names",
+                format!("At most {MAX_VALIDATE_NAMES} channels can be checked at once"),
+            ));
+        }
+
+        Ok(
+            futures::future::join_all(names.into_iter().map(|name| async move {
+                match untrack {
+                    true => self.validate_untrack(name).await,
+                    false => self.validate_track(name).await,
+                }
+            }))
+            .await,
+        )
+    }
+
+    async fn validate_track(&self, name: String) -> ChannelValidation {
+        let mut result = ChannelValidation {
+            name: name.clone(),
+            ..Default::default()
+        };
+        let channel = match parse_channel_ref(&name) {
+            Ok(channel) => self.lookup(channel).await,
+            Err(e) => {
+                result.set_outcome(ValidationOutcome::Invalid);
+                result.error = e.message().to_string();
+                return result;
+            }
+        };
+        let channel = match channel {
+            Ok(channel) => channel,
+            Err(e) => {
+                let status = error_status("get_channel", &e);
+                result.set_outcome(match status.code() {
+                    Code::NotFound => ValidationOutcome::NotFound,
+                    _ => ValidationOutcome::LookupFailed,
+                });
+                result.error = status.message().to_string();
+                return result;
+            }
+        };
+
+        if self.channels.contains_key(&channel.login) {
+            result.set_outcome(ValidationOutcome::AlreadyTracked);
+        } else {
+            result.set_outcome(ValidationOutcome::WouldTrack);
+            result.subscriptions = CHANNEL_EVENTS
+                .iter()
+                .map(|kind| ProtoSubscriptionChange {
+                    twitch_id: channel.id.clone(),
+                    name: channel.login.clone(),
+                    r#type: kind.to_string(),
+                    error: String::new(),
+                })
+                .collect();
+        }
+        result.login = channel.login;
+        result.display_name = channel.display_name;
+        result.twitch_id = channel.id;
+        result
+    }
+
+    async fn validate_untrack(&self, name: String) -> ChannelValidation {
+        let mut result = ChannelValidation {
+            name: name.clone(),
+            ..Default::default()
+        };
+        let channel = match self.tracked_channel(&name).await {
+            Ok(channel) => channel,
+            Err(e) => {
+                result.set_outcome(match e.code() {
+                    Code::NotFound => ValidationOutcome::NotTracked,
+                    _ => ValidationOutcome::LookupFailed,
+                });
+                result.error = e.message().to_string();
+                return result;
+            }
+        };
+
+        result.set_outcome(ValidationOutcome::WouldUntrack);
+        match self
+            .twitch_api
+            .get_subscriptions(Some(&channel.channel_id))
+            .await
+        {
+            Ok(subscriptions) => {
+                result.subscriptions = subscriptions
+                    .into_iter()
+                    .map(|subscription| ProtoSubscriptionChange {
+                        twitch_id: channel.channel_id.clone(),
+                        name: channel.name.clone(),
+                        r#type: subscription.event().to_string(),
+                        error: String::new(),
+                    })
+                    .collect();
+            }
+            Err(e) => {
+                let status = error_status("get_subscriptions", &e);
+                result.set_outcome(ValidationOutcome::LookupFailed);
+                result.error = status.message().to_string();
+            }
+        }
+        result.login = channel.name;
+        result.display_name = channel.display_name;
+        result.twitch_id = channel.channel_id;
+        result
+    }
+
+    /// Looks a channel up on Twitch by login or user ID.
+    async fn lookup(&self, channel: ChannelRef) -> anyhow::Result<TwitchChannel> {
+        match channel {
+            ChannelRef::Login(login) => self.twitch_api.get_channel_by_name(&login).await,
+            // All-digit logins exist too, so fall back to a login lookup.
+            ChannelRef::Id(id) => match self.twitch_api.get_channel(&id).await {
+                Ok(channel) => Ok(channel),
+                Err(_) => self.twitch_api.get_channel_by_name(&id).await,
+            },
+        }
+    }
+
     #[instrument(skip(self, name))]
     pub async fn untrack_channel(&self, name: String) -> Result<(), Status> {
         if !self.channels.contains_key(&name) {
-            return Err(Status::not_found("Channel not tracked"));
+            return Err(not_tracked());
         }
         let channel = self
             .twitch_api
             .get_channel_by_name(&name)
             .await
-            .map_err(|e| Status::internal(format!("get_channel failed: {e}")))?;
+            .map_err(|e| error_status("get_channel", &e))?;
+        if let Err(e) = self.twitch_api.unsubscribe_channel(&channel.id).await {
+            tracing::warn!("Failed to unsubscribe from Twitch: {e:#}");
+        }
+        self.webhook
+            .untrack_channel(&channel.id)
+            .await
+            .map_err(|e| error_status("untrack_channel", &e.into()))?;
+        db_untrack(&self.pool, &name)
+            .await
+            .map_err(|e| error_status("db_untrack", &e))?;
+        self.channels.remove(&name);
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    pub async fn set_channel_tags(
+        &self,
+        name: &str,
+        tags: Vec<String>,
+    ) -> Result<ProtoChannel, Status> {
+        let tags = normalize_tags(tags)?;
+        let mut channel = self.tracked_channel(name).await?;
+        channel.tags = db::set_channel_tags(&self.pool, &channel.channel_id, &tags)
+            .await
+            .map_err(|e| error_status("set_channel_tags", &e))?;
+        Ok(to_proto_channel(channel))
+    }
+
+    #[instrument(skip(self))]
+    pub async fn list_tags(&self) -> Result<Vec<TagCount>, Status> {
+        let tags = db::list_tags(&self.pool)
+            .await
+            .map_err(|e| error_status("list_tags", &e))?;
+        Ok(tags
+            .into_iter()
+            .map(|(tag, channels)| TagCount {
+                tag,
+                channels: channels as u32,
+            })
+            .collect())
+    }
+
+    #[instrument(skip(self))]
+    pub async fn pause_tracking(&self, name: &str) -> Result<ProtoChannel, Status> {
+        let channel = self.tracked_channel(name).await?;
+        if channel.paused {
+            return Err(Status::failed_precondition("Channel already paused"));
+        }
+        let channel = db::set_channel_paused(&self.pool, name, true)
+            .await
+            .map_err(|e| error_status("set_channel_paused", &e))?;
+        self.webhook
+            .pause_channel(channel.clone())
+            .await
+            .map_err(|e| error_status("pause_channel", &e.into()))?;
         if let Err(e) = self
             .twitch_api
-            .unsubscribe_channel(&channel.id)
+            .unsubscribe_channel(&channel.channel_id)
             .await
-            .map_err(|e| Status::internal(format!("unsubscribe failed: {e}")))
         {
             tracing::warn!("Failed to unsubscribe from Twitch: {e}");
         }
+        Ok(to_proto_channel(channel))
+    }
+
+    #[instrument(skip(self))]
+    pub async fn resume_tracking(&self, name: &str) -> Result<ProtoChannel, Status> {
+        let channel = self.tracked_channel(name).await?;
+        if !channel.paused {
+            return Err(Status::failed_precondition("Channel not paused"));
+        }
+        let channel = db::set_channel_paused(&self.pool, name, false)
+            .await
+            .map_err(|e| error_status("set_channel_paused", &e))?;
+        self.twitch_api
+            .subscribe_channel(&channel.channel_id)
+            .await
+            .map_err(|e| error_status("subscribe", &e))?;
+        self.resubscribe_hype_train(&channel).await;
         self.webhook
-            .untrack_channel(&channel.id)
+            .track_channel(&channel.channel_id, channel.clone())
             .await
-            .map_err(|e| Status::internal(format!("untrack_channel failed: {e}")))?;
-        db_untrack(&self.pool, &name).await.map_err(|e| {
-            tracing::error!(error = %e, "db_untrack failed");
-            Status::internal(format!("db_untrack failed: {e:#}"))
-        })?;
-        self.channels.remove(&name);
-        Ok(())
+            .map_err(|e| error_status("track_channel", &e.into()))?;
+        Ok(to_proto_channel(channel))
     }
 
+    /// Restores the Hype Train subscriptions of a channel that follows them.
+    /// The broadcaster may have withdrawn the scope they need in the
+    /// meantime, which isn't worth failing the resume over.
+    async fn resubscribe_hype_train(&self, channel: &db::Channel) {
+        let following = match db::get_channel_settings(&self.pool, &channel.channel_id).await {
+            Ok(settings) => settings.hype_train.is_some(),
+            Err(e) => {
+                warn!("Failed to get settings of {}: {e:#}", channel.name);
+                return;
+            }
+        };
+        if !following {
+            return;
+        }
+        if let Err(e) = self
+            .twitch_api
+            .subscribe_hype_train(&channel.channel_id, true)
+            .await
+        {
+            warn!(
+                "Failed to subscribe {} to Hype Train events: {e:#}",
+                channel.name
+            );
+        }
+    }
+
+    /// Untracks `names` in one database transaction, then tears down their
+    /// EventSub subscriptions and live Discord messages concurrently.
     #[instrument(skip(self))]
-    pub async fn list_channels(&self) -> Result<Vec<ProtoChannel>, Status> {
-        let db_channels = db_list(&self.pool)
+    pub async fn untrack_channels(&self, names: Vec<String>) -> Result<Vec<UntrackResult>, Status> {
+        let mut seen = HashSet::new();
+        let names: Vec<String> = names
+            .into_iter()
+            .map(|n| n.trim().to_string())
+            .filter(|n| !n.is_empty() && seen.insert(n.clone()))
+            .collect();
+        let tracked: Vec<String> = names
+            .iter()
+            .filter(|n| self.channels.contains_key(*n))
+            .cloned()
+            .collect();
+
+        let untracked: HashSet<String> = db::untrack_channels(&self.pool, &tracked)
             .await
-            .map_err(|e| Status::internal(format!("db_list failed: {e}")))?;
-        Ok(db_channels
+            .map_err(|e| error_status("db_untrack", &e))?
             .into_iter()
-            .map(|c| ProtoChannel {
-                id: c.id,
-                name: c.name,
+            .collect();
+
+        let results = futures::future::join_all(names.into_iter().map(|name| async {
+            if !untracked.contains(&name) {
+                return UntrackResult {
+                    name,
+                    success: false,
+                    error: "Channel not tracked".to_string(),
+                };
+            }
+            let Some((_, channel_id)) = self.channels.remove(&name) else {
+                return UntrackResult {
+                    name,
+                    success: true,
+                    error: String::new(),
+                };
+            };
+            let (unsubscribed, cleaned) = tokio::join!(
+                self.twitch_api.unsubscribe_channel(&channel_id),
+                self.webhook.untrack_channel(&channel_id),
+            );
+            let mut errors = Vec::new();
+            if let Err(e) = unsubscribed {
+                errors.push(format!("unsubscribe failed: {e:#}"));
+            }
+            if let Err(e) = cleaned {
+                errors.push(format!("untrack_channel failed: {e}"));
+            }
+            UntrackResult {
+                name,
+                success: errors.is_empty(),
+                error: errors.join("; "),
+            }
+        }))
+        .await;
+        Ok(results)
+    }
+
+    /// Tracked channels with no stream starting in the last `days`. Channels
+    /// tracked more recently than that, or live right now, aren't included.
+    #[instrument(skip(self))]
+    pub async fn list_inactive_channels(&self, days: u32) -> Result<Vec<InactiveChannel>, Status> {
+        let days = inactive_days(days)?;
+        let cutoff = Utc::now() - TimeDelta::days(i64::from(days));
+        let live: HashSet<String> = self.webhook.live_channel_ids().into_iter().collect();
+        let channels = db::list_inactive_channels(&self.pool, cutoff)
+            .await
+            .map_err(|e| error_status("list_inactive_channels", &e))?;
+        Ok(channels
+            .into_iter()
+            .filter(|c| !live.contains(&c.channel.channel_id))
+            .map(|c| InactiveChannel {
+                last_stream_at: c.last_stream_at.map(to_timestamp),
+                channel: Some(to_proto_channel(c.channel)),
             })
             .collect())
     }
+
+    #[instrument(skip(self))]
+    pub async fn list_channels(
+        &self,
+        req: ListChannelsRequest,
+    ) -> Result<ListChannelsResponse, Status> {
+        let offset = match req.page_token.as_str() {
+            "" => 0,
+            token => token
+                .parse::<i64>()
+                .ok()
+                .filter(|offset| *offset >= 0)
+                .ok_or_else(|| bad_request("page_token", "Invalid page token"))?,
+        };
+        let order = match req.sort() {
+            ChannelSort::Unspecified | ChannelSort::Id => ChannelOrder::Id,
+            ChannelSort::Name => ChannelOrder::Name,
+            ChannelSort::CreatedAt => ChannelOrder::CreatedAt,
+        };
+        let page_size = req.page_size.min(MAX_PAGE_SIZE);
+        // Fetch one extra row to find out whether another page follows.
+        let limit = (page_size > 0).then(|| i64::from(page_size) + 1);
+
+        let live_ids = req.live_only.then(|| self.webhook.live_channel_ids());
+        let tag = req.tag.trim().to_lowercase();
+        let filter = ChannelFilter {
+            text: req.filter.trim(),
+            tag: &tag,
+            channel_ids: live_ids.as_deref(),
+        };
+
+        let mut db_channels =
+            db_list_page(&self.pool, &filter, order, req.descending, limit, offset)
+                .await
+                .map_err(|e| error_status("db_list", &e))?;
+
+        let next_page_token = if page_size > 0 && db_channels.len() > page_size as usize {
+            db_channels.truncate(page_size as usize);
+            (offset + i64::from(page_size)).to_string()
+        } else {
+            String::new()
+        };
+
+        let live: HashMap<String, Stream> = self
+            .webhook
+            .live_streams()
+            .await
+            .into_iter()
+            .map(|stream| (stream.channel_id.clone(), stream))
+            .collect();
+        let now = Utc::now();
+        let channels = db_channels
+            .into_iter()
+            .map(|channel| {
+                let stream = live.get(&channel.channel_id);
+                let mut channel = to_proto_channel(channel);
+                if let Some(stream) = stream {
+                    channel.is_live = true;
+                    channel.category = stream.category.clone();
+                    channel.uptime = Some(to_duration(now - stream.started_at));
+                }
+                channel
+            })
+            .collect();
+
+        Ok(ListChannelsResponse {
+            channels,
+            next_page_token,
+        })
+    }
+
+    #[instrument(skip(self))]
+    pub async fn search_channels(
+        &self,
+        query: &str,
+        live_only: bool,
+        include_streams: bool,
+        limit: u32,
+    ) -> Result<Vec<ProtoChannel>, Status> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Err(bad_request("query", "Search query must not be empty"));
+        }
+        let limit = match limit {
+            0 => DEFAULT_SEARCH_LIMIT,
+            n => n.min(MAX_SEARCH_LIMIT),
+        };
+        let live = live_only.then(|| self.webhook.live_channel_ids());
+        let matched = match include_streams {
+            true => stream_matches(&self.webhook.live_streams().await, query),
+            false => Vec::new(),
+        };
+
+        let db_channels = db_search(
+            &self.pool,
+            query,
+            &matched,
+            live.as_deref(),
+            i64::from(limit),
+        )
+        .await
+        .map_err(|e| error_status("db_search", &e))?;
+        Ok(db_channels.into_iter().map(to_proto_channel).collect())
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get_channel_settings(&self, name: &str) -> Result<ProtoChannelSettings, Status> {
+        let channel = self.tracked_channel(name).await?;
+        let settings = db::get_channel_settings(&self.pool, &channel.channel_id)
+            .await
+            .map_err(|e| error_status("get_channel_settings", &e))?;
+        Ok(to_proto_settings(settings))
+    }
+
+    #[instrument(skip(self))]
+    pub async fn update_channel_settings(
+        &self,
+        name: &str,
+        settings: ProtoChannelSettings,
+    ) -> Result<ProtoChannelSettings, Status> {
+        if settings.color.is_some_and(|c| c > MAX_COLOR) {
+            return Err(bad_request(
+                "settings.color",
+                "Color must be a 0xRRGGBB value",
+            ));
+        }
+        if settings
+            .push_priority
+            .is_some_and(|p| !PUSH_PRIORITIES.contains(&p))
+        {
+            return Err(bad_request(
+                "settings.push_priority",
+                "Push priority must be between 1 and 5",
+            ));
+        }
+        let channel = self.tracked_channel(name).await?;
+        let hype_train = match settings.hype_train() {
+            HypeTrainMode::Unspecified => None,
+            HypeTrainMode::Annotate => Some("annotate".to_string()),
+            HypeTrainMode::Post => Some("post".to_string()),
+        };
+        let category_change = match settings.category_change() {
+            CategoryChangeMode::Unspecified => None,
+            CategoryChangeMode::Post => Some("post".to_string()),
+            CategoryChangeMode::Reply => Some("reply".to_string()),
+        };
+        let current = db::get_channel_settings(&self.pool, &channel.channel_id)
+            .await
+            .map_err(|e| error_status("get_channel_settings", &e))?;
+        // Following Hype Trains only takes once Twitch accepts the
+        // subscriptions, which it doesn't without the broadcaster's consent.
+        if hype_train.is_some() && current.hype_train.is_none() {
+            self.twitch_api
+                .subscribe_hype_train(&channel.channel_id, true)
+                .await
+                .map_err(|e| {
+                    Status::failed_precondition(format!(
+                        "Could not subscribe to Hype Train events, has the broadcaster \
+                         authorized channel:read:hype_train? {e:#}"
+                    ))
+                })?;
+        }
+        let settings = db::ChannelSettings {
+            mention_role_id: (settings.mention_role_id != 0)
+                .then_some(settings.mention_role_id as i64),
+            color: settings.color.map(|c| c as i32),
+            muted: settings.muted,
+            mass_mention: match settings.mass_mention() {
+                MassMention::Unspecified => None,
+                MassMention::Everyone => Some("everyone".to_string()),
+                MassMention::Here => Some("here".to_string()),
+            },
+            push: settings.push,
+            push_priority: settings.push_priority.map(|p| p as i16),
+            email: match settings.email() {
+                EmailMode::Unspecified => None,
+                EmailMode::Off => Some("off".to_string()),
+                EmailMode::Live => Some("live".to_string()),
+                EmailMode::Digest => Some("digest".to_string()),
+            },
+            categories: filter_terms(settings.categories),
+            muted_keywords: filter_terms(settings.muted_keywords),
+            hype_train,
+            category_change,
+        };
+        let settings = db::update_channel_settings(&self.pool, &channel.channel_id, &settings)
+            .await
+            .map_err(|e| error_status("update_channel_settings", &e))?;
+        if settings.hype_train.is_none() && current.hype_train.is_some() {
+            if let Err(e) = self
+                .twitch_api
+                .subscribe_hype_train(&channel.channel_id, false)
+                .await
+            {
+                // The next resync drops them.
+                warn!(
+                    "Failed to unsubscribe {} from Hype Train events: {e:#}",
+                    channel.name
+                );
+            }
+        }
+        Ok(to_proto_settings(settings))
+    }
+
+    #[instrument(skip(self))]
+    pub async fn resync_subscriptions(&self) -> Result<ResyncSubscriptionsResponse, Status> {
+        // Paused channels are left out so their subscriptions get dropped.
+        let names: HashMap<String, String> = db::list_channels(&self.pool)
+            .await
+            .map_err(|e| error_status("list_channels", &e))?
+            .into_iter()
+            .filter(|c| !c.paused)
+            .map(|c| (c.channel_id, c.name))
+            .collect();
+        let channel_ids: Vec<String> = names.keys().cloned().collect();
+        let hype_trains = db::list_hype_train_channels(&self.pool)
+            .await
+            .map_err(|e| error_status("list_hype_train_channels", &e))?;
+
+        let report = self
+            .twitch_api
+            .sync(&channel_ids, &hype_trains)
+            .await
+            .map_err(|e| error_status("sync", &e))?;
+
+        let to_proto = |change: &SubscriptionChange, error: String| ProtoSubscriptionChange {
+            name: names
+                .get(&change.broadcaster_user_id)
+                .cloned()
+                .unwrap_or_default(),
+            twitch_id: change.broadcaster_user_id.clone(),
+            r#type: change.kind.clone(),
+            error,
+        };
+        Ok(ResyncSubscriptionsResponse {
+            added: report
+                .added
+                .iter()
+                .map(|c| to_proto(c, String::new()))
+                .collect(),
+            removed: report
+                .removed
+                .iter()
+                .map(|c| to_proto(c, String::new()))
+                .collect(),
+            stale: report
+                .stale
+                .iter()
+                .map(|c| to_proto(c, String::new()))
+                .collect(),
+            failed: report
+                .failed
+                .iter()
+                .map(|(c, e)| to_proto(c, e.clone()))
+                .collect(),
+            kept: report.kept as u32,
+        })
+    }
+
+    async fn tracked_channel(&self, name: &str) -> Result<db::Channel, Status> {
+        db::get_channel_by_name(&self.pool, name)
+            .await
+            .map_err(|e| error_status("get_channel_by_name", &e))?
+            .filter(|c| c.active)
+            .ok_or_else(not_tracked)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum ChannelRef {
+    Login(String),
+    Id(String),
+}
+
+/// Normalizes user input naming a channel: a login (optionally prefixed with
+/// `@`), a Twitch channel URL, or a numeric user ID.
+fn parse_channel_ref(input: &str) -> Result<ChannelRef, Status> {
+    let input = input.trim();
+    let without_scheme = input
+        .strip_prefix("https://")
+        .or_else(|| input.strip_prefix("http://"))
+        .unwrap_or(input);
+    let host_path = ["www.", "m."]
+        .iter()
+        .find_map(|p| without_scheme.strip_prefix(p))
+        .unwrap_or(without_scheme);
+
+    let url_path = host_path.strip_prefix("twitch.tv/");
+    let name = match url_path {
+        Some(path) => path.split(['/', '?', '#']).next().unwrap_or_default(),
+        None if without_scheme != input => {
+            return Err(bad_request(
+                "name",
+                format!("Not a Twitch channel URL: {input}"),
+            ));
+        }
+        None => input.strip_prefix('@').unwrap_or(input),
+    };
+
+    if name.is_empty()
+        || name.len() > MAX_LOGIN_LEN
+        || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        return Err(bad_request(
+            "name",
+            format!("Invalid channel name: {input}"),
+        ));
+    }
+    if url_path.is_none() && name.chars().all(|c| c.is_ascii_digit()) {
+        return Ok(ChannelRef::Id(name.to_string()));
+    }
+    Ok(ChannelRef::Login(name.to_lowercase()))
+}
+
+/// Lowercases, trims and deduplicates `tags`, rejecting anything that isn't a
+/// short `[a-z0-9_-]` word.
+fn normalize_tags(tags: Vec<String>) -> Result<Vec<String>, Status> {
+    let mut normalized: Vec<String> = tags
+        .into_iter()
+        .map(|t| t.trim().to_lowercase())
+        .filter(|t| !t.is_empty())
+        .collect();
+    normalized.sort();
+    normalized.dedup();
+
+    if normalized.len() > MAX_TAGS {
+        return Err(bad_request(
+            "tags",
+            format!("At most {MAX_TAGS} tags are allowed"),
+        ));
+    }
+    if let Some(tag) = normalized.iter().find(|t| {
+        t.len() > MAX_TAG_LEN
+            || !t
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    }) {
+        return Err(bad_request("tags", format!("Invalid tag `{tag}`")));
+    }
+    Ok(normalized)
+}
+
+/// `days` for [`ChannelService::list_inactive_channels`], 0 meaning the default.
+fn inactive_days(days: u32) -> Result<u32, Status> {
+    match days {
+        0 => Ok(DEFAULT_INACTIVE_DAYS),
+        days if days > MAX_INACTIVE_DAYS => Err(bad_request(
+            "days",
+            format!("days must be at most {MAX_INACTIVE_DAYS}"),
+        )),
+        days => Ok(days),
+    }
+}
+
+/// Trimmed, non-empty filter terms without case-insensitive duplicates.
+fn filter_terms(terms: Vec<String>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    terms
+        .into_iter()
+        .map(|term| term.trim().to_string())
+        .filter(|term| !term.is_empty() && seen.insert(term.to_lowercase()))
+        .collect()
+}
+
+fn to_proto_settings(settings: db::ChannelSettings) -> ProtoChannelSettings {
+    ProtoChannelSettings {
+        mention_role_id: settings.mention_role_id.unwrap_or_default() as u64,
+        color: settings.color.map(|c| c as u32),
+        muted: settings.muted,
+        mass_mention: match settings.mass_mention.as_deref() {
+            Some("everyone") => MassMention::Everyone,
+            Some("here") => MassMention::Here,
+            _ => MassMention::Unspecified,
+        }
+        .into(),
+        push: settings.push,
+        push_priority: settings.push_priority.map(|p| p as u32),
+        email: match settings.email.as_deref() {
+            Some("off") => EmailMode::Off,
+            Some("live") => EmailMode::Live,
+            Some("digest") => EmailMode::Digest,
+            _ => EmailMode::Unspecified,
+        }
+        .into(),
+        categories: settings.categories,
+        muted_keywords: settings.muted_keywords,
+        hype_train: match settings.hype_train.as_deref() {
+            Some("annotate") => HypeTrainMode::Annotate,
+            Some("post") => HypeTrainMode::Post,
+            _ => HypeTrainMode::Unspecified,
+        }
+        .into(),
+        category_change: match settings.category_change.as_deref() {
+            Some("post") => CategoryChangeMode::Post,
+            Some("reply") => CategoryChangeMode::Reply,
+            _ => CategoryChangeMode::Unspecified,
+        }
+        .into(),
+    }
+}
+
+pub(crate) fn to_proto_channel(channel: db::Channel) -> ProtoChannel {
+    ProtoChannel {
+        id: channel.id,
+        name: channel.name,
+        display_name: channel.display_name,
+        twitch_id: channel.channel_id,
+        paused: channel.paused,
+        tags: channel.tags,
+        profile_image_url: channel.profile_image_url,
+        broken_reason: channel.broken_reason.unwrap_or_default(),
+        is_live: false,
+        category: String::new(),
+        uptime: None,
+        created_at: Some(to_timestamp(channel.created_at.and_utc())),
+        updated_at: Some(to_timestamp(channel.updated_at.and_utc())),
+    }
+}
+
+/// Twitch IDs of the channels whose live stream has `query` in its title or
+/// category, ignoring case.
+fn stream_matches(streams: &[Stream], query: &str) -> Vec<String> {
+    let query = query.to_lowercase();
+    streams
+        .iter()
+        .filter(|s| {
+            s.title.to_lowercase().contains(&query) || s.category.to_lowercase().contains(&query)
+        })
+        .map(|s| s.channel_id.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_matches() {
+        let stream = |channel_id: &str, title: &str, category: &str| Stream {
+            id: String::new(),
+            channel_id: channel_id.to_string(),
+            user_login: String::new(),
+            user_name: String::new(),
+            title: title.to_string(),
+            category: category.to_string(),
+            events: Vec::new(),
+            started_at: Utc::now(),
+            last_updated: Utc::now(),
+            messages: Vec::new(),
+            profile_image_url: String::new(),
+            thumbnail_url: String::new(),
+            hype_train: None,
+        };
+        let streams = [
+            stream("1", "Speedrunning all day", "Celeste"),
+            stream("2", "Chill vibes", "Just Chatting"),
+            stream("3", "Road to grandmaster", "StarCraft II"),
+        ];
+        assert_eq!(stream_matches(&streams, "SPEEDRUN"), vec!["1"]);
+        assert_eq!(stream_matches(&streams, "chatting"), vec!["2"]);
+        assert_eq!(stream_matches(&streams, "r"), vec!["1", "3"]);
+        assert!(stream_matches(&streams, "minecraft").is_empty());
+    }
+
+    #[test]
+    fn test_parse_channel_ref() {
+        let parse = |s: &str| parse_channel_ref(s).ok();
+        let login = |s: &str| Some(ChannelRef::Login(s.to_string()));
+        assert_eq!(parse("Shroud"), login("shroud"));
+        assert_eq!(parse(" @shroud "), login("shroud"));
+        assert_eq!(parse("https://www.twitch.tv/Shroud"), login("shroud"));
+        assert_eq!(parse("twitch.tv/shroud/videos?filter=all"), login("shroud"));
+        assert_eq!(parse("https://m.twitch.tv/shroud#chat"), login("shroud"));
+        // A numeric path segment in a URL is still a login.
+        assert_eq!(parse("twitch.tv/12345"), login("12345"));
+        assert_eq!(
+            parse("37402112"),
+            Some(ChannelRef::Id("37402112".to_string()))
+        );
+
+        assert!(parse_channel_ref("").is_err());
+        assert!(parse_channel_ref("https://youtube.com/shroud").is_err());
+        assert!(parse_channel_ref("twitch.tv/").is_err());
+        assert!(parse_channel_ref("not a name").is_err());
+    }
+
+    #[test]
+    fn test_normalize_tags() {
+        let tags = vec![
+            " Friends ".to_string(),
+            "esports".to_string(),
+            "friends".to_string(),
+            "".to_string(),
+        ];
+        assert_eq!(normalize_tags(tags).unwrap(), vec!["esports", "friends"]);
+
+        assert!(normalize_tags(vec!["two words".to_string()]).is_err());
+        assert!(normalize_tags(vec!["x".repeat(MAX_TAG_LEN + 1)]).is_err());
+    }
+
+    #[test]
+    fn test_inactive_days() {
+        assert_eq!(inactive_days(0).unwrap(), DEFAULT_INACTIVE_DAYS);
+        assert_eq!(inactive_days(90).unwrap(), 90);
+        assert!(inactive_days(MAX_INACTIVE_DAYS + 1).is_err());
+    }
 }
@@ -0,0 +1,59 @@
+use crate::adapters::db::{self, Pool};
+use crate::service::error_status;
+use futures::Stream;
+use proto::stitch::ExportDataChunk;
+use serde::Serialize;
+use tonic::Status;
+use tracing::instrument;
+
+/// Records per streamed chunk.
+const EXPORT_CHUNK_RECORDS: usize = 500;
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ExportRecord<'a> {
+    Channel(&'a db::Channel),
+    Stream(&'a db::Stream),
+}
+
+#[derive(Clone)]
+pub struct ExportService {
+    pool: Pool,
+}
+
+impl ExportService {
+    pub fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+
+    /// Dumps all channels followed by all streams as NDJSON chunks.
+    #[instrument(skip(self))]
+    pub async fn export_data(
+        &self,
+    ) -> Result<impl Stream<Item = Result<ExportDataChunk, Status>> + Send + 'static, Status> {
+        let (channels, streams) = tokio::try_join!(
+            db::export_channels(&self.pool),
+            db::export_streams(&self.pool)
+        )
+        .map_err(|e| error_status("export", &e))?;
+
+        let records = channels
+            .iter()
+            .map(ExportRecord::Channel)
+            .chain(streams.iter().map(ExportRecord::Stream))
+            .collect::<Vec<_>>();
+        let chunks = records
+            .chunks(EXPORT_CHUNK_RECORDS)
+            .map(|chunk| {
+                let mut data = Vec::new();
+                for record in chunk {
+                    serde_json::to_writer(&mut data, record)
+                        .map_err(|e| Status::internal(format!("serialize failed: {e}")))?;
+                    data.push(b'\n');
+                }
+                Ok(ExportDataChunk { data })
+            })
+            .collect::<Vec<_>>();
+        Ok(futures::stream::iter(chunks))
+    }
+}
@@ -0,0 +1,244 @@
+use crate::adapters::twitch::{TwitchAPI, TwitchError};
+use crate::adapters::webhook::TwitchWebhook;
+use crate::service::{error_status, not_tracked, status_with_reason, to_duration, to_timestamp};
+use crate::utils::log_tail::{LogEntry, LogTail};
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use futures::{Stream as FuturesStream, StreamExt};
+use proto::errors;
+use proto::stitch::{
+    EventSubSubscription, GetServerInfoResponse, LogEntry as ProtoLogEntry, LogLevel,
+    PostedMessage, SendTestNotificationResponse, TailLogsRequest,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_util::sync::CancellationToken;
+use tonic::{Code, Status};
+use tracing::{instrument, warn};
+
+const DEFAULT_LOG_BACKLOG: u32 = 100;
+
+#[derive(Clone)]
+pub struct InfoService {
+    started_at: DateTime<Utc>,
+    channels: Arc<DashMap<String, String>>,
+    webhook: Arc<TwitchWebhook>,
+    twitch_api: Arc<TwitchAPI>,
+    log_tail: LogTail,
+    shutdown: CancellationToken,
+}
+
+impl InfoService {
+    pub fn new(
+        started_at: DateTime<Utc>,
+        channels: Arc<DashMap<String, String>>,
+        webhook: Arc<TwitchWebhook>,
+        twitch_api: Arc<TwitchAPI>,
+        log_tail: LogTail,
+        shutdown: CancellationToken,
+    ) -> Self {
+        Self {
+            started_at,
+            channels,
+            webhook,
+            twitch_api,
+            log_tail,
+            shutdown,
+        }
+    }
+
+    /// Recent server log entries, then new ones as they're logged if
+    /// `follow` is set.
+    pub fn tail_logs(
+        &self,
+        request: TailLogsRequest,
+    ) -> impl FuturesStream<Item = Result<ProtoLogEntry, Status>> + Send + 'static {
+        let min_level = request.min_level();
+        let wanted = move |entry: &LogEntry| {
+            to_proto_level(entry.level) as i32 >= min_level as i32
+                && entry.target.starts_with(&request.target)
+        };
+
+        // Subscribe before taking the backlog so nothing falls in between.
+        let live = request.follow.then(|| self.log_tail.subscribe());
+        let backlog = match request.backlog {
+            0 => DEFAULT_LOG_BACKLOG,
+            n => n,
+        };
+        let backlog: Vec<LogEntry> = self
+            .log_tail
+            .recent(backlog as usize)
+            .into_iter()
+            .filter(&wanted)
+            .collect();
+        let last_seq = backlog.last().map(|e| e.seq);
+
+        let live = live.map(|receiver| {
+            // A lagging follower just misses entries; logging about it here
+            // would only add to the backlog it can't keep up with.
+            BroadcastStream::new(receiver).filter_map(move |entry| {
+                let entry = entry
+                    .ok()
+                    .filter(|e| last_seq.is_none_or(|seq| e.seq > seq))
+                    .filter(&wanted);
+                futures::future::ready(entry)
+            })
+        });
+        futures::stream::iter(backlog)
+            .chain(futures::stream::iter(live).flatten())
+            .map(|entry| Ok(to_proto_log_entry(entry)))
+            .take_until(self.shutdown.clone().cancelled_owned())
+    }
+
+    #[instrument(skip(self))]
+    pub async fn send_test_notification(
+        &self,
+        message: &str,
+    ) -> Result<SendTestNotificationResponse, Status> {
+        let text = match message.trim() {
+            "" => "If you can read this, Stitch can post to this channel.",
+            text => text,
+        };
+        let messages: Vec<PostedMessage> = self
+            .webhook
+            .send_test_message(text)
+            .await
+            .map_err(discord_status)?
+            .into_iter()
+            .map(|message| PostedMessage {
+                discord_channel_id: message.channel_id.to_string(),
+                message_id: message.id.to_string(),
+                message_url: message.link(),
+            })
+            .collect();
+        let first = messages.first().cloned().unwrap_or_default();
+        Ok(SendTestNotificationResponse {
+            discord_channel_id: first.discord_channel_id,
+            message_id: first.message_id,
+            message_url: first.message_url,
+            messages,
+        })
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get_eventsub_subscriptions(
+        &self,
+        name: &str,
+    ) -> Result<Vec<EventSubSubscription>, Status> {
+        let channel_id = match name {
+            "" => None,
+            name => Some(
+                self.channels
+                    .get(name)
+                    .map(|id| id.value().clone())
+                    .ok_or_else(not_tracked)?,
+            ),
+        };
+        let names: HashMap<String, String> = self
+            .channels
+            .iter()
+            .map(|e| (e.value().clone(), e.key().clone()))
+            .collect();
+
+        let mut subscriptions = self
+            .twitch_api
+            .get_subscriptions(channel_id.as_deref())
+            .await
+            .map_err(|e| error_status("get_subscriptions", &e))?;
+        subscriptions.sort_by(|a, b| {
+            (a.condition.user_id(), a.event()).cmp(&(b.condition.user_id(), b.event()))
+        });
+
+        Ok(subscriptions
+            .into_iter()
+            .map(|sub| EventSubSubscription {
+                name: names
+                    .get(sub.condition.user_id())
+                    .cloned()
+                    .unwrap_or_default(),
+                r#type: sub.event().to_string(),
+                twitch_id: sub.condition.user_id().to_string(),
+                id: sub.id,
+                status: sub.status,
+                created_at: Some(to_timestamp(sub.created_at)),
+            })
+            .collect())
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get_server_info(&self) -> Result<GetServerInfoResponse, Status> {
+        let mut info = GetServerInfoResponse {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            proto_revision: proto::stitch::PROTO_REVISION.to_string(),
+            started_at: Some(to_timestamp(self.started_at)),
+            uptime: Some(to_duration(Utc::now() - self.started_at)),
+            tracked_channels: self.channels.len() as u32,
+            live_streams: self.webhook.live_stream_count() as u32,
+            ..Default::default()
+        };
+
+        match self.twitch_api.get_subscriptions(None).await {
+            Ok(subscriptions) => {
+                let mut by_status: HashMap<String, u32> = HashMap::new();
+                for subscription in &subscriptions {
+                    *by_status.entry(subscription.status.clone()).or_default() += 1;
+                }
+                info.subscriptions_total = subscriptions.len() as u32;
+                info.subscriptions_by_status = by_status;
+            }
+            Err(e) => {
+                warn!(error = ?e, "Failed to fetch EventSub subscriptions");
+                info.subscriptions_error = e
+                    .chain()
+                    .find_map(|cause| cause.downcast_ref::<TwitchError>())
+                    .map_or_else(|| "unavailable".to_string(), ToString::to_string);
+            }
+        }
+
+        Ok(info)
+    }
+}
+
+fn to_proto_level(level: tracing::Level) -> LogLevel {
+    match level {
+        tracing::Level::TRACE => LogLevel::Trace,
+        tracing::Level::DEBUG => LogLevel::Debug,
+        tracing::Level::INFO => LogLevel::Info,
+        tracing::Level::WARN => LogLevel::Warn,
+        tracing::Level::ERROR => LogLevel::Error,
+    }
+}
+
+fn to_proto_log_entry(entry: LogEntry) -> ProtoLogEntry {
+    ProtoLogEntry {
+        timestamp: Some(to_timestamp(entry.timestamp)),
+        level: to_proto_level(entry.level).into(),
+        target: entry.target,
+        message: entry.message,
+        fields: entry.fields.into_iter().collect(),
+    }
+}
+
+/// Rejections that mean the bot token or channel permissions are wrong get
+/// their own reason; anything else is treated as Discord being unreachable.
+fn discord_status(e: serenity::Error) -> Status {
+    let rejected = match &e {
+        serenity::Error::Http(http) => http
+            .status_code()
+            .is_some_and(|code| matches!(code.as_u16(), 401 | 403 | 404)),
+        _ => false,
+    };
+    match rejected {
+        true => status_with_reason(
+            Code::FailedPrecondition,
+            format!("Discord rejected the message: {e}"),
+            errors::DISCORD_PERMISSION_DENIED,
+        ),
+        false => status_with_reason(
+            Code::Unavailable,
+            format!("Failed to reach Discord: {e}"),
+            errors::DISCORD_UNAVAILABLE,
+        ),
+    }
+}
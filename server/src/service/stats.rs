@@ -0,0 +1,255 @@
+use crate::adapters::db;
+use crate::service::stream::category_durations;
+use chrono::{DateTime, Datelike, Days, NaiveTime, TimeDelta, Utc};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Aggregates over a single channel's streams.
+#[derive(Debug, Default)]
+pub(crate) struct ChannelStats<'a> {
+    pub total_streams: u32,
+    pub total_duration: TimeDelta,
+    pub longest_stream: Option<&'a db::Stream>,
+    /// Seconds per category, longest first.
+    pub categories: Vec<(String, u64)>,
+    /// Time streamed per week, keyed by [`week_start`].
+    pub weekly: BTreeMap<DateTime<Utc>, TimeDelta>,
+    /// Time streamed per weekday, Monday first.
+    pub weekdays: [TimeDelta; 7],
+    /// Durations of the last [`RECENT_STREAMS`] streams, oldest first.
+    pub recent: Vec<TimeDelta>,
+}
+
+/// Streams whose durations [`ChannelStats::recent`] keeps.
+pub(crate) const RECENT_STREAMS: usize = 20;
+
+impl ChannelStats<'_> {
+    pub fn average_duration(&self) -> TimeDelta {
+        match self.total_streams {
+            0 => TimeDelta::zero(),
+            n => self.total_duration / n as i32,
+        }
+    }
+}
+
+/// Midnight UTC on the Monday of `time`'s week.
+pub(crate) fn week_start(time: DateTime<Utc>) -> DateTime<Utc> {
+    let monday = time.date_naive() - Days::new(time.weekday().num_days_from_monday().into());
+    monday.and_time(NaiveTime::MIN).and_utc()
+}
+
+pub(crate) fn stream_duration(stream: &db::Stream, now: DateTime<Utc>) -> TimeDelta {
+    (stream.ended_at.unwrap_or(now) - stream.started_at).max(TimeDelta::zero())
+}
+
+/// Groups `streams` by channel ID and aggregates each group. Ongoing streams
+/// count up to `now`.
+pub(crate) fn channel_stats(
+    streams: &[db::Stream],
+    now: DateTime<Utc>,
+) -> HashMap<&str, ChannelStats<'_>> {
+    let mut stats: HashMap<&str, ChannelStats> = HashMap::new();
+    let mut categories: HashMap<&str, HashMap<String, u64>> = HashMap::new();
+    let mut recent: HashMap<&str, Vec<(DateTime<Utc>, TimeDelta)>> = HashMap::new();
+
+    for stream in streams {
+        let entry = stats.entry(stream.channel_id.as_str()).or_default();
+        let duration = stream_duration(stream, now);
+        entry.total_streams += 1;
+        entry.total_duration += duration;
+        *entry
+            .weekly
+            .entry(week_start(stream.started_at))
+            .or_default() += duration;
+        entry.weekdays[stream.started_at.weekday().num_days_from_monday() as usize] += duration;
+        recent
+            .entry(stream.channel_id.as_str())
+            .or_default()
+            .push((stream.started_at, duration));
+        if entry
+            .longest_stream
+            .is_none_or(|longest| stream_duration(longest, now) < duration)
+        {
+            entry.longest_stream = Some(stream);
+        }
+
+        let totals = categories.entry(stream.channel_id.as_str()).or_default();
+        for (category, secs) in category_durations(stream, now) {
+            *totals.entry(category).or_default() += secs;
+        }
+    }
+
+    for (channel_id, totals) in categories {
+        let mut totals: Vec<_> = totals.into_iter().collect();
+        totals.sort_by(|a, b| Reverse(a.1).cmp(&Reverse(b.1)).then_with(|| a.0.cmp(&b.0)));
+        if let Some(entry) = stats.get_mut(channel_id) {
+            entry.categories = totals;
+        }
+    }
+    for (channel_id, mut durations) in recent {
+        durations.sort_by_key(|(started_at, _)| *started_at);
+        let skip = durations.len().saturating_sub(RECENT_STREAMS);
+        if let Some(entry) = stats.get_mut(channel_id) {
+            entry.recent = durations.into_iter().skip(skip).map(|(_, d)| d).collect();
+        }
+    }
+    stats
+}
+
+/// Time spent in a category across every channel.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct CategoryTotals {
+    pub seconds: u64,
+    pub channels: u32,
+    pub streams: u32,
+}
+
+/// Totals per category over all of `streams`, longest first. Ongoing streams
+/// count up to `now`.
+pub(crate) fn category_totals(
+    streams: &[db::Stream],
+    now: DateTime<Utc>,
+) -> Vec<(String, CategoryTotals)> {
+    let mut totals: HashMap<String, CategoryTotals> = HashMap::new();
+    let mut channels: HashMap<String, HashSet<&str>> = HashMap::new();
+
+    for stream in streams {
+        for (category, secs) in category_durations(stream, now) {
+            channels
+                .entry(category.clone())
+                .or_default()
+                .insert(stream.channel_id.as_str());
+            let entry = totals.entry(category).or_default();
+            entry.seconds += secs;
+            entry.streams += 1;
+        }
+    }
+
+    let mut totals: Vec<_> = totals
+        .into_iter()
+        .map(|(category, mut entry)| {
+            entry.channels = channels[&category].len() as u32;
+            (category, entry)
+        })
+        .collect();
+    totals.sort_by(|a, b| {
+        Reverse(a.1.seconds)
+            .cmp(&Reverse(b.1.seconds))
+            .then_with(|| a.0.cmp(&b.0))
+    });
+    totals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use sqlx::types::Json;
+
+    fn stream(channel_id: &str, start_hour: u32, hours: i64, category: &str) -> db::Stream {
+        let started_at = Utc.with_ymd_and_hms(2024, 1, 1, start_hour, 0, 0).unwrap();
+        db::Stream {
+            id: 0,
+            channel_id: channel_id.to_string(),
+            stream_id: format!("{channel_id}-{start_hour}"),
+            title: "Title".to_string(),
+            started_at,
+            last_updated: started_at,
+            message_id: 0,
+            ended_at: Some(started_at + TimeDelta::hours(hours)),
+            events: Json(vec![db::UpdateEvent {
+                title: "Title".to_string(),
+                category: category.to_string(),
+                timestamp: started_at,
+                raid: None,
+            }]),
+        }
+    }
+
+    #[test]
+    fn test_channel_stats() {
+        let now = Utc.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap();
+        let streams = vec![
+            stream("a", 1, 2, "Factorio"),
+            stream("a", 8, 4, "Just Chatting"),
+            stream("a", 14, 3, "Factorio"),
+            stream("b", 1, 1, "Chess"),
+        ];
+        let stats = channel_stats(&streams, now);
+
+        let a = &stats["a"];
+        assert_eq!(a.total_streams, 3);
+        assert_eq!(a.total_duration, TimeDelta::hours(9));
+        assert_eq!(a.average_duration(), TimeDelta::hours(3));
+        assert_eq!(a.longest_stream.unwrap().stream_id, "a-8");
+        assert_eq!(
+            a.categories,
+            vec![
+                ("Factorio".to_string(), 5 * 3600),
+                ("Just Chatting".to_string(), 4 * 3600)
+            ]
+        );
+        assert_eq!(
+            a.weekly.iter().collect::<Vec<_>>(),
+            vec![(&now.with_day(1).unwrap(), &TimeDelta::hours(9))]
+        );
+        // 2024-01-01 was a Monday.
+        assert_eq!(a.weekdays[0], TimeDelta::hours(9));
+        assert_eq!(a.weekdays[1..], [TimeDelta::zero(); 6]);
+        assert_eq!(
+            a.recent,
+            vec![
+                TimeDelta::hours(2),
+                TimeDelta::hours(4),
+                TimeDelta::hours(3)
+            ]
+        );
+        assert_eq!(stats["b"].total_streams, 1);
+    }
+
+    #[test]
+    fn test_week_start() {
+        let monday = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let sunday_night = Utc.with_ymd_and_hms(2024, 1, 7, 23, 59, 0).unwrap();
+        assert_eq!(week_start(monday), monday);
+        assert_eq!(week_start(sunday_night), monday);
+        assert_eq!(
+            week_start(sunday_night + TimeDelta::minutes(1)),
+            monday + TimeDelta::weeks(1)
+        );
+    }
+
+    #[test]
+    fn test_category_totals() {
+        let now = Utc.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap();
+        let streams = vec![
+            stream("a", 1, 2, "Factorio"),
+            stream("a", 8, 1, "Chess"),
+            stream("b", 1, 3, "Factorio"),
+            stream("c", 1, 1, "Chess"),
+        ];
+        let totals = category_totals(&streams, now);
+
+        assert_eq!(
+            totals,
+            vec![
+                (
+                    "Factorio".to_string(),
+                    CategoryTotals {
+                        seconds: 5 * 3600,
+                        channels: 2,
+                        streams: 2,
+                    }
+                ),
+                (
+                    "Chess".to_string(),
+                    CategoryTotals {
+                        seconds: 2 * 3600,
+                        channels: 2,
+                        streams: 2,
+                    }
+                ),
+            ]
+        );
+    }
+}
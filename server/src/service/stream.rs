@@ -0,0 +1,416 @@
+use crate::adapters::db::{self, get_channel_by_name, get_stream_history, Pool};
+use crate::adapters::twitch::TwitchAPI;
+use crate::adapters::webhook::{
+    self, tally_categories, StreamEvent, StreamEventKind, TwitchWebhook,
+};
+use crate::service::stats::{category_totals, channel_stats};
+use crate::service::{error_status, not_tracked, to_duration, to_timestamp};
+use chrono::{DateTime, Utc};
+use futures::{Stream as FuturesStream, StreamExt};
+use proto::stitch::{
+    CategoryStats, CategoryTime, ChannelStats, Event, EventKind, GetChannelStatusResponse,
+    LiveStream, Notification, NotificationAction, Stream as ProtoStream, WeeklyTime,
+};
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
+use tokio_util::sync::CancellationToken;
+use tonic::Status;
+use tracing::{instrument, warn};
+
+const DEFAULT_HISTORY_LIMIT: u32 = 20;
+const MAX_HISTORY_LIMIT: u32 = 100;
+const TOP_CATEGORIES: usize = 5;
+const DEFAULT_TOP_LIMIT: u32 = 10;
+const MAX_TOP_LIMIT: u32 = 100;
+
+#[derive(Clone)]
+pub struct StreamService {
+    pool: Pool,
+    webhook: Arc<TwitchWebhook>,
+    twitch_api: Arc<TwitchAPI>,
+    shutdown: CancellationToken,
+}
+
+impl StreamService {
+    pub fn new(
+        pool: Pool,
+        webhook: Arc<TwitchWebhook>,
+        twitch_api: Arc<TwitchAPI>,
+        shutdown: CancellationToken,
+    ) -> Self {
+        Self {
+            pool,
+            webhook,
+            twitch_api,
+            shutdown,
+        }
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get_stream_history(
+        &self,
+        name: &str,
+        since: Option<DateTime<Utc>>,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<ProtoStream>, Status> {
+        let channel = get_channel_by_name(&self.pool, name)
+            .await
+            .map_err(|e| error_status("get_channel_by_name", &e))?
+            .ok_or_else(not_tracked)?;
+
+        let limit = match limit {
+            0 => DEFAULT_HISTORY_LIMIT,
+            n => n.min(MAX_HISTORY_LIMIT),
+        };
+        let streams = get_stream_history(
+            &self.pool,
+            &channel.channel_id,
+            since,
+            i64::from(limit),
+            i64::from(offset),
+        )
+        .await
+        .map_err(|e| error_status("get_stream_history", &e))?;
+
+        Ok(streams.iter().map(to_proto_stream).collect())
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get_channel_status(&self, name: &str) -> Result<GetChannelStatusResponse, Status> {
+        let channel = get_channel_by_name(&self.pool, name)
+            .await
+            .map_err(|e| error_status("get_channel_by_name", &e))?
+            .filter(|c| c.active)
+            .ok_or_else(not_tracked)?;
+
+        let stream = self.webhook.live_stream(&channel.channel_id).await;
+        Ok(GetChannelStatusResponse {
+            name: channel.name,
+            live: stream.is_some(),
+            stream: stream.as_ref().map(to_live_stream),
+        })
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get_stream_stats(
+        &self,
+        name: &str,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<Vec<ChannelStats>, Status> {
+        let channels = if name.is_empty() {
+            db::list_channels(&self.pool)
+                .await
+                .map_err(|e| error_status("list_channels", &e))?
+        } else {
+            let channel = get_channel_by_name(&self.pool, name)
+                .await
+                .map_err(|e| error_status("get_channel_by_name", &e))?
+                .filter(|c| c.active)
+                .ok_or_else(not_tracked)?;
+            vec![channel]
+        };
+        let channel_id = (!name.is_empty()).then(|| channels[0].channel_id.as_str());
+
+        let streams = db::list_streams(&self.pool, channel_id, since)
+            .await
+            .map_err(|e| error_status("list_streams", &e))?;
+        let mut stats = channel_stats(&streams, Utc::now());
+        let viewers = db::viewer_stats(&self.pool, channel_id, since)
+            .await
+            .map_err(|e| error_status("viewer_stats", &e))?;
+
+        let mut result: Vec<ChannelStats> = channels
+            .iter()
+            .map(|channel| {
+                let stats = stats
+                    .remove(channel.channel_id.as_str())
+                    .unwrap_or_default();
+                let viewers = viewers
+                    .get(&channel.channel_id)
+                    .copied()
+                    .unwrap_or_default();
+                ChannelStats {
+                    name: channel.name.clone(),
+                    display_name: channel.display_name.clone(),
+                    total_streams: stats.total_streams,
+                    total_duration: Some(to_duration(stats.total_duration)),
+                    average_duration: Some(to_duration(stats.average_duration())),
+                    top_categories: stats
+                        .categories
+                        .into_iter()
+                        .take(TOP_CATEGORIES)
+                        .map(|(category, secs)| CategoryTime {
+                            category,
+                            duration: Some(prost_types::Duration {
+                                seconds: secs as i64,
+                                nanos: 0,
+                            }),
+                        })
+                        .collect(),
+                    longest_stream: stats.longest_stream.map(to_proto_stream),
+                    weekly: stats
+                        .weekly
+                        .into_iter()
+                        .map(|(week, duration)| WeeklyTime {
+                            week_start: Some(to_timestamp(week)),
+                            duration: Some(to_duration(duration)),
+                        })
+                        .collect(),
+                    weekdays: stats.weekdays.into_iter().map(to_duration).collect(),
+                    recent: stats.recent.into_iter().map(to_duration).collect(),
+                    average_viewers: viewers.average.round() as u32,
+                    peak_viewers: viewers.peak as u32,
+                }
+            })
+            .collect();
+        result.sort_by_key(|s| Reverse(s.total_duration.as_ref().map_or(0, |d| d.seconds)));
+        Ok(result)
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get_top_categories(
+        &self,
+        since: Option<DateTime<Utc>>,
+        limit: u32,
+    ) -> Result<Vec<CategoryStats>, Status> {
+        let limit = match limit {
+            0 => DEFAULT_TOP_LIMIT,
+            n => n.min(MAX_TOP_LIMIT),
+        };
+        let streams = db::list_streams(&self.pool, None, since)
+            .await
+            .map_err(|e| error_status("list_streams", &e))?;
+
+        Ok(category_totals(&streams, Utc::now())
+            .into_iter()
+            .take(limit as usize)
+            .map(|(category, totals)| CategoryStats {
+                category,
+                duration: Some(prost_types::Duration {
+                    seconds: totals.seconds as i64,
+                    nanos: 0,
+                }),
+                channels: totals.channels,
+                streams: totals.streams,
+            })
+            .collect())
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get_notification_log(
+        &self,
+        name: &str,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<Notification>, Status> {
+        let channel_id = match name {
+            "" => None,
+            name => Some(
+                get_channel_by_name(&self.pool, name)
+                    .await
+                    .map_err(|e| error_status("get_channel_by_name", &e))?
+                    .ok_or_else(not_tracked)?
+                    .channel_id,
+            ),
+        };
+        let limit = match limit {
+            0 => DEFAULT_HISTORY_LIMIT,
+            n => n.min(MAX_HISTORY_LIMIT),
+        };
+
+        let entries = db::get_notification_log(
+            &self.pool,
+            channel_id.as_deref(),
+            i64::from(limit),
+            i64::from(offset),
+        )
+        .await
+        .map_err(|e| error_status("get_notification_log", &e))?;
+        Ok(entries.into_iter().map(to_proto_notification).collect())
+    }
+
+    #[instrument(skip(self))]
+    pub async fn list_live_streams(&self) -> Result<Vec<LiveStream>, Status> {
+        let mut streams = self.webhook.live_streams().await;
+        streams.sort_by_key(|s| s.started_at);
+
+        // Viewer counts aren't pushed over EventSub, so ask Helix for them.
+        let ids: Vec<String> = streams.iter().map(|s| s.channel_id.clone()).collect();
+        let viewers: HashMap<String, u32> = match self.twitch_api.get_streams(&ids).await {
+            Ok(live) => live
+                .into_iter()
+                .map(|s| (s.user_id, s.viewer_count))
+                .collect(),
+            Err(e) => {
+                warn!("Failed to fetch viewer counts: {e:#}");
+                HashMap::new()
+            }
+        };
+
+        Ok(streams
+            .iter()
+            .map(|stream| LiveStream {
+                viewer_count: viewers.get(&stream.channel_id).copied().unwrap_or_default(),
+                ..to_live_stream(stream)
+            })
+            .collect())
+    }
+
+    /// Streams webhook events for `names` (or every channel when empty) until
+    /// the client disconnects or the server shuts down.
+    pub fn watch_events(
+        &self,
+        names: Vec<String>,
+    ) -> impl FuturesStream<Item = Result<Event, Status>> + Send + 'static {
+        let names: HashSet<String> = names.into_iter().map(|n| n.to_lowercase()).collect();
+        BroadcastStream::new(self.webhook.subscribe())
+            .filter_map(move |event| {
+                let event = match event {
+                    Ok(event) => event,
+                    Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+                        warn!(skipped, "WatchEvents subscriber lagged behind");
+                        return futures::future::ready(None);
+                    }
+                };
+                let wanted = names.is_empty() || names.contains(&event.user_login);
+                futures::future::ready(wanted.then(|| Ok(to_proto_event(&event))))
+            })
+            .take_until(self.shutdown.clone().cancelled_owned())
+    }
+}
+
+fn to_proto_notification(entry: db::NotificationLogEntry) -> Notification {
+    let action = match entry.action.as_str() {
+        "send" => NotificationAction::Send,
+        "edit" => NotificationAction::Edit,
+        "delete" => NotificationAction::Delete,
+        _ => NotificationAction::Unspecified,
+    };
+    Notification {
+        id: entry.id,
+        name: entry.name.unwrap_or_default(),
+        twitch_id: entry.channel_id,
+        action: action.into(),
+        message_id: entry.message_id.unwrap_or_default() as u64,
+        error: entry.error.unwrap_or_default(),
+        created_at: Some(to_timestamp(entry.created_at)),
+    }
+}
+
+fn to_proto_event(event: &StreamEvent) -> Event {
+    let kind = match event.kind {
+        StreamEventKind::Online => EventKind::Online,
+        StreamEventKind::Offline => EventKind::Offline,
+        StreamEventKind::Update => EventKind::Update,
+    };
+    Event {
+        kind: kind.into(),
+        name: event.user_login.clone(),
+        display_name: event.user_name.clone(),
+        title: event.title.clone(),
+        category: event.category.clone(),
+        timestamp: Some(to_timestamp(event.timestamp)),
+    }
+}
+
+pub(crate) fn to_live_stream(stream: &webhook::Stream) -> LiveStream {
+    LiveStream {
+        name: stream.user_login.clone(),
+        display_name: stream.user_name.clone(),
+        stream_id: stream.id.clone(),
+        title: stream.title.clone(),
+        category: stream.category.clone(),
+        started_at: Some(to_timestamp(stream.started_at)),
+        uptime: Some(to_duration(Utc::now() - stream.started_at)),
+        viewer_count: 0,
+    }
+}
+
+fn to_proto_stream(stream: &db::Stream) -> ProtoStream {
+    ProtoStream {
+        stream_id: stream.stream_id.clone(),
+        title: stream.title.clone(),
+        started_at: Some(to_timestamp(stream.started_at)),
+        ended_at: stream.ended_at.map(to_timestamp),
+        categories: categories_by_time(stream),
+    }
+}
+
+/// Categories played during `stream`, ordered by time spent in each. Ongoing
+/// streams are tallied up to now.
+pub(crate) fn categories_by_time(stream: &db::Stream) -> Vec<String> {
+    category_durations(stream, Utc::now())
+        .into_iter()
+        .map(|(c, _)| c)
+        .collect()
+}
+
+/// Seconds spent in each category during `stream`, longest first. Ongoing
+/// streams are tallied up to `now`.
+pub(crate) fn category_durations(stream: &db::Stream, now: DateTime<Utc>) -> Vec<(String, u64)> {
+    let Some(last) = stream.events.0.last() else {
+        return Vec::new();
+    };
+    let mut events = stream.events.0.clone();
+    events.push(db::UpdateEvent {
+        title: last.title.clone(),
+        category: last.category.clone(),
+        timestamp: stream.ended_at.unwrap_or(now),
+        raid: None,
+    });
+    events.sort_by_key(|e| e.timestamp);
+
+    let (_, categories) = tally_categories(&events);
+    let mut categories: Vec<_> = categories.into_iter().collect();
+    categories.sort_by(|a, b| Reverse(a.1).cmp(&Reverse(b.1)).then(a.0.cmp(b.0)));
+    categories
+        .into_iter()
+        .map(|(c, secs)| (c.to_string(), secs))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use sqlx::types::Json;
+
+    fn event(category: &str, minutes: i64) -> db::UpdateEvent {
+        db::UpdateEvent {
+            title: "Title".to_string(),
+            category: category.to_string(),
+            timestamp: Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap()
+                + chrono::Duration::minutes(minutes),
+            raid: None,
+        }
+    }
+
+    #[test]
+    fn test_categories_by_time() {
+        let events = vec![event("Just Chatting", 0), event("Factorio", 15)];
+        let stream = db::Stream {
+            id: 1,
+            channel_id: "1".to_string(),
+            stream_id: "s".to_string(),
+            title: "Title".to_string(),
+            started_at: events[0].timestamp,
+            last_updated: events[1].timestamp,
+            message_id: 0,
+            ended_at: Some(events[1].timestamp + chrono::Duration::hours(2)),
+            events: Json(events),
+        };
+        assert_eq!(
+            categories_by_time(&stream),
+            vec!["Factorio", "Just Chatting"]
+        );
+
+        let stream = db::Stream {
+            events: Json(Vec::new()),
+            ..stream
+        };
+        assert!(categories_by_time(&stream).is_empty());
+    }
+}
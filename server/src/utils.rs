@@ -1 +1,7 @@
+pub mod idempotency;
+pub mod log_tail;
+pub mod quiet_hours;
+pub mod rate_limit;
+pub mod request_id;
+pub mod ttl_cache;
 pub mod ttl_set;
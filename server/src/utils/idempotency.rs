@@ -0,0 +1,145 @@
+use std::future::Future;
+use std::sync::Arc;
+
+use tokio::sync::OnceCell;
+use tokio::time::Duration;
+use tonic::{Request, Status};
+
+use crate::service::bad_request;
+use crate::utils::ttl_cache::TtlCache;
+
+pub(crate) const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+const MAX_KEY_LEN: usize = 128;
+const KEY_TTL: Duration = Duration::from_secs(10 * 60);
+
+struct Slot<T> {
+    fingerprint: String,
+    result: OnceCell<T>,
+}
+
+/// Replays the result of an earlier call that carried the same
+/// `idempotency-key`, so a retried RPC doesn't run twice. Only successes are
+/// remembered; a failed call can be retried with the same key.
+pub(crate) struct Idempotency<T> {
+    slots: TtlCache<Arc<Slot<T>>>,
+}
+
+impl<T: Clone + Send + Sync + 'static> Idempotency<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            slots: TtlCache::new(),
+        }
+    }
+
+    /// Runs `call` unless `key` was already used for a call with the same
+    /// `fingerprint`. Concurrent calls with one key wait for the first.
+    pub(crate) async fn run<F, Fut>(
+        &self,
+        key: Option<&str>,
+        fingerprint: &str,
+        call: F,
+    ) -> Result<T, Status>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, Status>>,
+    {
+        let Some(key) = key else {
+            return call().await;
+        };
+
+        let slot = self.slots.get_or_insert_with(key, KEY_TTL, || {
+            Arc::new(Slot {
+                fingerprint: fingerprint.to_string(),
+                result: OnceCell::new(),
+            })
+        });
+        if slot.fingerprint != fingerprint {
+            return Err(bad_request(
+                IDEMPOTENCY_KEY_HEADER,
+                "Idempotency key was already used for a different request",
+            ));
+        }
+
+        slot.result.get_or_try_init(call).await.cloned()
+    }
+}
+
+/// The request's `idempotency-key`, if it sent one, scoped to the caller so
+/// one client's key never replays another client's call.
+pub(crate) fn idempotency_key<T>(request: &Request<T>) -> Result<Option<String>, Status> {
+    let Some(value) = request.metadata().get(IDEMPOTENCY_KEY_HEADER) else {
+        return Ok(None);
+    };
+    match value.to_str() {
+        Ok(key) if !key.is_empty() && key.len() <= MAX_KEY_LEN => {
+            Ok(Some(format!("{}/{key}", caller(request))))
+        }
+        _ => Err(bad_request(
+            IDEMPOTENCY_KEY_HEADER,
+            format!("Idempotency key must be 1-{MAX_KEY_LEN} printable ASCII characters"),
+        )),
+    }
+}
+
+/// Who sent `request`: its API key, or its peer IP when it has none, the same
+/// way the rate limiter tells clients apart.
+fn caller<T>(request: &Request<T>) -> String {
+    let api_key = request
+        .metadata()
+        .get("authorization")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+    if let Some(key) = api_key {
+        return format!("key:{}", key.trim());
+    }
+    match request.remote_addr() {
+        Some(addr) => format!("ip:{}", addr.ip()),
+        None => "unknown".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[tokio::test]
+    async fn test_idempotency() {
+        let keys = Idempotency::new();
+        let calls = AtomicU32::new(0);
+        let call = || async { Ok::<_, Status>(calls.fetch_add(1, Ordering::SeqCst)) };
+
+        assert_eq!(keys.run(Some("k"), "a", call).await.unwrap(), 0);
+        assert_eq!(keys.run(Some("k"), "a", call).await.unwrap(), 0);
+        assert!(keys.run(Some("k"), "b", call).await.is_err());
+        assert_eq!(keys.run(None, "a", call).await.unwrap(), 1);
+        assert_eq!(keys.run(Some("other"), "a", call).await.unwrap(), 2);
+
+        // Failures aren't remembered.
+        let fail = || async { Err::<u32, _>(Status::unavailable("down")) };
+        assert!(keys.run(Some("retry"), "a", fail).await.is_err());
+        assert_eq!(keys.run(Some("retry"), "a", call).await.unwrap(), 3);
+    }
+
+    fn request(authorization: Option<&str>, key: &str) -> Request<()> {
+        let mut request = Request::new(());
+        let metadata = request.metadata_mut();
+        if let Some(value) = authorization {
+            metadata.insert("authorization", value.parse().unwrap());
+        }
+        metadata.insert(IDEMPOTENCY_KEY_HEADER, key.parse().unwrap());
+        request
+    }
+
+    #[test]
+    fn test_idempotency_key_is_per_caller() {
+        let key = |authorization, key| idempotency_key(&request(authorization, key)).unwrap();
+
+        assert_eq!(key(Some("Bearer a"), "k"), key(Some("Bearer a "), "k"));
+        assert_ne!(key(Some("Bearer a"), "k"), key(Some("Bearer b"), "k"));
+        assert_ne!(key(Some("Bearer a"), "k"), key(None, "k"));
+        assert_ne!(key(Some("Bearer a"), "k"), key(Some("Bearer a"), "l"));
+        assert_eq!(idempotency_key(&Request::new(())).unwrap(), None);
+        assert!(idempotency_key(&request(None, "")).is_err());
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+use tokio::sync::broadcast;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::Layer;
+
+/// Entries kept for `TailLogs` backlogs.
+pub(crate) const LOG_TAIL_CAPACITY: usize = 1000;
+
+#[derive(Debug, Clone)]
+pub(crate) struct LogEntry {
+    /// Increases by one per entry, so a live follower can skip what it
+    /// already got from the backlog.
+    pub seq: u64,
+    pub timestamp: DateTime<Utc>,
+    pub level: Level,
+    pub target: String,
+    pub message: String,
+    pub fields: Vec<(String, String)>,
+}
+
+struct Buffer {
+    entries: VecDeque<LogEntry>,
+    next_seq: u64,
+}
+
+/// A `tracing` layer that keeps the most recent events in memory and
+/// broadcasts new ones, so they can be tailed over gRPC.
+#[derive(Clone)]
+pub struct LogTail {
+    buffer: Arc<Mutex<Buffer>>,
+    capacity: usize,
+    sender: broadcast::Sender<LogEntry>,
+}
+
+impl LogTail {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            buffer: Arc::new(Mutex::new(Buffer {
+                entries: VecDeque::with_capacity(capacity),
+                next_seq: 0,
+            })),
+            capacity,
+            sender: broadcast::channel(capacity.max(1)).0,
+        }
+    }
+
+    /// Up to `limit` of the most recent entries, oldest first.
+    pub(crate) fn recent(&self, limit: usize) -> Vec<LogEntry> {
+        let buffer = self.buffer.lock().unwrap();
+        let skip = buffer.entries.len().saturating_sub(limit);
+        buffer.entries.iter().skip(skip).cloned().collect()
+    }
+
+    pub(crate) fn subscribe(&self) -> broadcast::Receiver<LogEntry> {
+        self.sender.subscribe()
+    }
+}
+
+impl<S: Subscriber> Layer<S> for LogTail {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+
+        let mut buffer = self.buffer.lock().unwrap();
+        let entry = LogEntry {
+            seq: buffer.next_seq,
+            timestamp: Utc::now(),
+            level: *event.metadata().level(),
+            target: event.metadata().target().to_string(),
+            message: visitor.message,
+            fields: visitor.fields,
+        };
+        buffer.next_seq += 1;
+        if buffer.entries.len() == self.capacity {
+            buffer.entries.pop_front();
+        }
+        if self.capacity > 0 {
+            buffer.entries.push_back(entry.clone());
+        }
+        // Sent under the lock so followers see entries in sequence order.
+        // Having no followers isn't an error.
+        let _ = self.sender.send(entry);
+    }
+}
+
+#[derive(Default)]
+struct FieldVisitor {
+    message: String,
+    fields: Vec<(String, String)>,
+}
+
+impl Visit for FieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "message" => self.message = value.to_string(),
+            name => self.fields.push((name.to_string(), value.to_string())),
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        match field.name() {
+            "message" => self.message = format!("{value:?}"),
+            name => self.fields.push((name.to_string(), format!("{value:?}"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_log_tail() {
+        let tail = LogTail::new(2);
+        let mut follower = tail.subscribe();
+        let subscriber = tracing_subscriber::registry().with(tail.clone());
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!("first");
+            tracing::warn!(channel = "abc", "second");
+            tracing::error!("third");
+        });
+
+        let recent = tail.recent(10);
+        assert_eq!(recent.len(), 2);
+        assert_eq!(recent[0].message, "second");
+        assert_eq!(recent[0].level, Level::WARN);
+        assert_eq!(
+            recent[0].fields,
+            vec![("channel".to_string(), "abc".to_string())]
+        );
+        assert_eq!(recent[1].seq, 2);
+        assert_eq!(tail.recent(1)[0].message, "third");
+
+        // Followers are bounded by the same capacity.
+        assert!(matches!(
+            follower.try_recv(),
+            Err(broadcast::error::TryRecvError::Lagged(1))
+        ));
+        assert_eq!(follower.try_recv().unwrap().message, "second");
+    }
+}
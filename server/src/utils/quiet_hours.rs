@@ -0,0 +1,84 @@
+use chrono::{DateTime, NaiveTime, Utc};
+use chrono_tz::Tz;
+use std::str::FromStr;
+
+/// A daily window without pings, like `00:00-08:00`, for one Discord channel
+/// (`<channel id>=00:00-08:00`) or every one. Windows that end before they
+/// start run past midnight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuietHours {
+    pub channel: Option<u64>,
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl QuietHours {
+    /// Whether `at` falls in the window for `channel`, in `tz` local time.
+    pub fn contains(&self, channel: u64, at: DateTime<Utc>, tz: Tz) -> bool {
+        if self.channel.is_some_and(|c| c != channel) {
+            return false;
+        }
+        let time = at.with_timezone(&tz).time();
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+impl FromStr for QuietHours {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (channel, window) = match s.split_once('=') {
+            Some((channel, window)) => {
+                let channel = channel
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("invalid Discord channel `{channel}`"))?;
+                (Some(channel), window)
+            }
+            None => (None, s),
+        };
+        let (start, end) = window
+            .split_once('-')
+            .ok_or_else(|| format!("expected HH:MM-HH:MM, got `{window}`"))?;
+        let time = |t: &str| {
+            NaiveTime::parse_from_str(t.trim(), "%H:%M").map_err(|_| format!("invalid time `{t}`"))
+        };
+        Ok(Self {
+            channel,
+            start: time(start)?,
+            end: time(end)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_quiet_hours() {
+        let night: QuietHours = "22:30-07:00".parse().unwrap();
+        let morning: QuietHours = "42=00:00-08:00".parse().unwrap();
+        assert_eq!(night.channel, None);
+        assert_eq!(morning.channel, Some(42));
+        assert!("25:00-08:00".parse::<QuietHours>().is_err());
+        assert!("x=00:00-08:00".parse::<QuietHours>().is_err());
+
+        let at = |hour, min| Utc.with_ymd_and_hms(2025, 1, 10, hour, min, 0).unwrap();
+        assert!(night.contains(1, at(23, 0), Tz::UTC));
+        assert!(night.contains(1, at(6, 59), Tz::UTC));
+        assert!(!night.contains(1, at(7, 0), Tz::UTC));
+        assert!(!night.contains(1, at(12, 0), Tz::UTC));
+
+        assert!(morning.contains(42, at(3, 0), Tz::UTC));
+        assert!(!morning.contains(7, at(3, 0), Tz::UTC));
+        // 07:30 UTC is already 08:30 in Berlin during winter.
+        assert!(morning.contains(42, at(6, 30), Tz::Europe__Berlin));
+        assert!(!morning.contains(42, at(7, 30), Tz::Europe__Berlin));
+    }
+}
@@ -0,0 +1,183 @@
+use std::future::Future;
+use std::net::IpAddr;
+use std::num::NonZeroU32;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use governor::clock::{Clock, DefaultClock};
+use governor::{DefaultKeyedRateLimiter, Quota, RateLimiter};
+use tonic::body::Body;
+use tonic::codegen::http;
+use tonic::transport::server::{TcpConnectInfo, TlsConnectInfo};
+use tonic::{Code, Status};
+use tonic_types::{ErrorDetails, StatusExt};
+use tower::{Layer, Service};
+use tracing::warn;
+
+/// RPCs that create or delete EventSub subscriptions.
+const LIMITED_METHODS: &[&str] = &[
+    "/stitch.v1.StitchService/TrackChannel",
+    "/stitch.v1.StitchService/UntrackChannel",
+    "/stitch.v1.StitchService/UntrackChannels",
+    "/stitch.v1.StitchService/PauseTracking",
+    "/stitch.v1.StitchService/ResumeTracking",
+];
+
+/// Limits subscription-changing RPCs per client, keyed by API key when
+/// authentication is enabled and by peer IP otherwise. Rejected calls get `RESOURCE_EXHAUSTED` with
+/// a `RetryInfo` detail.
+#[derive(Clone)]
+pub(crate) struct RateLimitLayer {
+    limiter: Option<Arc<DefaultKeyedRateLimiter<String>>>,
+    by_api_key: bool,
+}
+
+impl RateLimitLayer {
+    /// Allows `per_minute` calls per client; 0 disables limiting. Without
+    /// `by_api_key` clients could dodge the limit by rotating bogus keys, so
+    /// only set it when keys are actually verified.
+    pub(crate) fn per_minute(per_minute: u32, by_api_key: bool) -> Self {
+        let limiter =
+            NonZeroU32::new(per_minute).map(|n| Arc::new(RateLimiter::keyed(Quota::per_minute(n))));
+        if let Some(limiter) = &limiter {
+            let limiter = Arc::downgrade(limiter);
+            tokio::spawn(async move {
+                loop {
+                    tokio::time::sleep(Duration::from_secs(60)).await;
+                    let Some(limiter) = limiter.upgrade() else {
+                        break;
+                    };
+                    limiter.retain_recent();
+                    limiter.shrink_to_fit();
+                }
+            });
+        }
+        Self {
+            limiter,
+            by_api_key,
+        }
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimit<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimit {
+            inner,
+            limiter: self.limiter.clone(),
+            by_api_key: self.by_api_key,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct RateLimit<S> {
+    inner: S,
+    limiter: Option<Arc<DefaultKeyedRateLimiter<String>>>,
+    by_api_key: bool,
+}
+
+impl<S, B> Service<http::Request<B>> for RateLimit<S>
+where
+    S: Service<http::Request<B>, Response = http::Response<Body>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: http::Request<B>) -> Self::Future {
+        if let Some(limiter) = &self.limiter {
+            if LIMITED_METHODS.contains(&request.uri().path()) {
+                let key = client_key(&request, self.by_api_key);
+                if let Err(not_until) = limiter.check_key(&key) {
+                    let wait = not_until.wait_time_from(DefaultClock::default().now());
+                    warn!(method = request.uri().path(), "Rate limited client");
+                    let status = Status::with_error_details(
+                        Code::ResourceExhausted,
+                        format!("Rate limit exceeded, retry in {}s", wait.as_secs() + 1),
+                        ErrorDetails::with_retry_info(Some(wait)),
+                    );
+                    return Box::pin(async move { Ok(status.into_http()) });
+                }
+            }
+        }
+        Box::pin(self.inner.call(request))
+    }
+}
+
+fn client_key<B>(request: &http::Request<B>, by_api_key: bool) -> String {
+    let api_key = request
+        .headers()
+        .get("authorization")
+        .filter(|_| by_api_key)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+    if let Some(key) = api_key {
+        return format!("key:{}", key.trim());
+    }
+    match peer_ip(request) {
+        Some(ip) => format!("ip:{ip}"),
+        None => "unknown".to_string(),
+    }
+}
+
+fn peer_ip<B>(request: &http::Request<B>) -> Option<IpAddr> {
+    let extensions = request.extensions();
+    let addr = match extensions.get::<TcpConnectInfo>() {
+        Some(info) => info.remote_addr(),
+        None => extensions
+            .get::<TlsConnectInfo<TcpConnectInfo>>()
+            .and_then(|info| info.get_ref().remote_addr()),
+    };
+    addr.map(|a| a.ip())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tower::{service_fn, ServiceExt};
+
+    fn request(path: &str, api_key: &str) -> http::Request<()> {
+        http::Request::builder()
+            .uri(path)
+            .header("authorization", format!("Bearer {api_key}"))
+            .body(())
+            .unwrap()
+    }
+
+    fn grpc_status(response: &http::Response<Body>) -> Option<&str> {
+        response
+            .headers()
+            .get("grpc-status")
+            .and_then(|v| v.to_str().ok())
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit() {
+        let inner = service_fn(|_: http::Request<()>| async {
+            Ok::<_, std::convert::Infallible>(http::Response::new(Body::default()))
+        });
+        let service = RateLimitLayer::per_minute(1, true).layer(inner);
+        let track = "/stitch.v1.StitchService/TrackChannel";
+
+        let first = service.clone().oneshot(request(track, "a")).await.unwrap();
+        assert_eq!(grpc_status(&first), None);
+        let second = service.clone().oneshot(request(track, "a")).await.unwrap();
+        assert_eq!(grpc_status(&second), Some("8"));
+
+        // Other clients and other RPCs are unaffected.
+        let other_key = service.clone().oneshot(request(track, "b")).await.unwrap();
+        assert_eq!(grpc_status(&other_key), None);
+        let list = "/stitch.v1.StitchService/ListChannels";
+        let other_rpc = service.clone().oneshot(request(list, "a")).await.unwrap();
+        assert_eq!(grpc_status(&other_rpc), None);
+    }
+}
@@ -0,0 +1,130 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tonic::codegen::http::{self, HeaderValue};
+use tower::{Layer, Service};
+use tracing::Instrument;
+
+pub(crate) const REQUEST_ID_HEADER: &str = "x-request-id";
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+tokio::task_local! {
+    pub(crate) static CURRENT: String;
+}
+
+/// The ID of the call being handled, for pointing clients at its logs.
+pub(crate) fn current() -> Option<String> {
+    CURRENT.try_with(String::clone).ok()
+}
+
+/// Tags every call with an `x-request-id`, reusing the client's when it sends
+/// a sane one. The ID is recorded on a span wrapping the whole call and echoed
+/// back in the response headers.
+#[derive(Clone, Default)]
+pub(crate) struct RequestIdLayer;
+
+impl<S> Layer<S> for RequestIdLayer {
+    type Service = RequestId<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestId { inner }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct RequestId<S> {
+    inner: S,
+}
+
+impl<S, B, R> Service<http::Request<B>> for RequestId<S>
+where
+    S: Service<http::Request<B>, Response = http::Response<R>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: http::Request<B>) -> Self::Future {
+        let id = request
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .filter(|v| is_valid(v))
+            .cloned()
+            .unwrap_or_else(|| {
+                HeaderValue::from_str(&uuid::Uuid::new_v4().to_string())
+                    .expect("UUIDs are valid header values")
+            });
+        request.headers_mut().insert(REQUEST_ID_HEADER, id.clone());
+
+        let request_id = id.to_str().unwrap_or_default().to_string();
+        let span = tracing::info_span!(
+            "rpc",
+            request_id = request_id.as_str(),
+            method = request.uri().path(),
+        );
+        let response = self.inner.call(request).instrument(span);
+        Box::pin(CURRENT.scope(request_id, async move {
+            let mut response = response.await?;
+            response.headers_mut().insert(REQUEST_ID_HEADER, id);
+            Ok(response)
+        }))
+    }
+}
+
+fn is_valid(id: &HeaderValue) -> bool {
+    !id.is_empty()
+        && id.len() <= MAX_REQUEST_ID_LEN
+        && id.as_bytes().iter().all(|b| b.is_ascii_graphic())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tower::{service_fn, ServiceExt};
+
+    async fn call(request_id: Option<&str>) -> (Option<String>, String) {
+        let inner = service_fn(|request: http::Request<()>| async move {
+            let seen = request.headers()[REQUEST_ID_HEADER]
+                .to_str()
+                .unwrap()
+                .to_string();
+            assert_eq!(current().as_deref(), Some(seen.as_str()));
+            Ok::<_, std::convert::Infallible>(http::Response::new(seen))
+        });
+        let mut request = http::Request::builder().uri("/stitch.v1.StitchService/ListChannels");
+        if let Some(id) = request_id {
+            request = request.header(REQUEST_ID_HEADER, id);
+        }
+        let response = RequestIdLayer
+            .layer(inner)
+            .oneshot(request.body(()).unwrap())
+            .await
+            .unwrap();
+        let echoed = response
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .map(|v| v.to_str().unwrap().to_string());
+        (echoed, response.into_body())
+    }
+
+    #[tokio::test]
+    async fn test_request_id_propagation() {
+        let (echoed, seen) = call(Some("abc-123")).await;
+        assert_eq!(echoed.as_deref(), Some("abc-123"));
+        assert_eq!(seen, "abc-123");
+
+        let (echoed, seen) = call(None).await;
+        assert_eq!(echoed.as_deref(), Some(seen.as_str()));
+        assert!(uuid::Uuid::parse_str(&seen).is_ok());
+
+        let (echoed, _) = call(Some("has spaces")).await;
+        assert_ne!(echoed.as_deref(), Some("has spaces"));
+        assert_eq!(current(), None);
+    }
+}
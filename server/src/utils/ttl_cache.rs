@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use dashmap::{DashMap, Entry};
+use tokio::time;
+
+/// Like [`TtlSet`](super::ttl_set::TtlSet), but remembers a value per key.
+pub struct TtlCache<V> {
+    map: Arc<DashMap<String, (tokio::time::Instant, V)>>,
+    janitor: tokio::task::JoinHandle<()>,
+}
+
+impl<V: Clone + Send + Sync + 'static> TtlCache<V> {
+    pub fn new() -> Self {
+        let map = Arc::new(DashMap::new());
+        let weak = Arc::downgrade(&map);
+        let janitor = tokio::spawn(async move {
+            let mut ticker = time::interval(time::Duration::from_secs(1));
+            loop {
+                ticker.tick().await;
+                let Some(map) = weak.upgrade() else { break };
+                let now = time::Instant::now();
+                map.retain(|_, (expiration, _)| *expiration > now);
+            }
+        });
+
+        TtlCache { map, janitor }
+    }
+
+    /// Returns the fresh value under `key`, or stores the one built by `make`
+    /// for `ttl`.
+    pub fn get_or_insert_with(
+        &self,
+        key: &str,
+        ttl: tokio::time::Duration,
+        make: impl FnOnce() -> V,
+    ) -> V {
+        let now = time::Instant::now();
+
+        let entry = self.map.entry(key.to_string());
+        if let Entry::Occupied(entry) = &entry {
+            let (expiration, value) = entry.get();
+            if *expiration > now {
+                return value.clone();
+            }
+        }
+
+        let value = make();
+        entry.insert((now + ttl, value.clone()));
+        value
+    }
+}
+
+impl<V> Drop for TtlCache<V> {
+    fn drop(&mut self) {
+        self.janitor.abort();
+    }
+}
@@ -39,6 +39,10 @@ impl TtlSet {
         entry.insert(now + ttl);
         true
     }
+
+    pub fn remove(&self, key: &str) {
+        self.map.remove(key);
+    }
 }
 
 impl Drop for TtlSet {